@@ -217,6 +217,11 @@ async function processEpoch(
     [Buffer.from("config")],
     program.programId
   );
+  // Classement de l'époque, dont chaque proposition finalisée sort
+  const [leaderboardPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard"), epochId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
 
   const programAccounts = program.account as unknown as ProgramAccounts;
 
@@ -277,6 +282,7 @@ async function processEpoch(
               programConfig: programConfigPda,
               epochManagement: epochPda,
               proposal: proposalInfo.publicKey,
+              leaderboard: leaderboardPda,
             })
             .signers([adminAuthority])
             .rpc();
@@ -305,6 +311,7 @@ async function processEpoch(
               programConfig: programConfigPda,
               epochManagement: epochPda,
               proposal: proposalInfo.publicKey,
+              leaderboard: leaderboardPda,
             })
             .signers([adminAuthority])
             .rpc();
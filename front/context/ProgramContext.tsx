@@ -4,6 +4,7 @@ import {
   getProgramContext,
   AnchorWallet as UtilsAnchorWallet,
 } from "@/lib/utils";
+import { buildReclaimManyTransactions } from "@/lib/reclaimMany";
 import { BN, Program } from "@coral-xyz/anchor";
 import { useAnchorWallet, useConnection } from "@solana/wallet-adapter-react";
 import { LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
@@ -86,6 +87,7 @@ type ProgramContextType = {
   ) => Promise<ProposalState[]>;
   getProposalSupports: (proposalId: string) => Promise<ProposalSupport[]>;
   reclaimSupport: (proposal: PublicKey, epochId: number) => Promise<string>;
+  reclaimMany: (proposals: PublicKey[], epochId: number) => Promise<string[]>;
   getProposalsByEpoch: (epochId: string) => Promise<ProposalState[]>;
 };
 
@@ -636,6 +638,35 @@ export function ProgramProvider({ children }: { children: React.ReactNode }) {
     [program, wallet]
   );
 
+  const reclaimMany = useCallback(
+    async (proposals: PublicKey[], epochId: number) => {
+      if (!wallet?.publicKey || !program)
+        throw new Error("Wallet not connected");
+
+      try {
+        const transactions = await buildReclaimManyTransactions(
+          program as Program,
+          wallet.publicKey,
+          new BN(epochId),
+          proposals
+        );
+        const signed = await wallet.signAllTransactions(transactions);
+
+        const signatures: string[] = [];
+        for (const tx of signed) {
+          const signature = await connection.sendRawTransaction(tx.serialize());
+          await connection.confirmTransaction(signature, "confirmed");
+          signatures.push(signature);
+        }
+        return signatures;
+      } catch (error) {
+        console.error("Error reclaiming supports:", error);
+        throw error;
+      }
+    },
+    [program, wallet, connection]
+  );
+
   // --- Context Value & Provider ---
   const value = useMemo(
     () => ({
@@ -655,6 +686,7 @@ export function ProgramProvider({ children }: { children: React.ReactNode }) {
       getUserSupportedProposals,
       getProposalSupports,
       reclaimSupport,
+      reclaimMany,
       getProposalsByEpoch,
     }),
     [
@@ -672,6 +704,7 @@ export function ProgramProvider({ children }: { children: React.ReactNode }) {
       getUserSupportedProposals,
       getProposalSupports,
       reclaimSupport,
+      reclaimMany,
       getProposalsByEpoch,
     ]
   );
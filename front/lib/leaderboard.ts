@@ -0,0 +1,37 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+
+// Lecture du classement d'une époque (compte EpochLeaderboard).
+// Module sans alias "@/" pour pouvoir être réutilisé par les tests du programme.
+
+export interface LeaderboardEntry {
+  proposal: PublicKey;
+  solRaised: BN;
+}
+
+/**
+ * Lit le classement d'une époque, de la mieux classée à la moins bien classée.
+ * Le classement est approximatif une fois plein : une proposition dont le montant baisse garde
+ * sa place jusqu'au prochain support d'une proposition non suivie qui la dépasse. À afficher
+ * comme un aperçu ; le résultat de l'époque se lit sur les TokenProposal. Une fois l'époque
+ * fermée, les propositions finalisées par le crank en sortent.
+ * @param program Le programme Anchor.
+ * @param epochId L'ID de l'époque.
+ * @returns Les entrées du classement, ou null si l'époque n'a pas (encore) de classement.
+ */
+export async function fetchEpochLeaderboard(
+  program: Program<any>,
+  epochId: BN
+): Promise<LeaderboardEntry[] | null> {
+  const [leaderboardPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard"), epochId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+  const leaderboard = await (program.account as any).epochLeaderboard.fetchNullable(
+    leaderboardPda
+  );
+  if (!leaderboard) {
+    return null;
+  }
+  return (leaderboard.entries as LeaderboardEntry[]).slice(0, leaderboard.len);
+}
@@ -0,0 +1,125 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  PACKET_DATA_SIZE,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+
+// Client reclaim_many : regroupe les réclamations d'un utilisateur sur plusieurs
+// propositions rejetées/annulées d'une même époque en un minimum de transactions.
+// Module sans alias "@/" pour pouvoir être réutilisé par les tests du programme.
+
+/**
+ * Nombre maximum de triplets (proposition, vault, support) acceptés par reclaim_many,
+ * lu dans les constantes de l'IDL (MAX_RECLAIM_MANY_PAIRS côté programme).
+ */
+export function getMaxReclaimManyPairs(program: Program<any>): number {
+  const constant = program.idl.constants?.find(
+    (c) => c.name === "MAX_RECLAIM_MANY_PAIRS"
+  );
+  if (!constant) {
+    throw new Error("MAX_RECLAIM_MANY_PAIRS is missing from the IDL constants");
+  }
+  return Number(constant.value);
+}
+
+const epochIdSeed = (epochId: BN) => epochId.toArrayLike(Buffer, "le", 8);
+
+/**
+ * Construit les transactions reclaim_many pour une liste de propositions d'une même époque.
+ * Chaque transaction contient autant de triplets que la taille maximale d'un paquet
+ * et la limite du programme le permettent.
+ * @param program Le programme Anchor (son provider fournit la connexion).
+ * @param user La clé publique de l'utilisateur qui réclame (payeur des transactions).
+ * @param epochId L'ID de l'époque commune aux propositions.
+ * @param proposalPdas Les PDAs des propositions à réclamer.
+ * @returns Les transactions à signer par l'utilisateur, avec un blockhash récent.
+ */
+export async function buildReclaimManyTransactions(
+  program: Program<any>,
+  user: PublicKey,
+  epochId: BN,
+  proposalPdas: PublicKey[]
+): Promise<Transaction[]> {
+  const maxPairs = getMaxReclaimManyPairs(program);
+  const programId = program.programId;
+  const [epochManagementPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("epoch"), epochIdSeed(epochId)],
+    programId
+  );
+  const [programConfigPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    programId
+  );
+  const [treasuryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    programId
+  );
+  const { blockhash } = await program.provider.connection.getLatestBlockhash();
+
+  const buildTransaction = async (batch: PublicKey[]): Promise<Transaction> => {
+    const remainingAccounts = batch.flatMap((proposalPda) => [
+      { pubkey: proposalPda, isWritable: true, isSigner: false },
+      {
+        pubkey: PublicKey.findProgramAddressSync(
+          [Buffer.from("proposal_vault"), proposalPda.toBuffer()],
+          programId
+        )[0],
+        isWritable: true,
+        isSigner: false,
+      },
+      {
+        pubkey: PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("support"),
+            epochIdSeed(epochId),
+            user.toBuffer(),
+            proposalPda.toBuffer(),
+          ],
+          programId
+        )[0],
+        isWritable: true,
+        isSigner: false,
+      },
+    ]);
+    const instruction = await program.methods
+      .reclaimMany()
+      .accounts({
+        user,
+        epochManagement: epochManagementPda,
+        programConfig: programConfigPda,
+        treasury: treasuryPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .instruction();
+    return new Transaction({ feePayer: user, recentBlockhash: blockhash }).add(
+      instruction
+    );
+  };
+  const fits = (tx: Transaction) =>
+    tx.serialize({ requireAllSignatures: false, verifySignatures: false })
+      .length <= PACKET_DATA_SIZE;
+
+  const transactions: Transaction[] = [];
+  let batch: PublicKey[] = [];
+  for (const proposalPda of proposalPdas) {
+    const candidate = [...batch, proposalPda];
+    if (candidate.length <= maxPairs && fits(await buildTransaction(candidate))) {
+      batch = candidate;
+      continue;
+    }
+    if (batch.length === 0) {
+      throw new Error(
+        `Proposal ${proposalPda.toBase58()} does not fit in a reclaim_many transaction`
+      );
+    }
+    transactions.push(await buildTransaction(batch));
+    batch = [proposalPda];
+  }
+  if (batch.length > 0) {
+    transactions.push(await buildTransaction(batch));
+  }
+  return transactions;
+}
@@ -21,5 +21,132 @@ pub const TREASURY_DISTRIBUTION_OPERATIONS_PERCENT: u8 = 5;  // 5%
 pub const TREASURY_DISTRIBUTION_INVESTMENTS_PERCENT: u8 = 44; // 44%
 pub const TREASURY_DISTRIBUTION_CRANK_PERCENT: u8 = 1;       // 1%
 
+// --- Validation des propositions ---
+// Longueurs maximales, identiques aux #[max_len] de TokenProposal
+pub const MAX_TOKEN_NAME_LENGTH: usize = 32;
+pub const MAX_TOKEN_SYMBOL_LENGTH: usize = 8;
+pub const MAX_DESCRIPTION_LENGTH: usize = 512;
+pub const MAX_IMAGE_URL_LENGTH: usize = 256;
+// Schémas acceptés pour l'URL de l'image
+pub const ALLOWED_IMAGE_URL_SCHEMES: [&str; 2] = ["https://", "ipfs://"];
+// Supply maximale d'un token (1 000 milliards)
+pub const MAX_TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000;
+// Bornes de la période de lockup du créateur : 0 (pas de lockup), sinon de 1 jour à 1 an
+pub const MIN_LOCKUP_PERIOD_SECONDS: i64 = 24 * 60 * 60;
+pub const MAX_LOCKUP_PERIOD_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+// --- Récompenses du crank (valeurs par défaut de ProgramConfig) ---
+// Récompense par appel de maintenance (0.00001 SOL, soit environ deux frais de transaction)
+pub const DEFAULT_CRANK_REWARD_LAMPORTS: u64 = 10_000;
+// Plafond des récompenses versées pour une même époque (0.001 SOL)
+pub const DEFAULT_CRANK_REWARD_EPOCH_CAP: u64 = 1_000_000;
+
+// --- Plafonds de support (valeurs par défaut de ProgramConfig, 0 = illimité) ---
+pub const DEFAULT_MAX_SUPPORT_PER_WALLET_PER_PROPOSAL: u64 = 0;
+pub const DEFAULT_MAX_SUPPORT_PER_WALLET_PER_EPOCH: u64 = 0;
+pub const DEFAULT_MAX_SOL_RAISED_PER_PROPOSAL: u64 = 0;
+
+// --- Seuils d'éligibilité au lancement (valeurs par défaut de ProgramConfig, 0 = désactivé) ---
+pub const DEFAULT_MIN_SOL_RAISED_FOR_LAUNCH: u64 = 0;
+pub const DEFAULT_MIN_CONTRIBUTORS_FOR_LAUNCH: u64 = 0;
+
+// --- Retrait de support ---
+// Pénalité de sortie par défaut (points de base, 0 = aucune pénalité)
+pub const DEFAULT_UNSUPPORT_PENALTY_BPS: u16 = 0;
+// Pénalité de sortie maximale configurable (50%)
+pub const MAX_UNSUPPORT_PENALTY_BPS: u16 = 5_000;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// --- Remboursement des frais de support ---
+// Part des frais remboursée par défaut lors d'un reclaim (points de base, 0 = aucun remboursement)
+pub const DEFAULT_SUPPORT_FEE_REFUND_BPS: u16 = 0;
+
+// --- Parrainage ---
+// Part des frais de support reversée au parrain par défaut (points de base, 0 = désactivé)
+pub const DEFAULT_REFERRAL_FEE_BPS: u16 = 0;
+// Part maximale configurable (50% des frais)
+pub const MAX_REFERRAL_FEE_BPS: u16 = 5_000;
+pub const REFERRAL_SEED: &[u8] = b"referral";
+
+// --- Caution des créateurs ---
+// Caution par défaut déposée à la création d'une proposition (0 = désactivée)
+pub const DEFAULT_CREATOR_BOND_LAMPORTS: u64 = 0;
+// Seuil de levée par défaut rendant la caution avant le lancement (0 = au lancement seulement)
+pub const DEFAULT_CREATOR_BOND_MIN_RAISE: u64 = 0;
+pub const CREATOR_BOND_SEED: &[u8] = b"creator_bond";
+
+// --- Modération ---
+pub const MODERATION_SEED: &[u8] = b"moderation";
+
+// --- Listes d'autorisation des époques ---
+// Préfixes de domaine des hachages keccak de l'arbre de Merkle (feuille = portefeuille, nœud = paire triée)
+pub const ALLOWLIST_LEAF_PREFIX: &[u8] = &[0];
+pub const ALLOWLIST_NODE_PREFIX: &[u8] = &[1];
+
+// --- Configuration propre à une époque ---
+// Nombre maximum de créateurs autorisés listés dans EpochConfig
+pub const MAX_EPOCH_ALLOWED_CREATORS: usize = 10;
+// Frais de support maximum d'une époque (10%)
+pub const MAX_EPOCH_SUPPORT_FEE_BPS: u16 = 1_000;
+
+// --- Réclamation groupée ---
+// Nombre maximum de propositions (triplets proposition, vault, support) traitées par reclaim_many.
+// Exposé dans l'IDL pour que le client découpe ses transactions en conséquence.
+#[constant]
+pub const MAX_RECLAIM_MANY_PAIRS: u8 = 10;
+
+// --- Archivage des époques ---
+// Délai par défaut entre le traitement d'une époque et son archivage (30 jours)
+pub const DEFAULT_EPOCH_ARCHIVE_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+// Nombre d'époques conservées dans l'historique glissant
+pub const EPOCH_HISTORY_CAPACITY: usize = 32;
+pub const EPOCH_HISTORY_SEED: &[u8] = b"epoch_history";
+
+// --- Classement des propositions ---
+// Nombre de propositions suivies par le classement zero-copy de chaque époque
+pub const LEADERBOARD_CAPACITY: usize = 64;
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+
+// --- Versions du format des comptes ---
+// À incrémenter à chaque ajout de champ, avec l'étape correspondante dans utils/migration.rs.
+// La version 0 désigne les comptes créés avant l'introduction du champ `version`.
+pub const PROGRAM_CONFIG_VERSION: u8 = 3; // v2 : referral_fee_bps, v3 : caution des créateurs
+pub const TREASURY_VERSION: u8 = 1;
+pub const EPOCH_MANAGEMENT_VERSION: u8 = 3; // v2 : liste d'autorisation Merkle, v3 : EpochConfig
+pub const USER_PROPOSAL_SUPPORT_VERSION: u8 = 1; // v1 : fees_paid
+pub const TOKEN_PROPOSAL_VERSION: u8 = 2; // v2 : textes dans ProposalMetadata, SOL dans le vault
+pub const TREASURY_ROLES_VERSION: u8 = 1; // v1 : taille calculée par InitSpace
+// Compteurs de propositions inconnus d'une époque migrée depuis la version 0 (créée avant leur
+// introduction) : l'époque ne peut ni recevoir de proposition ni être archivée avant que l'admin
+// ne les renseigne (backfill_legacy_epoch).
+pub const LEGACY_EPOCH_UNKNOWN_PROPOSALS: u64 = u64::MAX;
+// Comptes créés directement avec un champ `version` : la version 1 est leur format initial
+pub const USER_EPOCH_SUPPORT_VERSION: u8 = 1;
+pub const PROPOSAL_METADATA_VERSION: u8 = 1;
+pub const SYMBOL_REGISTRY_VERSION: u8 = 1;
+pub const REFERRAL_ACCOUNT_VERSION: u8 = 1;
+pub const CREATOR_BOND_VERSION: u8 = 1;
+pub const PROPOSAL_MODERATION_VERSION: u8 = 1;
+pub const EPOCH_LEADERBOARD_VERSION: u8 = 1;
+pub const EPOCH_HISTORY_VERSION: u8 = 1;
+pub const SPENDING_PROPOSAL_VERSION: u8 = 1;
+pub const SPENDING_VOTE_RECORD_VERSION: u8 = 1;
+pub const PAYMENT_STREAM_VERSION: u8 = 1;
+
+// Seed pour le PDA ProposalMetadata
+pub const PROPOSAL_METADATA_SEED: &[u8] = b"proposal_metadata";
+
+// Seed pour le vault SOL (compte système) de chaque proposition
+pub const PROPOSAL_VAULT_SEED: &[u8] = b"proposal_vault";
+
 // Seed pour le PDA Treasury
 pub const TREASURY_SEED: &[u8] = b"treasury";
+
+
+// --- Propositions de dépenses de la trésorerie ---
+// Durée de la fenêtre de vote (3 jours)
+pub const SPENDING_PROPOSAL_VOTING_PERIOD_SECONDS: i64 = 3 * 24 * 60 * 60;
+// Poids total minimum (en lamports soutenus) pour que le vote soit valide (10 SOL)
+pub const SPENDING_PROPOSAL_QUORUM_LAMPORTS: u64 = 10_000_000_000;
+// Nombre maximum de comptes de support pris en compte dans un seul vote
+pub const SPENDING_PROPOSAL_MAX_SUPPORT_ACCOUNTS_PER_VOTE: usize = 10;
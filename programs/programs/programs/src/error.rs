@@ -67,7 +67,7 @@ pub enum ErrorCode {
     Unauthorized,
 
     // --- Codes pour reclaim_support ---
-    #[msg("La proposition doit être rejetée pour pouvoir réclamer les fonds.")]
+    #[msg("La proposition doit être rejetée ou annulée pour pouvoir réclamer les fonds.")]
     ProposalNotRejected,
     
     #[msg("Le compte de support ne correspond pas à la proposition fournie.")]
@@ -102,5 +102,215 @@ pub enum ErrorCode {
 
     #[msg("Epoch has not yet been marked as processed by the crank.")]
     EpochNotProcessedYet,
-}
 
+    // --- Codes pour les propositions de dépenses de la trésorerie ---
+    #[msg("Insufficient balance in the treasury sub-account.")]
+    InsufficientTreasuryBalance,
+
+    #[msg("The spending description is too long.")]
+    SpendingDescriptionTooLong,
+
+    #[msg("The requested amount exceeds the withdrawal limit of the role.")]
+    WithdrawalLimitExceeded,
+
+    #[msg("The voting window of this spending proposal is closed.")]
+    VotingClosed,
+
+    #[msg("The voting window of this spending proposal is still open.")]
+    VotingStillOpen,
+
+    #[msg("This spending proposal has already been finalized.")]
+    SpendingProposalAlreadyFinalized,
+
+    #[msg("Remaining accounts must be (user_proposal_support, epoch_management) pairs.")]
+    InvalidRemainingAccounts,
+
+    #[msg("The support account is not eligible to weight this vote.")]
+    IneligibleSupportAccount,
+
+    #[msg("The same support account was provided more than once.")]
+    DuplicateSupportAccount,
+
+    #[msg("The voter has no eligible historic support.")]
+    NoVotingWeight,
+
+    #[msg("The recipient account does not match the spending proposal.")]
+    InvalidRecipient,
+
+    // --- Codes pour les flux de paiement ---
+    #[msg("The stream end time must be in the future.")]
+    InvalidStreamTimeRange,
+
+    #[msg("There is nothing to claim on this stream yet.")]
+    NothingToClaim,
+
+    #[msg("This stream has already ended.")]
+    StreamAlreadyEnded,
+
+    #[msg("The crank reward cannot exceed the per-epoch cap.")]
+    InvalidCrankRewardConfig,
+
+    // --- Codes pour la gestion d'une proposition par son créateur ---
+    #[msg("Only the creator of the proposal can perform this action.")]
+    NotProposalCreator,
+
+    #[msg("Economic fields can only be changed before the first support.")]
+    EconomicFieldsLocked,
+
+    #[msg("A new image cannot be set while clearing the current one.")]
+    ConflictingImageUpdate,
+
+    #[msg("The description is too long.")]
+    DescriptionTooLong,
+
+    #[msg("The image URL is too long.")]
+    ImageUrlTooLong,
+
+    // --- Codes pour la validation des propositions ---
+    #[msg("The token name cannot be empty.")]
+    TokenNameEmpty,
+
+    #[msg("The token symbol cannot be empty.")]
+    TokenSymbolEmpty,
+
+    #[msg("The token symbol can only contain letters and digits.")]
+    InvalidTokenSymbol,
+
+    #[msg("The image URL must start with https:// or ipfs://.")]
+    InvalidImageUrl,
+
+    #[msg("The total supply must be greater than zero.")]
+    InvalidTotalSupply,
+
+    #[msg("The total supply is too high.")]
+    TotalSupplyTooHigh,
+
+    #[msg("The lockup period is too short.")]
+    LockupPeriodTooShort,
+
+    #[msg("The lockup period is too long.")]
+    LockupPeriodTooLong,
+
+    // --- Codes pour le registre des symboles ---
+    #[msg("This symbol is already used by another proposal in this epoch.")]
+    SymbolAlreadyTaken,
+
+    #[msg("This symbol is already used by a launched token.")]
+    SymbolAlreadyLaunched,
+
+    #[msg("The symbol registry does not belong to this proposal.")]
+    SymbolRegistryMismatch,
+
+    #[msg("The proposal must be validated to register its symbol as launched.")]
+    ProposalNotValidated,
+
+    // --- Codes pour les plafonds de support ---
+    #[msg("This support exceeds the maximum a wallet can commit to a proposal.")]
+    WalletProposalSupportCapExceeded,
+
+    #[msg("This support exceeds the maximum a wallet can commit during an epoch.")]
+    WalletEpochSupportCapExceeded,
+
+    #[msg("This support exceeds the maximum SOL a proposal can raise.")]
+    ProposalSolRaisedCapExceeded,
+
+    // --- Codes pour le retrait de support ---
+    #[msg("The amount exceeds the current support of the user for this proposal.")]
+    AmountExceedsSupport,
+
+    #[msg("The unsupport penalty exceeds the maximum allowed.")]
+    InvalidUnsupportPenalty,
+
+    #[msg("Support cannot be reallocated to the same proposal.")]
+    SameProposalReallocation,
+
+    // --- Codes pour le remboursement des frais de support ---
+    #[msg("The support fee refund share cannot exceed 100%.")]
+    InvalidSupportFeeRefund,
+
+    // --- Codes pour la réclamation groupée ---
+    #[msg("Remaining accounts must be between 1 and 10 (token_proposal, proposal_vault, user_proposal_support) triples.")]
+    InvalidReclaimManyAccounts,
+
+    // --- Codes pour la fermeture des propositions ---
+    #[msg("All the SOL raised must be refunded before the proposal can be closed.")]
+    ProposalNotFullyRefunded,
+
+    // --- Codes pour l'archivage des époques ---
+    #[msg("The grace period must not be negative.")]
+    InvalidGracePeriod,
+
+    #[msg("The grace period after processing has not elapsed yet.")]
+    EpochGracePeriodNotElapsed,
+
+    #[msg("Some proposals of this epoch are neither launched nor closed.")]
+    EpochHasOpenProposals,
+
+    // --- Codes pour la migration des comptes ---
+    #[msg("The account is already at the current version.")]
+    AccountAlreadyMigrated,
+
+    #[msg("The account version is newer than supported by this program.")]
+    UnsupportedAccountVersion,
+
+    #[msg("The proposal counters of this legacy epoch must be backfilled by the admin first.")]
+    LegacyEpochNotBackfilled,
+
+    #[msg("The proposal counters of this epoch are already known.")]
+    EpochAlreadyBackfilled,
+
+    #[msg("The backfilled epoch counters are inconsistent.")]
+    InvalidEpochCounters,
+
+    // --- Codes pour le vault des propositions ---
+    #[msg("The proposal vault holds less SOL than the outstanding support.")]
+    VaultAccountingMismatch,
+
+    // --- Codes pour le parrainage ---
+    #[msg("The referral fee must not exceed the configured maximum.")]
+    InvalidReferralFee,
+
+    #[msg("A supporter cannot refer themselves.")]
+    SelfReferral,
+
+    #[msg("The creator of the proposal cannot be the referrer.")]
+    ReferrerIsCreator,
+
+    // --- Codes pour la caution des créateurs ---
+    #[msg("The creator bond cannot be settled yet: the proposal is still active below the release threshold.")]
+    CreatorBondNotSettleable,
+
+    // --- Codes pour la modération ---
+    #[msg("The proposal is not flagged.")]
+    ProposalNotFlagged,
+
+    #[msg("A flagged proposal cannot be finalized; remove or unflag it first.")]
+    ProposalIsFlagged,
+
+    #[msg("The creator has already appealed this flag.")]
+    AppealAlreadyFiled,
+
+    // --- Codes pour les listes d'autorisation ---
+    #[msg("The wallet is not on the allowlist of this epoch.")]
+    NotAllowlisted,
+
+    // --- Codes pour la configuration des époques ---
+    #[msg("The epoch configuration is invalid.")]
+    InvalidEpochConfig,
+
+    #[msg("The creator is not allowed to propose in this epoch.")]
+    CreatorNotAllowedInEpoch,
+
+    #[msg("A proposal that raised more SOL must be finalized first.")]
+    ProposalOutOfRankOrder,
+
+    // --- Codes pour la planification des époques ---
+    #[msg("The epoch is not pending activation.")]
+    EpochNotPending,
+
+    #[msg("The epoch start time has not been reached yet.")]
+    EpochNotStarted,
+
+    #[msg("The epoch end time has passed; end it instead of activating it.")]
+    EpochWindowElapsed,
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{EpochManagement, EpochStatus, ProgramConfig, Treasury};
+use crate::error::ErrorCode;
+use crate::constants::TREASURY_SEED;
+use crate::utils::pay_crank_reward;
+
+// Passe une époque planifiée (Pending) à Active une fois son start_time atteint.
+// Sans permission : n'importe quel crank peut l'appeler et reçoit la récompense de maintenance.
+// Une époque dont end_time est déjà passé n'est plus activable : end_epoch la ferme directement.
+pub fn handler(ctx: Context<ActivateEpoch>, epoch_id: u64) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let epoch = &mut ctx.accounts.epoch_management;
+
+    require!(
+        epoch.status == EpochStatus::Pending,
+        ErrorCode::EpochNotPending
+    );
+    require!(
+        current_time >= epoch.start_time,
+        ErrorCode::EpochNotStarted
+    );
+    require!(
+        current_time < epoch.end_time,
+        ErrorCode::EpochWindowElapsed
+    );
+
+    epoch.status = EpochStatus::Active;
+
+    pay_crank_reward(
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.program_config,
+        epoch,
+        &ctx.accounts.authority.to_account_info(),
+    )?;
+
+    emit!(EpochActivated {
+        epoch_id,
+        activated_at: current_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(epoch_id: u64)]
+pub struct ActivateEpoch<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct EpochActivated {
+    pub epoch_id: u64,
+    pub activated_at: i64,
+}
@@ -0,0 +1,109 @@
+// Archivage d'une époque : une fois le délai de grâce écoulé après son traitement et toutes ses
+// propositions lancées ou fermées, plus aucun reclaim ne dépend du compte EpochManagement.
+// N'importe quel crank peut alors le fermer avec son classement (rentes rendues à l'admin qui
+// les a créés) ; un résumé compact est conservé dans l'historique glissant des époques. Ce résumé
+// remplace le compte d'époque pour le poids des votes de dépenses, tant qu'il n'est pas évincé.
+
+use anchor_lang::prelude::*;
+use crate::constants::{EPOCH_HISTORY_CAPACITY, EPOCH_HISTORY_SEED, EPOCH_HISTORY_VERSION, LEADERBOARD_SEED, LEGACY_EPOCH_UNKNOWN_PROPOSALS, TREASURY_SEED};
+use crate::error::ErrorCode;
+use crate::state::{EpochHistory, EpochLeaderboard, EpochManagement, EpochSummary, ProgramConfig, Treasury};
+use crate::utils::pay_crank_reward;
+
+#[derive(Accounts)]
+pub struct ArchiveEpoch<'info> {
+    // N'importe quel crank ; paie la création de l'historique si besoin et reçoit la récompense
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", epoch_management.epoch_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_management.processed @ ErrorCode::EpochNotProcessedYet,
+        constraint = epoch_management.proposals_count != LEGACY_EPOCH_UNKNOWN_PROPOSALS @ ErrorCode::LegacyEpochNotBackfilled,
+        constraint = epoch_management.proposals_settled >= epoch_management.proposals_count @ ErrorCode::EpochHasOpenProposals,
+        close = admin_authority
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    // Le classement de l'époque n'a plus d'usage une fois l'époque archivée
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, epoch_management.epoch_id.to_le_bytes().as_ref()],
+        bump,
+        close = admin_authority
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + EpochHistory::INIT_SPACE,
+        seeds = [EPOCH_HISTORY_SEED],
+        bump
+    )]
+    pub epoch_history: Account<'info, EpochHistory>,
+
+    /// CHECK: Reçoit la rente des comptes d'époque et de classement ; doit être l'admin du programme (payeur de start_epoch).
+    #[account(
+        mut,
+        address = program_config.admin_authority @ ErrorCode::Unauthorized
+    )]
+    pub admin_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ArchiveEpoch>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let epoch = &ctx.accounts.epoch_management;
+
+    let archivable_at = epoch.processed_at
+        .checked_add(ctx.accounts.program_config.epoch_archive_grace_period)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    require!(now >= archivable_at, ErrorCode::EpochGracePeriodNotElapsed);
+
+    // --- Résumé dans l'historique glissant ---
+    let history = &mut ctx.accounts.epoch_history;
+    history.version = EPOCH_HISTORY_VERSION;
+    if history.summaries.len() >= EPOCH_HISTORY_CAPACITY {
+        history.summaries.remove(0);
+    }
+    history.summaries.push(EpochSummary {
+        epoch_id: epoch.epoch_id,
+        start_time: epoch.start_time,
+        end_time: epoch.end_time,
+        processed_at: epoch.processed_at,
+        winners_count: epoch.winners_count,
+        total_raised: epoch.total_raised,
+    });
+    history.total_archived = history.total_archived.checked_add(1)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("Epoch {} archived: {} winners out of {} proposals, {} lamports raised",
+        epoch.epoch_id, epoch.winners_count, epoch.proposals_count, epoch.total_raised);
+
+    pay_crank_reward(
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.epoch_management,
+        &ctx.accounts.authority.to_account_info(),
+    )?;
+
+    // Le compte d'époque et son classement sont fermés via `close = admin_authority`
+    Ok(())
+}
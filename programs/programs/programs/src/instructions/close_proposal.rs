@@ -0,0 +1,162 @@
+// Fermeture d'une proposition rejetée, annulée ou retirée par la modération, une fois que tous ses supporters ont été
+// remboursés (sol_refunded >= sol_raised). N'importe qui peut la déclencher : la rente du compte,
+// de ses métadonnées et la réserve du vault reviennent au créateur, le surplus du vault (dépôts
+// directs) est balayé vers la trésorerie et le signataire reçoit une récompense de crank.
+// Une caution du créateur et une réservation de symbole encore ouvertes sont réglées au passage.
+
+use anchor_lang::prelude::*;
+use crate::constants::{CREATOR_BOND_SEED, PROPOSAL_METADATA_SEED, PROPOSAL_VAULT_SEED, TREASURY_SEED};
+use crate::error::ErrorCode;
+use crate::state::{EpochManagement, ProgramConfig, ProposalMetadata, ProposalStatus, SymbolRegistry, TokenProposal, Treasury};
+use crate::utils::{check_vault_accounting, distribute_fees_to_treasury, normalize_symbol, pay_crank_reward, settle_creator_bond, transfer_from_vault, FeeType};
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    // N'importe quel crank ; reçoit la récompense
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", token_proposal.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        constraint = matches!(token_proposal.status, ProposalStatus::Rejected | ProposalStatus::Cancelled | ProposalStatus::Removed) @ ErrorCode::ProposalNotRejected,
+        constraint = token_proposal.sol_refunded >= token_proposal.sol_raised @ ErrorCode::ProposalNotFullyRefunded,
+        close = creator
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    // Les textes de la proposition sont fermés avec elle
+    #[account(
+        mut,
+        seeds = [PROPOSAL_METADATA_SEED, token_proposal.key().as_ref()],
+        bump,
+        close = creator
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
+    // Vault de la proposition, vidé à la fermeture
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_vault: SystemAccount<'info>,
+
+    /// CHECK: Caution du créateur ; vide si déjà réglée (ou proposition antérieure aux cautions),
+    /// sinon vérifiée et réglée par settle_creator_bond.
+    #[account(
+        mut,
+        seeds = [CREATOR_BOND_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub creator_bond: UncheckedAccount<'info>,
+
+    /// CHECK: Réservation du symbole pour l'époque ; vide si déjà libérée (release_proposal_symbol,
+    /// cancel_proposal), sinon vérifiée et fermée au profit du créateur dans le handler.
+    #[account(
+        mut,
+        seeds = [
+            b"epoch_symbol",
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            normalize_symbol(&token_proposal.token_symbol).as_bytes()
+        ],
+        bump
+    )]
+    pub epoch_symbol: UncheckedAccount<'info>,
+
+    /// CHECK: Reçoit la rente du compte ; doit être le créateur de la proposition.
+    #[account(
+        mut,
+        address = token_proposal.creator @ ErrorCode::NotProposalCreator
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CloseProposal>) -> Result<()> {
+    // Une fois tout remboursé, le vault ne contient plus que sa réserve de rente et un éventuel surplus
+    let vault_info = ctx.accounts.proposal_vault.to_account_info();
+    let residual = check_vault_accounting(&vault_info, &ctx.accounts.token_proposal)?;
+    let reserve = vault_info.lamports().checked_sub(residual)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    let proposal_key = ctx.accounts.token_proposal.key();
+    let vault_bump = ctx.bumps.proposal_vault;
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    if residual > 0 {
+        transfer_from_vault(&vault_info, vault_bump, &proposal_key,
+            &ctx.accounts.treasury.to_account_info(), &system_program_info, residual)?;
+        distribute_fees_to_treasury(&mut ctx.accounts.treasury, residual, FeeType::ProposalResidual)?;
+    }
+    transfer_from_vault(&vault_info, vault_bump, &proposal_key,
+        &ctx.accounts.creator.to_account_info(), &system_program_info, reserve)?;
+
+    let bond_info = ctx.accounts.creator_bond.to_account_info();
+    if !bond_info.data_is_empty() {
+        settle_creator_bond(
+            &bond_info,
+            &ctx.accounts.token_proposal,
+            &ctx.accounts.epoch_management,
+            &ctx.accounts.creator.to_account_info(),
+            &mut ctx.accounts.treasury,
+        )?;
+    }
+
+    let symbol_info = ctx.accounts.epoch_symbol.to_account_info();
+    if !symbol_info.data_is_empty() {
+        let epoch_symbol = SymbolRegistry::try_deserialize(&mut &symbol_info.try_borrow_data()?[..])?;
+        require_keys_eq!(epoch_symbol.proposal, proposal_key, ErrorCode::SymbolRegistryMismatch);
+        let rent = symbol_info.lamports();
+        **symbol_info.try_borrow_mut_lamports()? = 0;
+        **ctx.accounts.creator.to_account_info().try_borrow_mut_lamports()? += rent;
+        symbol_info.assign(&System::id());
+        symbol_info.realloc(0, false)?;
+    }
+
+    let epoch = &mut ctx.accounts.epoch_management;
+    epoch.proposals_settled = epoch.proposals_settled.checked_add(1)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("Proposal {} closed ({:?}): {} lamports refunded to supporters, residual of {} lamports swept to treasury",
+        ctx.accounts.token_proposal.key(),
+        ctx.accounts.token_proposal.status,
+        ctx.accounts.token_proposal.sol_refunded,
+        residual);
+
+    pay_crank_reward(
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.epoch_management,
+        &ctx.accounts.authority.to_account_info(),
+    )?;
+
+    // La rente restante revient au créateur via `close = creator`
+    Ok(())
+}
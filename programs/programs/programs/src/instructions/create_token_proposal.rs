@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::ErrorCode;
 use crate::constants::*;
-use crate::utils::{distribute_fees_to_treasury, FeeType};
+use crate::utils::{distribute_fees_to_treasury, require_allowlisted, vault_rent_reserve, FeeType};
+use crate::utils::proposal_validation::*;
 
 #[derive(Accounts)]
 #[instruction(
@@ -25,17 +26,84 @@ pub struct CreateTokenProposal<'info> {
             b"proposal", // Static seed prefix
             creator.key().as_ref(),
             epoch.epoch_id.to_le_bytes().as_ref(), 
-            token_name.as_bytes()
+            token_name_seed(&token_name)?
         ],
         bump
     )]
     pub token_proposal: Account<'info, TokenProposal>,
 
+    // Textes de la proposition, dans un PDA séparé de l'état économique
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ProposalMetadata::INIT_SPACE,
+        seeds = [PROPOSAL_METADATA_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
+    // Vault SOL de la proposition, alimenté ici de sa réserve de rente
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_vault: SystemAccount<'info>,
+
+    // Caution du créateur, détenue par ce PDA jusqu'à l'issue de la proposition
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorBond::INIT_SPACE,
+        seeds = [CREATOR_BOND_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+
+    // Configuration globale (montant de la caution)
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
     // We need the epoch account to check status and use its ID in seeds
     // Constraint example: ensure epoch is active
     // #[account(constraint = epoch.status == EpochStatus::Active @ CustomError::EpochNotActive)]
+    // Mutable pour compter les propositions de l'époque
+    #[account(mut)]
     pub epoch: Account<'info, EpochManagement>,
 
+    // Réservation du symbole pour l'époque : un même symbole (insensible à la casse)
+    // ne peut être proposé qu'une fois par époque
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + SymbolRegistry::INIT_SPACE,
+        seeds = [
+            b"epoch_symbol",
+            epoch.epoch_id.to_le_bytes().as_ref(),
+            normalize_symbol(&token_symbol).as_bytes()
+        ],
+        bump
+    )]
+    pub epoch_symbol: Account<'info, SymbolRegistry>,
+
+    /// CHECK: Registre global des symboles déjà lancés ; le compte doit être vide (symbole libre).
+    #[account(
+        seeds = [b"launched_symbol", normalize_symbol(&token_symbol).as_bytes()],
+        bump
+    )]
+    pub launched_symbol: UncheckedAccount<'info>,
+
+    // Classement de l'époque, la proposition y est inscrite à 0 SOL
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, epoch.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
     #[account(
         mut,
         seeds = [TREASURY_SEED],
@@ -55,6 +123,7 @@ pub struct CreateTokenProposal<'info> {
 
 // }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateTokenProposal>,
     token_name: String,
@@ -64,22 +133,48 @@ pub fn handler(
     total_supply: u64,
     creator_allocation: u8,
     lockup_period: i64,
+    allowlist_proof: Vec<[u8; 32]>,
 ) -> Result<()> {
+    // --- Validation des paramètres ---
+    validate_token_name(&token_name)?;
+    validate_token_symbol(&token_symbol)?;
+    validate_description(&description)?;
+    if let Some(url) = image_url.as_deref() {
+        validate_image_url(url)?;
+    }
+    validate_total_supply(total_supply)?;
+    validate_creator_allocation(creator_allocation)?;
+    validate_lockup_period(lockup_period)?;
+
+    // --- Unicité du symbole ---
+    require!(
+        ctx.accounts.epoch_symbol.proposal == Pubkey::default(),
+        ErrorCode::SymbolAlreadyTaken
+    );
     require!(
-        creator_allocation <= 10,
-        ErrorCode::CreatorAllocationTooHigh
+        ctx.accounts.launched_symbol.data_is_empty(),
+        ErrorCode::SymbolAlreadyLaunched
     );
+
     require!(
         ctx.accounts.epoch.status == EpochStatus::Active,
         ErrorCode::EpochNotActive
     );
 
+    // --- Liste d'autorisation de l'époque (si elle filtre les créateurs) ---
+    require_allowlisted(&ctx.accounts.epoch, ctx.accounts.creator.key, &allowlist_proof, true)?;
+    require!(
+        ctx.accounts.epoch.config.is_creator_allowed(ctx.accounts.creator.key),
+        ErrorCode::CreatorNotAllowedInEpoch
+    );
+
     // --- Gestion des Frais ---
     let creator_account_info = ctx.accounts.creator.to_account_info();
     let treasury_account_info = ctx.accounts.treasury.to_account_info();
     let system_program_account_info = ctx.accounts.system_program.to_account_info();
 
-    // 1. Transférer les frais de création de proposition au compte Treasury
+    // 1. Transférer les frais de création de proposition (globaux ou propres à l'époque) au compte Treasury
+    let creation_fee = ctx.accounts.epoch.config.proposal_creation_fee();
     let cpi_accounts_transfer = anchor_lang::system_program::Transfer {
         from: creator_account_info.clone(),
         to: treasury_account_info.clone(),
@@ -88,23 +183,44 @@ pub fn handler(
         system_program_account_info.clone(), 
         cpi_accounts_transfer
     );
-    anchor_lang::system_program::transfer(cpi_context_transfer, PROPOSAL_CREATION_FEE_LAMPORTS)?;
+    anchor_lang::system_program::transfer(cpi_context_transfer, creation_fee)?;
 
     // 2. Distribuer les frais perçus en utilisant la fonction utilitaire
     distribute_fees_to_treasury(
         &mut ctx.accounts.treasury,
-        PROPOSAL_CREATION_FEE_LAMPORTS,
+        creation_fee,
         FeeType::ProposalCreation
     )?;
     
+    // 3. Ouvrir le vault de la proposition avec sa réserve de rente
+    let cpi_context_vault = anchor_lang::context::CpiContext::new(
+        system_program_account_info,
+        anchor_lang::system_program::Transfer {
+            from: creator_account_info,
+            to: ctx.accounts.proposal_vault.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_context_vault, vault_rent_reserve()?)?;
+
+    // 4. Déposer la caution du créateur
+    let bond_amount = ctx.accounts.program_config.creator_bond_lamports;
+    if bond_amount > 0 {
+        let cpi_context_bond = anchor_lang::context::CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: ctx.accounts.creator_bond.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context_bond, bond_amount)?;
+    }
+
     // --- Initialiser la proposition (logique existante) ---
     let proposal = &mut ctx.accounts.token_proposal;
     proposal.epoch_id = ctx.accounts.epoch.epoch_id;
     proposal.creator = ctx.accounts.creator.key();
     proposal.token_name = token_name;
     proposal.token_symbol = token_symbol;
-    proposal.description = description;
-    proposal.image_url = image_url;
     proposal.total_supply = total_supply;
     proposal.creator_allocation = creator_allocation;
     let remaining_allocation = 100u8.saturating_sub(creator_allocation);
@@ -116,6 +232,36 @@ pub fn handler(
     let clock = Clock::get()?;
     proposal.creation_timestamp = clock.unix_timestamp;
     proposal.status = ProposalStatus::Active;
+    proposal.sol_refunded = 0;
+    proposal.version = TOKEN_PROPOSAL_VERSION;
+
+    let bond = &mut ctx.accounts.creator_bond;
+    bond.proposal = proposal.key();
+    bond.creator = proposal.creator;
+    bond.amount = bond_amount;
+    bond.min_raise = ctx.accounts.program_config.creator_bond_min_raise;
+    bond.posted_at = proposal.creation_timestamp;
+    bond.version = CREATOR_BOND_VERSION;
+
+    let metadata = &mut ctx.accounts.proposal_metadata;
+    metadata.proposal = proposal.key();
+    metadata.description = description;
+    metadata.image_url = image_url;
+    metadata.version = PROPOSAL_METADATA_VERSION;
+
+    let epoch = &mut ctx.accounts.epoch;
+    require!(epoch.proposals_count != LEGACY_EPOCH_UNKNOWN_PROPOSALS, ErrorCode::LegacyEpochNotBackfilled);
+    epoch.proposals_count = epoch.proposals_count.checked_add(1)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    let epoch_symbol = &mut ctx.accounts.epoch_symbol;
+    epoch_symbol.epoch_id = proposal.epoch_id;
+    epoch_symbol.symbol = normalize_symbol(&proposal.token_symbol);
+    epoch_symbol.proposal = proposal.key();
+    epoch_symbol.registered_at = proposal.creation_timestamp;
+    epoch_symbol.version = SYMBOL_REGISTRY_VERSION;
+
+    ctx.accounts.leaderboard.load_mut()?.record(proposal.key(), 0);
 
     Ok(())
 }
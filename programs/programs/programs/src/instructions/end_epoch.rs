@@ -1,32 +1,48 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{EpochManagement, EpochStatus, ProgramConfig};
+use crate::state::{EpochManagement, EpochStatus, ProgramConfig, Treasury};
 use crate::error::ErrorCode;
+use crate::constants::TREASURY_SEED;
+use crate::utils::pay_crank_reward;
 
 pub fn handler(ctx: Context<EndEpoch>, epoch_id: u64) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let epoch = &mut ctx.accounts.epoch_management;
+
+    // L'admin peut fermer une époque à tout moment ; une fois expirée,
+    // n'importe quel crank peut la fermer.
+    let expired = current_time >= epoch.end_time;
     require!(
-        ctx.accounts.authority.key() == ctx.accounts.program_config.admin_authority,
+        ctx.accounts.authority.key() == ctx.accounts.program_config.admin_authority || expired,
         ErrorCode::Unauthorized
     );
-
-    let epoch = &mut ctx.accounts.epoch_management;
     
     require!(
         epoch.epoch_id == epoch_id,
         ErrorCode::InvalidEpochId
     );
     
+    // Une époque planifiée peut aussi être fermée (annulée) sans avoir été activée
     require!(
-        matches!(epoch.status, EpochStatus::Active),
+        matches!(epoch.status, EpochStatus::Active | EpochStatus::Pending),
         ErrorCode::EpochAlreadyInactive
     );
     
-    let current_time = Clock::get()?.unix_timestamp;
-    
     epoch.status = EpochStatus::Closed;
     epoch.end_time = current_time;
 
     println!("Epoch: {:?}", epoch.status);
+
+    // Seule la fermeture d'une époque expirée, ouverte à tous, est rémunérée ;
+    // une fermeture anticipée est réservée à l'admin.
+    if expired {
+        pay_crank_reward(
+            &mut ctx.accounts.treasury,
+            &ctx.accounts.program_config,
+            epoch,
+            &ctx.accounts.authority.to_account_info(),
+        )?;
+    }
     
     emit!(EpochEnded {
         epoch_id,
@@ -51,6 +67,13 @@ pub struct EndEpoch<'info> {
         bump
     )]
     pub epoch_management: Account<'info, EpochManagement>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -62,4 +85,4 @@ pub struct EndEpoch<'info> {
 pub struct EpochEnded {
     pub epoch_id: u64,
     pub ended_at: i64,
-} 
\ No newline at end of file
+} 
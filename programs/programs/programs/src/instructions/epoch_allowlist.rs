@@ -0,0 +1,44 @@
+// Liste d'autorisation d'une époque (lancements partenaires).
+// L'admin enregistre la racine Merkle des portefeuilles autorisés sur l'époque : seuls ces
+// portefeuilles peuvent alors soutenir des propositions, et éventuellement en créer. Les preuves
+// sont générées hors chaîne par tools/allowlist-tool. Une racine absente rouvre l'époque.
+
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::{EpochManagement, EpochStatus, ProgramConfig};
+
+#[derive(Accounts)]
+pub struct SetEpochAllowlist<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = program_config.admin_authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", epoch_management.epoch_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_management.status != EpochStatus::Closed @ ErrorCode::EpochAlreadyInactive
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+}
+
+pub fn set_epoch_allowlist(
+    ctx: Context<SetEpochAllowlist>,
+    allowlist_root: Option<[u8; 32]>,
+    gates_creators: bool,
+) -> Result<()> {
+    let epoch = &mut ctx.accounts.epoch_management;
+    epoch.allowlist_root = allowlist_root;
+    epoch.allowlist_gates_creators = allowlist_root.is_some() && gates_creators;
+
+    msg!("Epoch {} allowlist {} (creators gated: {})",
+        epoch.epoch_id,
+        if allowlist_root.is_some() { "set" } else { "cleared" },
+        epoch.allowlist_gates_creators);
+    Ok(())
+}
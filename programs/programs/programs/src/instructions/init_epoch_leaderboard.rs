@@ -0,0 +1,44 @@
+// Création du classement d'une époque démarrée avant l'introduction d'EpochLeaderboard.
+//
+// start_epoch crée le classement avec l'époque ; les époques plus anciennes n'en ont pas, ce qui
+// bloque toutes les instructions qui le mettent à jour (support, retrait, annulation, modération,
+// archivage). Sans permission : n'importe qui peut payer la rente, qui revient à l'administrateur
+// à l'archivage comme pour un classement créé par start_epoch.
+// Le classement d'une telle époque démarre vide et se remplit au fil des supports suivants.
+
+use anchor_lang::prelude::*;
+use crate::state::{EpochLeaderboard, EpochManagement};
+use crate::constants::{EPOCH_LEADERBOARD_VERSION, LEADERBOARD_SEED};
+
+#[derive(Accounts)]
+#[instruction(epoch_id: u64)]
+pub struct InitEpochLeaderboard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"epoch", epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<EpochLeaderboard>(),
+        seeds = [LEADERBOARD_SEED, epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitEpochLeaderboard>, epoch_id: u64) -> Result<()> {
+    let mut leaderboard = ctx.accounts.leaderboard.load_init()?;
+    leaderboard.epoch_id = epoch_id;
+    leaderboard.version = EPOCH_LEADERBOARD_VERSION;
+
+    msg!("Leaderboard created for epoch {} (status {:?})", epoch_id, ctx.accounts.epoch_management.status);
+    Ok(())
+}
@@ -22,6 +22,7 @@
 
 use anchor_lang::prelude::*;
 use crate::state::ProgramConfig;
+use crate::constants::*;
 
 #[derive(Accounts)]
 pub struct InitializeProgramConfig<'info> {
@@ -50,6 +51,20 @@ pub fn handler(ctx: Context<InitializeProgramConfig>, admin_authority: Pubkey) -
     
     let config = &mut ctx.accounts.program_config;
     config.admin_authority = admin_authority;
+    config.crank_reward_lamports = DEFAULT_CRANK_REWARD_LAMPORTS;
+    config.crank_reward_epoch_cap = DEFAULT_CRANK_REWARD_EPOCH_CAP;
+    config.max_support_per_wallet_per_proposal = DEFAULT_MAX_SUPPORT_PER_WALLET_PER_PROPOSAL;
+    config.max_support_per_wallet_per_epoch = DEFAULT_MAX_SUPPORT_PER_WALLET_PER_EPOCH;
+    config.max_sol_raised_per_proposal = DEFAULT_MAX_SOL_RAISED_PER_PROPOSAL;
+    config.min_sol_raised_for_launch = DEFAULT_MIN_SOL_RAISED_FOR_LAUNCH;
+    config.min_contributors_for_launch = DEFAULT_MIN_CONTRIBUTORS_FOR_LAUNCH;
+    config.unsupport_penalty_bps = DEFAULT_UNSUPPORT_PENALTY_BPS;
+    config.support_fee_refund_bps = DEFAULT_SUPPORT_FEE_REFUND_BPS;
+    config.epoch_archive_grace_period = DEFAULT_EPOCH_ARCHIVE_GRACE_PERIOD;
+    config.version = PROGRAM_CONFIG_VERSION;
+    config.referral_fee_bps = DEFAULT_REFERRAL_FEE_BPS;
+    config.creator_bond_lamports = DEFAULT_CREATOR_BOND_LAMPORTS;
+    config.creator_bond_min_raise = DEFAULT_CREATOR_BOND_MIN_RAISE;
 
     msg!("ProgramConfig initialized with admin authority: {}", admin_authority);
     Ok(())
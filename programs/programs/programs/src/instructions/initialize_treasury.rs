@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::state::{Treasury, TreasurySubAccount};
+use crate::constants::TREASURY_VERSION;
 
 #[derive(Accounts)]
 pub struct InitializeTreasury<'info> {
@@ -39,6 +40,7 @@ pub fn initialize_treasury(ctx: Context<InitializeTreasury>, initial_authority:
         sol_balance: 0,
         last_withdrawal: 0,
     };
+    treasury.version = TREASURY_VERSION;
     msg!("Treasury account initialized with authority: {}", initial_authority);
     Ok(())
 } 
\ No newline at end of file
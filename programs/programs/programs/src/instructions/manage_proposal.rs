@@ -0,0 +1,157 @@
+// Gestion d'une proposition par son créateur pendant que l'époque est active.
+//
+// - update_proposal_metadata : corrige la description ou l'image à tout moment ; les champs
+//   économiques (supply, allocation créateur, lockup) ne sont modifiables qu'avant le premier
+//   support, pour ne pas changer les conditions acceptées par les supporters.
+// - cancel_proposal : retire la proposition (statut Cancelled). Les supporters peuvent alors
+//   réclamer leurs fonds immédiatement, sans attendre le traitement de l'époque, et le symbole
+//   réservé pour l'époque est libéré.
+// Le nom du token fait partie des seeds du PDA et reste donc immuable.
+
+use anchor_lang::prelude::*;
+use crate::state::{EpochLeaderboard, EpochManagement, EpochStatus, ProposalMetadata, ProposalStatus, SymbolRegistry, TokenProposal};
+use crate::constants::{LEADERBOARD_SEED, PROPOSAL_METADATA_SEED};
+use crate::error::ErrorCode;
+use crate::utils::proposal_validation::*;
+
+#[derive(Accounts)]
+pub struct UpdateProposalMetadata<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = epoch.status == EpochStatus::Active @ ErrorCode::EpochNotActive,
+        constraint = epoch.epoch_id == token_proposal.epoch_id @ ErrorCode::ProposalEpochMismatch
+    )]
+    pub epoch: Account<'info, EpochManagement>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        has_one = creator @ ErrorCode::NotProposalCreator,
+        constraint = token_proposal.status == ProposalStatus::Active @ ErrorCode::ProposalNotActive
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_METADATA_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+}
+
+/// Met à jour les champs fournis (`None` = inchangé). `clear_image` retire l'image actuelle.
+#[allow(clippy::too_many_arguments)]
+pub fn update_proposal_metadata(
+    ctx: Context<UpdateProposalMetadata>,
+    description: Option<String>,
+    image_url: Option<String>,
+    clear_image: bool,
+    total_supply: Option<u64>,
+    creator_allocation: Option<u8>,
+    lockup_period: Option<i64>,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.token_proposal;
+
+    // Les champs économiques sont figés dès le premier support
+    let touches_economics = total_supply.is_some() || creator_allocation.is_some() || lockup_period.is_some();
+    if touches_economics {
+        require!(proposal.total_contributions == 0, ErrorCode::EconomicFieldsLocked);
+    }
+
+    if let Some(description) = description {
+        validate_description(&description)?;
+        ctx.accounts.proposal_metadata.description = description;
+    }
+    if clear_image {
+        require!(image_url.is_none(), ErrorCode::ConflictingImageUpdate);
+        ctx.accounts.proposal_metadata.image_url = None;
+    }
+    if let Some(image_url) = image_url {
+        validate_image_url(&image_url)?;
+        ctx.accounts.proposal_metadata.image_url = Some(image_url);
+    }
+    if let Some(total_supply) = total_supply {
+        validate_total_supply(total_supply)?;
+        proposal.total_supply = total_supply;
+    }
+    if let Some(creator_allocation) = creator_allocation {
+        validate_creator_allocation(creator_allocation)?;
+        proposal.creator_allocation = creator_allocation;
+        let remaining_allocation = 100u8.saturating_sub(creator_allocation);
+        proposal.supporter_allocation = remaining_allocation.saturating_add(1) / 2;
+    }
+    if let Some(lockup_period) = lockup_period {
+        validate_lockup_period(lockup_period)?;
+        proposal.lockup_period = lockup_period;
+    }
+
+    msg!("Proposal {} metadata updated by its creator", proposal.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    // Mutable car il récupère la rente du registre de symbole
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = epoch.status == EpochStatus::Active @ ErrorCode::EpochNotActive,
+        constraint = epoch.epoch_id == token_proposal.epoch_id @ ErrorCode::ProposalEpochMismatch
+    )]
+    pub epoch: Account<'info, EpochManagement>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        has_one = creator @ ErrorCode::NotProposalCreator,
+        constraint = token_proposal.status == ProposalStatus::Active @ ErrorCode::ProposalNotActive
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    // Le symbole redevient disponible pour l'époque
+    #[account(
+        mut,
+        seeds = [
+            b"epoch_symbol",
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            normalize_symbol(&token_proposal.token_symbol).as_bytes()
+        ],
+        bump,
+        constraint = epoch_symbol.proposal == token_proposal.key() @ ErrorCode::SymbolRegistryMismatch,
+        close = creator
+    )]
+    pub epoch_symbol: Account<'info, SymbolRegistry>,
+
+    // Classement de l'époque, d'où la proposition est retirée
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, token_proposal.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+}
+
+/// Retire la proposition : plus aucun support n'est accepté et les supporters peuvent réclamer.
+pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.token_proposal;
+    proposal.status = ProposalStatus::Cancelled;
+    ctx.accounts.leaderboard.load_mut()?.remove(proposal.key());
+
+    msg!("Proposal {} cancelled by its creator ({} lamports raised can be reclaimed)",
+        proposal.key(), proposal.sol_raised);
+    Ok(())
+}
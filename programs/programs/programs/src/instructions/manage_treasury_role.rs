@@ -4,6 +4,7 @@
 
 use anchor_lang::prelude::*;
 use crate::state::{TreasuryRoles, TreasuryRole, RoleType};
+use crate::constants::TREASURY_ROLES_VERSION;
 use crate::error::ErrorCode;
 
 /// Only an admin (present in authorities) of the TreasuryRoles account can call this instruction.
@@ -180,7 +181,7 @@ pub fn remove_admin(
 
 #[derive(Accounts)]
 pub struct InitializeTreasuryRoles<'info> {
-    #[account(init, payer = payer, space = 8 + 32 * 3 + 4 + (32 + 1 + 8 + 8 + 1) * 16, seeds = [b"treasury_roles"], bump)]
+    #[account(init, payer = payer, space = 8 + TreasuryRoles::INIT_SPACE, seeds = [b"treasury_roles"], bump)]
     pub treasury_roles: Account<'info, TreasuryRoles>,
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -191,9 +192,10 @@ pub fn initialize_treasury_roles(
     ctx: Context<InitializeTreasuryRoles>,
     authorities: Vec<Pubkey>,
 ) -> Result<()> {
-    require!(authorities.len() >= 1 && authorities.len() <= 3, ErrorCode::CustomError);
+    require!(!authorities.is_empty() && authorities.len() <= 3, ErrorCode::CustomError);
     let treasury_roles = &mut ctx.accounts.treasury_roles;
     treasury_roles.authorities = authorities;
     treasury_roles.roles = Vec::new();
+    treasury_roles.version = TREASURY_ROLES_VERSION;
     Ok(())
 } 
\ No newline at end of file
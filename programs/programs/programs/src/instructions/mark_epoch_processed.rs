@@ -44,6 +44,7 @@ pub fn handler(ctx: Context<MarkEpochProcessed>) -> Result<()> {
 
     // Mettre à jour le statut
     ctx.accounts.epoch_management.processed = true;
+    ctx.accounts.epoch_management.processed_at = Clock::get()?.unix_timestamp;
 
     msg!("Epoch {} marked as processed.", ctx.accounts.epoch_management.epoch_id);
     Ok(())
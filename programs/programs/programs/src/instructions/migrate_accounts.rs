@@ -0,0 +1,230 @@
+// Instructions `migrate_*` : mise à niveau en place des comptes versionnés (voir utils/migration.rs).
+// Les comptes sont passés non typés car un ancien format ne se désérialise plus avec la
+// structure actuelle. N'importe qui peut déclencher une migration ; le payeur complète la rente.
+
+use anchor_lang::prelude::*;
+use crate::constants::{LEGACY_EPOCH_UNKNOWN_PROPOSALS, PROPOSAL_METADATA_SEED, PROPOSAL_VAULT_SEED, TREASURY_SEED};
+use crate::error::ErrorCode;
+use crate::state::{EpochManagement, ProgramConfig, ProposalMetadata, Treasury, TreasuryRoles, UserProposalSupport};
+use crate::utils::migration::{clear_legacy_treasury_roles_tail, migrate_account, upgrade_legacy_token_proposal};
+
+#[derive(Accounts)]
+pub struct MigrateProgramConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ancien format possible ; propriétaire et discriminateur vérifiés par migrate_account.
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_program_config(ctx: Context<MigrateProgramConfig>) -> Result<()> {
+    migrate_account::<ProgramConfig>(
+        &ctx.accounts.program_config.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateTreasury<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ancien format possible ; propriétaire et discriminateur vérifiés par migrate_account.
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_treasury(ctx: Context<MigrateTreasury>) -> Result<()> {
+    migrate_account::<Treasury>(
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateTreasuryRoles<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ancien format possible ; propriétaire et discriminateur vérifiés avant toute écriture.
+    #[account(
+        mut,
+        seeds = [b"treasury_roles"],
+        bump
+    )]
+    pub treasury_roles: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_treasury_roles(ctx: Context<MigrateTreasuryRoles>) -> Result<()> {
+    let treasury_roles = ctx.accounts.treasury_roles.to_account_info();
+    clear_legacy_treasury_roles_tail(&treasury_roles)?;
+    migrate_account::<TreasuryRoles>(
+        &treasury_roles,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(epoch_id: u64)]
+pub struct MigrateEpochManagement<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ancien format possible ; propriétaire et discriminateur vérifiés par migrate_account.
+    #[account(
+        mut,
+        seeds = [b"epoch", epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_management: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_epoch_management(ctx: Context<MigrateEpochManagement>, _epoch_id: u64) -> Result<()> {
+    migrate_account::<EpochManagement>(
+        &ctx.accounts.epoch_management.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    Ok(())
+}
+
+// Les compteurs de propositions d'une époque v0 sont inconnus après migration : l'admin les
+// renseigne d'après l'historique des propositions de l'époque.
+#[derive(Accounts)]
+#[instruction(epoch_id: u64)]
+pub struct BackfillLegacyEpoch<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = program_config.admin_authority == admin_authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", epoch_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_management.proposals_count == LEGACY_EPOCH_UNKNOWN_PROPOSALS @ ErrorCode::EpochAlreadyBackfilled
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    pub admin_authority: Signer<'info>,
+}
+
+pub fn backfill_legacy_epoch(
+    ctx: Context<BackfillLegacyEpoch>,
+    _epoch_id: u64,
+    proposals_count: u64,
+    proposals_settled: u64,
+    winners_count: u64,
+    total_raised: u64,
+) -> Result<()> {
+    require!(
+        proposals_count != LEGACY_EPOCH_UNKNOWN_PROPOSALS
+            && proposals_settled <= proposals_count
+            && winners_count <= proposals_settled,
+        ErrorCode::InvalidEpochCounters
+    );
+
+    let epoch = &mut ctx.accounts.epoch_management;
+    epoch.proposals_count = proposals_count;
+    epoch.proposals_settled = proposals_settled;
+    epoch.winners_count = winners_count;
+    epoch.total_raised = total_raised;
+    // Époque déjà traitée avant l'ajout de processed_at : le délai de grâce part du backfill
+    if epoch.processed && epoch.processed_at == 0 {
+        epoch.processed_at = Clock::get()?.unix_timestamp;
+    }
+
+    msg!("Legacy epoch {} backfilled: {} proposals, {} settled, {} winners, {} lamports raised",
+        epoch.epoch_id, proposals_count, proposals_settled, winners_count, total_raised);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateTokenProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ancien format possible. Les seeds dépendent du contenu du compte :
+    /// propriétaire et discriminateur suffisent à garantir qu'il s'agit d'une proposition du programme.
+    #[account(mut)]
+    pub token_proposal: UncheckedAccount<'info>,
+
+    // Reçoit la description et l'image retirées de la proposition (format v2).
+    // Déjà présent pour une proposition v2 : la migration échoue alors avec AccountAlreadyMigrated.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ProposalMetadata::INIT_SPACE,
+        seeds = [PROPOSAL_METADATA_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
+    // Reçoit le SOL encore dû aux supporters (format v2)
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_token_proposal(ctx: Context<MigrateTokenProposal>) -> Result<()> {
+    upgrade_legacy_token_proposal(
+        &ctx.accounts.token_proposal.to_account_info(),
+        &mut ctx.accounts.proposal_metadata,
+        &ctx.accounts.proposal_vault.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserProposalSupport<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ancien format possible. Les seeds dépendent du contenu du compte :
+    /// propriétaire et discriminateur vérifiés par migrate_account.
+    #[account(mut)]
+    pub user_support: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_user_proposal_support(ctx: Context<MigrateUserProposalSupport>) -> Result<()> {
+    migrate_account::<UserProposalSupport>(
+        &ctx.accounts.user_support.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    Ok(())
+}
@@ -3,25 +3,61 @@ pub mod manage_treasury_role;
 pub use manage_treasury_role::*;
 
 // Autres instructions principales
+pub mod activate_epoch;
+pub mod archive_epoch;
+pub mod close_proposal;
 pub mod create_token_proposal;
 pub mod end_epoch;
+pub mod epoch_allowlist;
+pub mod init_epoch_leaderboard;
 pub mod initialize;
 pub mod initialize_program_config;
 pub mod initialize_treasury;     // Notre nouvelle instruction
+pub mod manage_proposal;
 pub mod mark_epoch_processed;
+pub mod migrate_accounts;
+pub mod moderation;
+pub mod payment_stream;
+pub mod push_refund;
+pub mod reallocate_support;
+pub mod reclaim_many;
 pub mod reclaim_support;
+pub mod referral;
+pub mod settle_creator_bond;
 pub mod start_epoch;
 pub mod support_proposal;
+pub mod symbol_registry;
+pub mod treasury_spending_proposal;
+pub mod unsupport_proposal;
+pub mod update_program_config;
 pub mod update_proposal_status;
-// NOTE: initialize_epoch, update_program_config n'existent pas en tant que fichiers séparés actuellement.
+// NOTE: initialize_epoch n'existe pas en tant que fichier séparé actuellement.
 
+pub use activate_epoch::*;
+pub use archive_epoch::*;
+pub use close_proposal::*;
 pub use create_token_proposal::*;
 pub use end_epoch::*;
+pub use epoch_allowlist::*;
+pub use init_epoch_leaderboard::*;
 pub use initialize::*;
 pub use initialize_program_config::*;
 pub use initialize_treasury::*;
+pub use manage_proposal::*;
 pub use mark_epoch_processed::*;
+pub use migrate_accounts::*;
+pub use moderation::*;
+pub use payment_stream::*;
+pub use push_refund::*;
+pub use reallocate_support::*;
+pub use reclaim_many::*;
 pub use reclaim_support::*;
+pub use referral::*;
+pub use settle_creator_bond::*;
 pub use start_epoch::*;
 pub use support_proposal::*;
+pub use symbol_registry::*;
+pub use treasury_spending_proposal::*;
+pub use unsupport_proposal::*;
+pub use update_program_config::*;
 pub use update_proposal_status::*;
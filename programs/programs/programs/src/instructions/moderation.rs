@@ -0,0 +1,309 @@
+// Modération des propositions abusives (usurpation, nom ou image offensants...).
+//
+// - flag_proposal : pendant l'époque, un modérateur signale une proposition avec un motif.
+//   Elle est masquée du classement, n'accepte plus de nouveaux supports et ses supporters
+//   peuvent retirer leurs fonds sans pénalité via unsupport_proposal.
+// - appeal_proposal_flag : le créateur fait appel du signalement, une seule fois par signalement.
+// - unflag_proposal : un modérateur lève le signalement, la proposition redevient active.
+// - remove_proposal : un modérateur confirme le signalement ; la proposition est retirée,
+//   ses supporters sont remboursables immédiatement et la caution du créateur est confisquée.
+//   unflag_proposal et remove_proposal ferment le compte de signalement, dont la rente revient
+//   au modérateur qui l'a créé ; un nouveau signalement en recrée un.
+//
+// Chaque étape émet un événement. Les modérateurs sont les admins de TreasuryRoles et les
+// détenteurs du rôle ProposalModerator.
+
+use anchor_lang::prelude::*;
+use crate::constants::{LEADERBOARD_SEED, MODERATION_SEED, PROPOSAL_MODERATION_VERSION};
+use crate::error::ErrorCode;
+use crate::state::{
+    EpochLeaderboard, EpochManagement, EpochStatus, ModerationReason, ProposalModeration,
+    ProposalStatus, TokenProposal, TreasuryRoles,
+};
+
+#[derive(Accounts)]
+pub struct FlagProposal<'info> {
+    // Le modérateur paie la rente du compte de signalement, qui lui revient à sa fermeture
+    #[account(mut)]
+    pub moderator: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury_roles"],
+        bump
+    )]
+    pub treasury_roles: Account<'info, TreasuryRoles>,
+
+    #[account(
+        constraint = epoch.status == EpochStatus::Active @ ErrorCode::EpochNotActive,
+        constraint = epoch.epoch_id == token_proposal.epoch_id @ ErrorCode::ProposalEpochMismatch
+    )]
+    pub epoch: Account<'info, EpochManagement>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        constraint = token_proposal.status == ProposalStatus::Active @ ErrorCode::ProposalNotActive
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    #[account(
+        init,
+        payer = moderator,
+        space = 8 + ProposalModeration::INIT_SPACE,
+        seeds = [MODERATION_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub moderation: Account<'info, ProposalModeration>,
+
+    // Classement de l'époque, d'où la proposition est retirée
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, token_proposal.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn flag_proposal(ctx: Context<FlagProposal>, reason: ModerationReason) -> Result<()> {
+    let moderator = ctx.accounts.moderator.key();
+    require!(
+        ctx.accounts.treasury_roles.is_proposal_moderator(&moderator),
+        ErrorCode::Unauthorized
+    );
+
+    let proposal = &mut ctx.accounts.token_proposal;
+    proposal.status = ProposalStatus::Flagged;
+    ctx.accounts.leaderboard.load_mut()?.remove(proposal.key());
+
+    let now = Clock::get()?.unix_timestamp;
+    let moderation = &mut ctx.accounts.moderation;
+    moderation.proposal = proposal.key();
+    moderation.moderator = moderator;
+    moderation.reason = reason;
+    moderation.flagged_at = now;
+    moderation.appealed = false;
+    moderation.appealed_at = 0;
+    moderation.version = PROPOSAL_MODERATION_VERSION;
+
+    emit!(ProposalFlagged {
+        proposal: proposal.key(),
+        moderator,
+        reason,
+        flagged_at: now,
+    });
+    Ok(())
+}
+
+/// Seul le créateur de la proposition peut faire appel.
+#[derive(Accounts)]
+pub struct AppealProposalFlag<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        has_one = creator @ ErrorCode::NotProposalCreator,
+        constraint = token_proposal.status == ProposalStatus::Flagged @ ErrorCode::ProposalNotFlagged
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    #[account(
+        mut,
+        seeds = [MODERATION_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub moderation: Account<'info, ProposalModeration>,
+}
+
+pub fn appeal_proposal_flag(ctx: Context<AppealProposalFlag>) -> Result<()> {
+    let moderation = &mut ctx.accounts.moderation;
+    require!(!moderation.appealed, ErrorCode::AppealAlreadyFiled);
+
+    let now = Clock::get()?.unix_timestamp;
+    moderation.appealed = true;
+    moderation.appealed_at = now;
+
+    emit!(ProposalFlagAppealed {
+        proposal: moderation.proposal,
+        creator: ctx.accounts.creator.key(),
+        reason: moderation.reason,
+        appealed_at: now,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnflagProposal<'info> {
+    pub moderator: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury_roles"],
+        bump
+    )]
+    pub treasury_roles: Account<'info, TreasuryRoles>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        constraint = token_proposal.status == ProposalStatus::Flagged @ ErrorCode::ProposalNotFlagged
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    // Le signalement est tranché : son compte est fermé au profit du modérateur qui l'a payé
+    #[account(
+        mut,
+        seeds = [MODERATION_SEED, token_proposal.key().as_ref()],
+        bump,
+        close = flagging_moderator
+    )]
+    pub moderation: Account<'info, ProposalModeration>,
+
+    /// CHECK: Reçoit la rente du signalement ; doit être le modérateur qui l'a créé.
+    #[account(
+        mut,
+        address = moderation.moderator @ ErrorCode::Unauthorized
+    )]
+    pub flagging_moderator: UncheckedAccount<'info>,
+
+    // Classement de l'époque, où la proposition réapparaît
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, token_proposal.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+}
+
+/// Lève le signalement, après appel ou non : la proposition redevient active.
+pub fn unflag_proposal(ctx: Context<UnflagProposal>) -> Result<()> {
+    let moderator = ctx.accounts.moderator.key();
+    require!(
+        ctx.accounts.treasury_roles.is_proposal_moderator(&moderator),
+        ErrorCode::Unauthorized
+    );
+
+    let proposal = &mut ctx.accounts.token_proposal;
+    proposal.status = ProposalStatus::Active;
+    ctx.accounts.leaderboard.load_mut()?.record(proposal.key(), proposal.sol_raised);
+
+    emit!(ProposalUnflagged {
+        proposal: proposal.key(),
+        moderator,
+        appealed: ctx.accounts.moderation.appealed,
+        unflagged_at: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveProposal<'info> {
+    pub moderator: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury_roles"],
+        bump
+    )]
+    pub treasury_roles: Account<'info, TreasuryRoles>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        constraint = token_proposal.status == ProposalStatus::Flagged @ ErrorCode::ProposalNotFlagged
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    // Le signalement est tranché : son compte est fermé au profit du modérateur qui l'a payé
+    #[account(
+        mut,
+        seeds = [MODERATION_SEED, token_proposal.key().as_ref()],
+        bump,
+        close = flagging_moderator
+    )]
+    pub moderation: Account<'info, ProposalModeration>,
+
+    /// CHECK: Reçoit la rente du signalement ; doit être le modérateur qui l'a créé.
+    #[account(
+        mut,
+        address = moderation.moderator @ ErrorCode::Unauthorized
+    )]
+    pub flagging_moderator: UncheckedAccount<'info>,
+}
+
+/// Confirme le signalement : la proposition est définitivement retirée.
+pub fn remove_proposal(ctx: Context<RemoveProposal>) -> Result<()> {
+    let moderator = ctx.accounts.moderator.key();
+    require!(
+        ctx.accounts.treasury_roles.is_proposal_moderator(&moderator),
+        ErrorCode::Unauthorized
+    );
+
+    let proposal = &mut ctx.accounts.token_proposal;
+    proposal.status = ProposalStatus::Removed;
+
+    emit!(ProposalRemoved {
+        proposal: proposal.key(),
+        moderator,
+        reason: ctx.accounts.moderation.reason,
+        appealed: ctx.accounts.moderation.appealed,
+        removed_at: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct ProposalFlagged {
+    pub proposal: Pubkey,
+    pub moderator: Pubkey,
+    pub reason: ModerationReason,
+    pub flagged_at: i64,
+}
+
+#[event]
+pub struct ProposalFlagAppealed {
+    pub proposal: Pubkey,
+    pub creator: Pubkey,
+    pub reason: ModerationReason,
+    pub appealed_at: i64,
+}
+
+#[event]
+pub struct ProposalUnflagged {
+    pub proposal: Pubkey,
+    pub moderator: Pubkey,
+    pub appealed: bool,
+    pub unflagged_at: i64,
+}
+
+#[event]
+pub struct ProposalRemoved {
+    pub proposal: Pubkey,
+    pub moderator: Pubkey,
+    pub reason: ModerationReason,
+    pub appealed: bool,
+    pub removed_at: i64,
+}
@@ -0,0 +1,155 @@
+// Payment streams for the norug.fun protocol
+// An admin opens a stream paying a recipient at a fixed rate per second from a treasury
+// category until an end time. The recipient claims whatever has vested at any time and
+// each claim is accounted against the sol_balance of the category's sub-account.
+
+use anchor_lang::prelude::*;
+use crate::constants::{PAYMENT_STREAM_VERSION, TREASURY_SEED};
+use crate::error::ErrorCode;
+use crate::state::{PaymentStream, Treasury, TreasuryCategory, TreasuryRoles};
+use crate::utils::{available_in_treasury, withdraw_from_treasury};
+
+/// Only an admin (present in authorities) of the TreasuryRoles account can call this instruction.
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CreateStream<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury_roles"],
+        bump
+    )]
+    pub treasury_roles: Account<'info, TreasuryRoles>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PaymentStream::INIT_SPACE,
+        seeds = [b"stream", stream_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stream: Account<'info, PaymentStream>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a stream paying `rate_per_second` lamports from `category` to `recipient` until `end_time`.
+pub fn create_stream(
+    ctx: Context<CreateStream>,
+    stream_id: u64,
+    category: TreasuryCategory,
+    recipient: Pubkey,
+    rate_per_second: u64,
+    end_time: i64,
+) -> Result<()> {
+    // Check that the signer is an admin
+    require!(
+        ctx.accounts.treasury_roles.authorities.contains(ctx.accounts.authority.key),
+        ErrorCode::Unauthorized
+    );
+    require!(rate_per_second > 0, ErrorCode::AmountMustBeGreaterThanZero);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(end_time > now, ErrorCode::InvalidStreamTimeRange);
+
+    let stream = &mut ctx.accounts.stream;
+    stream.stream_id = stream_id;
+    stream.creator = ctx.accounts.authority.key();
+    stream.category = category;
+    stream.recipient = recipient;
+    stream.rate_per_second = rate_per_second;
+    stream.start_time = now;
+    stream.end_time = end_time;
+    stream.total_claimed = 0;
+    stream.version = PAYMENT_STREAM_VERSION;
+
+    msg!("Stream {} created: {} lamports/s from {:?} to {} until {}",
+        stream_id, rate_per_second, stream.category, recipient, end_time);
+    Ok(())
+}
+
+/// Only the recipient of the stream can claim it.
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", stream.stream_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = recipient @ ErrorCode::InvalidRecipient
+    )]
+    pub stream: Account<'info, PaymentStream>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+/// Pays the recipient everything vested since the last claim, capped by what the category holds.
+pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let claimable = ctx.accounts.stream.claimable_amount(now)?;
+    require!(claimable > 0, ErrorCode::NothingToClaim);
+
+    let category = ctx.accounts.stream.category.clone();
+    let amount = claimable.min(available_in_treasury(&ctx.accounts.treasury, &category)?);
+    require!(amount > 0, ErrorCode::InsufficientTreasuryBalance);
+
+    withdraw_from_treasury(
+        &mut ctx.accounts.treasury,
+        &category,
+        &ctx.accounts.recipient.to_account_info(),
+        amount,
+    )?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.total_claimed = stream.total_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("Stream {}: {} of {} vested lamports claimed by {} (total claimed: {})",
+        stream.stream_id, amount, claimable, stream.recipient, stream.total_claimed);
+    Ok(())
+}
+
+/// Only an admin (present in authorities) of the TreasuryRoles account can call this instruction.
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury_roles"],
+        bump
+    )]
+    pub treasury_roles: Account<'info, TreasuryRoles>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", stream.stream_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stream: Account<'info, PaymentStream>,
+}
+
+/// Stops a stream now. What has already vested stays claimable by the recipient.
+pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+    // Check that the signer is an admin
+    require!(
+        ctx.accounts.treasury_roles.authorities.contains(ctx.accounts.authority.key),
+        ErrorCode::Unauthorized
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let stream = &mut ctx.accounts.stream;
+    require!(stream.end_time > now, ErrorCode::StreamAlreadyEnded);
+    stream.end_time = now;
+
+    msg!("Stream {} cancelled at {}", stream.stream_id, now);
+    Ok(())
+}
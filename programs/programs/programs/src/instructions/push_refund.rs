@@ -0,0 +1,129 @@
+// Remboursement poussé par le crank : une fois l'époque traitée, n'importe qui peut renvoyer
+// le support d'une proposition rejetée (ou annulée) à son supporter, qui n'a ainsi pas besoin
+// de revenir réclamer ses fonds. Le compte de support est fermé et sa rente rendue au supporter ;
+// le signataire reçoit une récompense de crank.
+
+use anchor_lang::prelude::*;
+use crate::constants::{BPS_DENOMINATOR, PROPOSAL_VAULT_SEED, TREASURY_SEED};
+use crate::error::ErrorCode;
+use crate::state::{EpochManagement, ProgramConfig, ProposalStatus, TokenProposal, Treasury, UserProposalSupport};
+use crate::utils::{check_vault_accounting, pay_crank_reward, refund_fees_from_treasury, transfer_from_vault};
+
+#[derive(Accounts)]
+pub struct PushRefund<'info> {
+    // N'importe quel crank ; reçoit la récompense
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", token_proposal.epoch_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_management.processed @ ErrorCode::EpochNotProcessedYet
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        constraint = matches!(token_proposal.status, ProposalStatus::Rejected | ProposalStatus::Cancelled | ProposalStatus::Removed) @ ErrorCode::ProposalNotRejected
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"support",
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            user_proposal_support.user.as_ref(),
+            token_proposal.key().as_ref()
+        ],
+        bump,
+        constraint = user_proposal_support.proposal == token_proposal.key() @ ErrorCode::ProposalMismatch,
+        constraint = user_proposal_support.amount > 0 @ ErrorCode::NothingToReclaim,
+        close = user
+    )]
+    pub user_proposal_support: Account<'info, UserProposalSupport>,
+
+    /// CHECK: Reçoit le remboursement et la rente ; doit être le supporter enregistré.
+    #[account(
+        mut,
+        address = user_proposal_support.user @ ErrorCode::InvalidAuthority
+    )]
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PushRefund>) -> Result<()> {
+    let amount_to_refund = ctx.accounts.user_proposal_support.amount;
+    let user_info = ctx.accounts.user.to_account_info();
+
+    // --- Transfert signé depuis le vault vers le supporter ---
+    let vault_info = ctx.accounts.proposal_vault.to_account_info();
+    require!(vault_info.lamports() >= amount_to_refund, ErrorCode::InsufficientProposalFunds);
+    transfer_from_vault(
+        &vault_info,
+        ctx.bumps.proposal_vault,
+        &ctx.accounts.token_proposal.key(),
+        &user_info,
+        &ctx.accounts.system_program.to_account_info(),
+        amount_to_refund,
+    )?;
+
+    let token_proposal = &mut ctx.accounts.token_proposal;
+    token_proposal.sol_refunded = token_proposal.sol_refunded.checked_add(amount_to_refund)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    check_vault_accounting(&vault_info, token_proposal)?;
+
+    // --- Même remboursement partiel des frais qu'un reclaim_support ---
+    let fee_refund = ctx.accounts.user_proposal_support.fees_paid
+        .checked_mul(ctx.accounts.program_config.support_fee_refund_bps as u64)
+        .ok_or(ErrorCode::CalculationOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    if fee_refund > 0 {
+        refund_fees_from_treasury(&mut ctx.accounts.treasury, &user_info, fee_refund)?;
+    }
+
+    msg!("Pushed refund of {} lamports to {} from {:?} proposal {}",
+        amount_to_refund,
+        ctx.accounts.user.key(),
+        ctx.accounts.token_proposal.status,
+        ctx.accounts.token_proposal.key());
+
+    pay_crank_reward(
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.epoch_management,
+        &ctx.accounts.authority.to_account_info(),
+    )?;
+
+    Ok(())
+}
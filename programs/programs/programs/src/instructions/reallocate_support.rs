@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use crate::state::{EpochManagement, TokenProposal, UserProposalSupport, EpochStatus, ProposalStatus, ProgramConfig, EpochLeaderboard};
+use crate::error::ErrorCode;
+use crate::constants::{LEADERBOARD_SEED, PROPOSAL_VAULT_SEED, USER_PROPOSAL_SUPPORT_VERSION};
+use crate::utils::proposal_vault::{check_vault_accounting, transfer_from_vault};
+
+// Déplace tout ou partie d'un support d'une proposition vers une autre de la même époque active.
+// Les lamports nets passent directement d'un vault de proposition à l'autre : les frais de support
+// ont déjà été payés à l'entrée et ne sont pas prélevés une seconde fois.
+#[derive(Accounts)]
+pub struct ReallocateSupport<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        constraint = epoch.status == EpochStatus::Active @ ErrorCode::EpochNotActive,
+        constraint = epoch.epoch_id == from_proposal.epoch_id @ ErrorCode::ProposalEpochMismatch,
+        constraint = epoch.epoch_id == to_proposal.epoch_id @ ErrorCode::ProposalEpochMismatch
+    )]
+    pub epoch: Account<'info, EpochManagement>,
+
+    // Proposition d'origine
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            from_proposal.creator.as_ref(),
+            from_proposal.epoch_id.to_le_bytes().as_ref(),
+            from_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        constraint = from_proposal.status == ProposalStatus::Active @ ErrorCode::ProposalNotActive
+    )]
+    pub from_proposal: Account<'info, TokenProposal>,
+
+    // Proposition de destination
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            to_proposal.creator.as_ref(),
+            to_proposal.epoch_id.to_le_bytes().as_ref(),
+            to_proposal.token_name.as_bytes(),
+        ],
+        bump,
+        constraint = to_proposal.status == ProposalStatus::Active @ ErrorCode::ProposalNotActive,
+        constraint = to_proposal.key() != from_proposal.key() @ ErrorCode::SameProposalReallocation
+    )]
+    pub to_proposal: Account<'info, TokenProposal>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, from_proposal.key().as_ref()],
+        bump
+    )]
+    pub from_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, to_proposal.key().as_ref()],
+        bump
+    )]
+    pub to_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"support",
+            from_proposal.epoch_id.to_le_bytes().as_ref(),
+            user.key().as_ref(),
+            from_proposal.key().as_ref()
+        ],
+        bump,
+        constraint = from_support.user == user.key() @ ErrorCode::InvalidAuthority,
+        constraint = from_support.amount > 0 @ ErrorCode::NothingToReclaim
+    )]
+    pub from_support: Account<'info, UserProposalSupport>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserProposalSupport::INIT_SPACE,
+        seeds = [
+            b"support",
+            to_proposal.epoch_id.to_le_bytes().as_ref(),
+            user.key().as_ref(),
+            to_proposal.key().as_ref()
+        ],
+        bump
+    )]
+    pub to_support: Account<'info, UserProposalSupport>,
+
+    // Plafonds par proposition appliqués à la destination
+    // (le support cumulé sur l'époque ne change pas)
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    // Classement de l'époque, mis à jour pour les deux propositions
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, epoch.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ReallocateSupport>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountMustBeGreaterThanZero);
+    require!(amount <= ctx.accounts.from_support.amount, ErrorCode::AmountExceedsSupport);
+
+    // --- Plafonds de la destination (0 = illimité), éventuellement surchargés par l'époque ---
+    let config = &ctx.accounts.program_config;
+    let max_per_proposal = ctx.accounts.epoch.config.max_support_per_wallet_per_proposal(config);
+    let max_sol_raised = ctx.accounts.epoch.config.max_sol_raised_per_proposal(config);
+    let new_to_support_amount = ctx.accounts.to_support.amount.checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    let new_to_sol_raised = ctx.accounts.to_proposal.sol_raised.checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(
+        max_per_proposal == 0 || new_to_support_amount <= max_per_proposal,
+        ErrorCode::WalletProposalSupportCapExceeded
+    );
+    require!(
+        max_sol_raised == 0 || new_to_sol_raised <= max_sol_raised,
+        ErrorCode::ProposalSolRaisedCapExceeded
+    );
+
+    // --- Transfert signé entre les deux vaults ---
+    let from_vault_info = ctx.accounts.from_vault.to_account_info();
+    let to_vault_info = ctx.accounts.to_vault.to_account_info();
+    require!(from_vault_info.lamports() >= amount, ErrorCode::InsufficientProposalFunds);
+    transfer_from_vault(
+        &from_vault_info,
+        ctx.bumps.from_vault,
+        &ctx.accounts.from_proposal.key(),
+        &to_vault_info,
+        &ctx.accounts.system_program.to_account_info(),
+        amount,
+    )?;
+
+    // Les frais déjà payés suivent les lamports déplacés
+    let moved_fees = ctx.accounts.from_support.fees_for_amount(amount);
+
+    // --- Proposition de destination ---
+    let to_proposal = &mut ctx.accounts.to_proposal;
+    let to_support = &mut ctx.accounts.to_support;
+    if to_support.amount == 0 {
+        to_proposal.total_contributions = to_proposal.total_contributions.checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        to_support.epoch_id = to_proposal.epoch_id;
+        to_support.user = ctx.accounts.user.key();
+        to_support.proposal = to_proposal.key();
+        to_support.version = USER_PROPOSAL_SUPPORT_VERSION;
+    }
+    to_proposal.sol_raised = new_to_sol_raised;
+    to_support.amount = new_to_support_amount;
+    to_support.fees_paid = to_support.fees_paid.checked_add(moved_fees)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // --- Proposition d'origine ---
+    let from_proposal = &mut ctx.accounts.from_proposal;
+    from_proposal.sol_raised = from_proposal.sol_raised.checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let from_support = &mut ctx.accounts.from_support;
+    from_support.fees_paid = from_support.fees_paid.saturating_sub(moved_fees);
+    from_support.amount = from_support.amount.checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    check_vault_accounting(&to_vault_info, to_proposal)?;
+    check_vault_accounting(&from_vault_info, from_proposal)?;
+
+    let mut leaderboard = ctx.accounts.leaderboard.load_mut()?;
+    leaderboard.record(to_proposal.key(), to_proposal.sol_raised);
+    leaderboard.record(from_proposal.key(), from_proposal.sol_raised);
+    drop(leaderboard);
+
+    msg!("User {} moved {} lamports from proposal {} to proposal {}",
+        ctx.accounts.user.key(), amount, from_proposal.key(), to_proposal.key());
+
+    // Tout le support a été déplacé : le supporter quitte la proposition d'origine
+    if from_support.amount == 0 {
+        from_proposal.total_contributions = from_proposal.total_contributions.checked_sub(1)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        ctx.accounts.from_support.close(ctx.accounts.user.to_account_info())?;
+    }
+
+    Ok(())
+}
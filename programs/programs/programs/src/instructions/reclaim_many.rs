@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use crate::state::{UserProposalSupport, TokenProposal, ProposalStatus, EpochManagement, ProgramConfig, Treasury};
+use crate::error::ErrorCode;
+use crate::constants::{BPS_DENOMINATOR, MAX_RECLAIM_MANY_PAIRS, PROPOSAL_VAULT_SEED, TREASURY_SEED};
+use crate::utils::fee_distribution::refund_fees_from_treasury;
+use crate::utils::proposal_vault::{check_vault_accounting, transfer_from_vault};
+
+// Variante groupée de reclaim_support : les propositions rejetées (ou annulées) d'une même
+// époque sont passées en remaining accounts sous forme de triplets
+// (token_proposal, proposal_vault, user_proposal_support).
+// Chaque triplet est vérifié comme dans reclaim_support (seeds, statut, époque traitée)
+// et chaque compte de support est fermé au profit de l'utilisateur.
+#[derive(Accounts)]
+pub struct ReclaimMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // Époque commune à toutes les propositions du lot
+    #[account(
+        seeds = [b"epoch", epoch_management.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ReclaimMany<'info>>) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty()
+            && remaining.chunks_exact(3).remainder().is_empty()
+            && remaining.len() / 3 <= MAX_RECLAIM_MANY_PAIRS as usize,
+        ErrorCode::InvalidReclaimManyAccounts
+    );
+
+    let user_key = ctx.accounts.user.key();
+    let user_info = ctx.accounts.user.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let epoch = &ctx.accounts.epoch_management;
+
+    let mut total_reclaimed: u64 = 0;
+    let mut total_fees_paid: u64 = 0;
+    let mut seen_supports: Vec<Pubkey> = Vec::with_capacity(remaining.len() / 3);
+
+    for triple in remaining.chunks(3) {
+        let (proposal_info, vault_info, support_info) = (&triple[0], &triple[1], &triple[2]);
+        require!(!seen_supports.contains(support_info.key), ErrorCode::DuplicateSupportAccount);
+        seen_supports.push(support_info.key());
+
+        // Account::try_from vérifie le propriétaire (ce programme) et le discriminateur
+        let mut proposal: Account<'info, TokenProposal> = Account::try_from(proposal_info)?;
+        let support: Account<'info, UserProposalSupport> = Account::try_from(support_info)?;
+
+        // --- Mêmes seeds que reclaim_support ---
+        let (expected_proposal, _) = Pubkey::find_program_address(
+            &[
+                b"proposal",
+                proposal.creator.as_ref(),
+                proposal.epoch_id.to_le_bytes().as_ref(),
+                proposal.token_name.as_bytes(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(expected_proposal, proposal_info.key(), anchor_lang::error::ErrorCode::ConstraintSeeds);
+        let (expected_vault, vault_bump) = Pubkey::find_program_address(
+            &[PROPOSAL_VAULT_SEED, proposal_info.key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(expected_vault, vault_info.key(), anchor_lang::error::ErrorCode::ConstraintSeeds);
+        let (expected_support, _) = Pubkey::find_program_address(
+            &[
+                b"support",
+                proposal.epoch_id.to_le_bytes().as_ref(),
+                user_key.as_ref(),
+                proposal_info.key.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(expected_support, support_info.key(), anchor_lang::error::ErrorCode::ConstraintSeeds);
+
+        // --- Mêmes contraintes que reclaim_support ---
+        require!(
+            matches!(proposal.status, ProposalStatus::Rejected | ProposalStatus::Cancelled | ProposalStatus::Removed),
+            ErrorCode::ProposalNotRejected
+        );
+        require!(proposal.epoch_id == epoch.epoch_id, ErrorCode::ProposalEpochMismatch);
+        require!(
+            epoch.processed || matches!(proposal.status, ProposalStatus::Cancelled | ProposalStatus::Removed),
+            ErrorCode::EpochNotProcessedYet
+        );
+        require!(support.user == user_key, ErrorCode::InvalidAuthority);
+        require!(support.proposal == proposal_info.key(), ErrorCode::ProposalMismatch);
+        require!(support.amount > 0, ErrorCode::NothingToReclaim);
+
+        // --- Transfert signé depuis le vault de la proposition ---
+        let amount_to_reclaim = support.amount;
+        require!(vault_info.lamports() >= amount_to_reclaim, ErrorCode::InsufficientProposalFunds);
+        transfer_from_vault(vault_info, vault_bump, proposal_info.key, &user_info, &system_program_info, amount_to_reclaim)?;
+
+        // Suivi du SOL rendu, persisté manuellement (compte hors du contexte Anchor)
+        proposal.sol_refunded = proposal.sol_refunded.checked_add(amount_to_reclaim)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        check_vault_accounting(vault_info, &proposal)?;
+        proposal.exit(&crate::ID)?;
+
+        total_reclaimed = total_reclaimed.checked_add(amount_to_reclaim)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        total_fees_paid = total_fees_paid.checked_add(support.fees_paid)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        // Fermeture du compte de support, la rente revient à l'utilisateur
+        support.close(user_info.clone())?;
+    }
+
+    // --- Remboursement groupé d'une partie des frais de support ---
+    let fee_refund = total_fees_paid
+        .checked_mul(ctx.accounts.program_config.support_fee_refund_bps as u64)
+        .ok_or(ErrorCode::CalculationOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    if fee_refund > 0 {
+        refund_fees_from_treasury(&mut ctx.accounts.treasury, &user_info, fee_refund)?;
+    }
+
+    msg!("User {} reclaimed {} lamports from {} proposals of epoch {}",
+        user_key, total_reclaimed, remaining.len() / 3, epoch.epoch_id);
+
+    Ok(())
+}
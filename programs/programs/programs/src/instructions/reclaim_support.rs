@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 
 
-use crate::state::{UserProposalSupport, TokenProposal, ProposalStatus, EpochManagement};
+use crate::state::{UserProposalSupport, TokenProposal, ProposalStatus, EpochManagement, ProgramConfig, Treasury};
 use crate::error::ErrorCode;
+use crate::constants::{BPS_DENOMINATOR, TREASURY_SEED, PROPOSAL_VAULT_SEED};
+use crate::utils::fee_distribution::refund_fees_from_treasury;
+use crate::utils::proposal_vault::{check_vault_accounting, transfer_from_vault};
 
 #[derive(Accounts)]
 pub struct ReclaimSupport<'info> {
@@ -10,8 +13,8 @@ pub struct ReclaimSupport<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
-    // Le compte TokenProposal pour vérifier son statut et d'où les fonds seront transférés.
-    // Mutable car son solde va diminuer.
+    // Le compte TokenProposal pour vérifier son statut.
+    // Mutable car sol_refunded va augmenter.
     #[account(
         mut,
         // Ajout des seeds et du bump pour vérification et accès au bump
@@ -22,11 +25,19 @@ pub struct ReclaimSupport<'info> {
             token_proposal.token_name.as_bytes(), // Récupérer depuis le compte lui-même
         ],
         bump, // <-- Demander le bump ici
-        // Contrainte clé : la proposition DOIT être "Rejected".
-        constraint = token_proposal.status == ProposalStatus::Rejected @ ErrorCode::ProposalNotRejected,
+        // Contrainte clé : la proposition DOIT être "Rejected", "Cancelled" par son créateur ou "Removed" par la modération.
+        constraint = matches!(token_proposal.status, ProposalStatus::Rejected | ProposalStatus::Cancelled | ProposalStatus::Removed) @ ErrorCode::ProposalNotRejected,
     )]
     pub token_proposal: Account<'info, TokenProposal>,
 
+    // Le vault SOL de la proposition, d'où les fonds seront transférés.
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_vault: SystemAccount<'info>,
+
     // Le compte UserProposalSupport qui contient le montant à réclamer (amount).
     // Mutable car il sera fermé.
     // Doit correspondre à l'utilisateur et à la proposition.
@@ -54,11 +65,28 @@ pub struct ReclaimSupport<'info> {
     #[account(
         seeds = [b"epoch", token_proposal.epoch_id.to_le_bytes().as_ref()],
         bump, // Anchor gère le bump pour la vérification
-        // Contrainte : l'époque doit avoir été processed par le crank.
-        constraint = epoch_management.processed == true @ ErrorCode::EpochNotProcessedYet,
+        // Contrainte : l'époque doit avoir été processed par le crank,
+        // sauf pour une proposition annulée ou retirée qui est remboursable immédiatement.
+        constraint = epoch_management.processed
+            || matches!(token_proposal.status, ProposalStatus::Cancelled | ProposalStatus::Removed) @ ErrorCode::EpochNotProcessedYet,
     )]
     pub epoch_management: Account<'info, EpochManagement>,
 
+    // Configuration contenant la part des frais de support à rembourser
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    // Trésorerie d'où est remboursée la part des frais de support
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
     // Requis pour le transfert de SOL (CPI)
     pub system_program: Program<'info, System>,
 }
@@ -68,26 +96,46 @@ pub fn handler(ctx: Context<ReclaimSupport>) -> Result<()> {
 
     // Vérifications déjà effectuées par les contraintes Anchor
 
-    // --- Transfert manuel des lamports ---
+    // --- Transfert signé depuis le vault de la proposition ---
 
-    // S'assurer que le compte proposal a assez de fonds (Toujours important!)
-    let token_proposal_account_info = ctx.accounts.token_proposal.to_account_info();
-    if token_proposal_account_info.lamports() < amount_to_reclaim {
+    // S'assurer que le vault a assez de fonds (Toujours important!)
+    let vault_info = ctx.accounts.proposal_vault.to_account_info();
+    if vault_info.lamports() < amount_to_reclaim {
         return err!(ErrorCode::InsufficientProposalFunds);
     }
 
-    // Débiter le compte token_proposal
-    **ctx.accounts.token_proposal.to_account_info().try_borrow_mut_lamports()? -= amount_to_reclaim;
-    
-    // Créditer le compte user
-    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += amount_to_reclaim;
+    transfer_from_vault(
+        &vault_info,
+        ctx.bumps.proposal_vault,
+        &ctx.accounts.token_proposal.key(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        amount_to_reclaim,
+    )?;
+
+    let token_proposal = &mut ctx.accounts.token_proposal;
+    token_proposal.sol_refunded = token_proposal.sol_refunded.checked_add(amount_to_reclaim)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    check_vault_accounting(&vault_info, token_proposal)?;
+
+    // --- Remboursement d'une partie des frais de support par la trésorerie ---
+    let fee_refund = ctx.accounts.user_proposal_support.fees_paid
+        .checked_mul(ctx.accounts.program_config.support_fee_refund_bps as u64)
+        .ok_or(ErrorCode::CalculationOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    if fee_refund > 0 {
+        let user_info = ctx.accounts.user.to_account_info();
+        refund_fees_from_treasury(&mut ctx.accounts.treasury, &user_info, fee_refund)?;
+    }
 
     // Le compte user_proposal_support sera automatiquement fermé et sa rente
     // remboursée à `ctx.accounts.user` grâce à `close = user`.
 
-    msg!("User {} reclaimed {} lamports from rejected proposal {} via direct transfer",
+    msg!("User {} reclaimed {} lamports from {:?} proposal {} vault",
         ctx.accounts.user.key(),
         amount_to_reclaim,
+        ctx.accounts.token_proposal.status,
         ctx.accounts.token_proposal.key()
     );
 
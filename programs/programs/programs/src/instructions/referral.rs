@@ -0,0 +1,71 @@
+// Parrainage des supports.
+// Un parrain ouvre son ReferralAccount une fois ; les supporters le passent ensuite à
+// support_proposal, qui y verse la part de parrainage des frais avant leur distribution
+// dans la trésorerie. Le parrain réclame ses récompenses quand il le souhaite.
+
+use anchor_lang::prelude::*;
+use crate::constants::{REFERRAL_ACCOUNT_VERSION, REFERRAL_SEED};
+use crate::error::ErrorCode;
+use crate::state::ReferralAccount;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferralAccount::INIT_SPACE,
+        seeds = [REFERRAL_SEED, referrer.key().as_ref()],
+        bump
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Ouvre le compte de parrainage du signataire.
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referral_account = &mut ctx.accounts.referral_account;
+    referral_account.referrer = ctx.accounts.referrer.key();
+    referral_account.pending_rewards = 0;
+    referral_account.total_earned = 0;
+    referral_account.referred_supports = 0;
+    referral_account.version = REFERRAL_ACCOUNT_VERSION;
+
+    msg!("Referral account opened for {}", referral_account.referrer);
+    Ok(())
+}
+
+/// Seul le parrain peut réclamer ses récompenses.
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REFERRAL_SEED, referrer.key().as_ref()],
+        bump,
+        has_one = referrer @ ErrorCode::Unauthorized
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+}
+
+/// Verse au parrain toutes ses récompenses en attente ; la rente du compte n'est pas touchée.
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let amount = ctx.accounts.referral_account.pending_rewards;
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    // Le compte appartient au programme : débit direct de ses lamports
+    **ctx.accounts.referral_account.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.referrer.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    let referral_account = &mut ctx.accounts.referral_account;
+    referral_account.pending_rewards = 0;
+
+    msg!("{} lamports of referral rewards claimed by {} (total earned: {})",
+        amount, referral_account.referrer, referral_account.total_earned);
+    Ok(())
+}
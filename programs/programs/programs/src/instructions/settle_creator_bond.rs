@@ -0,0 +1,64 @@
+// Règlement de la caution d'un créateur, sans attendre la fermeture de la proposition.
+// N'importe qui peut le déclencher dès que la proposition est lancée, a atteint le seuil de
+// levée une fois l'époque fermée, ou a été rejetée, annulée ou retirée. close_proposal règle aussi une caution encore ouverte.
+
+use anchor_lang::prelude::*;
+use crate::constants::{CREATOR_BOND_SEED, TREASURY_SEED};
+use crate::error::ErrorCode;
+use crate::state::{CreatorBond, EpochManagement, TokenProposal, Treasury};
+use crate::utils::settle_creator_bond;
+
+#[derive(Accounts)]
+pub struct SettleCreatorBond<'info> {
+    pub authority: Signer<'info>,
+
+    // Époque de la proposition : le seuil de levée n'est jugé qu'une fois l'époque fermée
+    #[account(
+        seeds = [b"epoch", token_proposal.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    #[account(
+        seeds = [
+            b"proposal",
+            token_proposal.creator.as_ref(),
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            token_proposal.token_name.as_bytes(),
+        ],
+        bump
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_BOND_SEED, token_proposal.key().as_ref()],
+        bump
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+
+    /// CHECK: Reçoit la caution rendue et la rente ; doit être le créateur de la proposition.
+    #[account(
+        mut,
+        address = token_proposal.creator @ ErrorCode::NotProposalCreator
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+pub fn handler(ctx: Context<SettleCreatorBond>) -> Result<()> {
+    settle_creator_bond(
+        &ctx.accounts.creator_bond.to_account_info(),
+        &ctx.accounts.token_proposal,
+        &ctx.accounts.epoch_management,
+        &ctx.accounts.creator.to_account_info(),
+        &mut ctx.accounts.treasury,
+    )?;
+    Ok(())
+}
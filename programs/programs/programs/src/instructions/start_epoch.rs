@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::{EpochManagement, EpochStatus, ProgramConfig};
+use crate::state::{EpochConfig, EpochLeaderboard, EpochManagement, EpochStatus, ProgramConfig};
 use crate::error::ErrorCode;
+use crate::constants::{EPOCH_LEADERBOARD_VERSION, EPOCH_MANAGEMENT_VERSION, LEADERBOARD_SEED, MAX_EPOCH_ALLOWED_CREATORS, MAX_EPOCH_SUPPORT_FEE_BPS};
 
 #[derive(Accounts)]
 #[instruction(epoch_id: u64)] // If epoch_id is needed for PDA seeds
@@ -23,6 +24,16 @@ pub struct StartEpoch<'info> {
     )]
     pub epoch_management: Account<'info, EpochManagement>, // Here we use our EpochManagement struct
 
+    // Classement zero-copy des propositions de l'époque
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<EpochLeaderboard>(),
+        seeds = [LEADERBOARD_SEED, epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -38,6 +49,7 @@ pub fn handler(
     epoch_id: u64,
     start_time: i64,
     end_time: i64,
+    config: Option<EpochConfig>,
 ) -> Result<()> {
     require!(
         ctx.accounts.authority.key() == ctx.accounts.program_config.admin_authority,
@@ -49,12 +61,35 @@ pub fn handler(
         ErrorCode::InvalidEpochTimeRange
     );
 
-    // Initialize the epoch
+    let config = config.unwrap_or_default();
+    validate_epoch_config(&config)?;
+
+    // Initialize the epoch. Elle reste planifiée (Pending) jusqu'à son activation par
+    // activate_epoch une fois start_time atteint : plusieurs époques peuvent être publiées à l'avance.
     let epoch = &mut ctx.accounts.epoch_management;
     epoch.epoch_id = epoch_id;
     epoch.start_time = start_time;
     epoch.end_time = end_time;
-    epoch.status = EpochStatus::Active;
+    epoch.status = EpochStatus::Pending;
+    epoch.version = EPOCH_MANAGEMENT_VERSION;
+    epoch.config = config;
+
+    let mut leaderboard = ctx.accounts.leaderboard.load_init()?;
+    leaderboard.epoch_id = epoch_id;
+    leaderboard.version = EPOCH_LEADERBOARD_VERSION;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// Un support doit toujours payer des frais non nuls : le taux surchargé ne peut pas être nul.
+fn validate_epoch_config(config: &EpochConfig) -> Result<()> {
+    require!(config.max_winners != Some(0), ErrorCode::InvalidEpochConfig);
+    if let Some(bps) = config.support_fee_bps {
+        require!(bps > 0 && bps <= MAX_EPOCH_SUPPORT_FEE_BPS, ErrorCode::InvalidEpochConfig);
+    }
+    require!(
+        config.allowed_creators.len() <= MAX_EPOCH_ALLOWED_CREATORS,
+        ErrorCode::InvalidEpochConfig
+    );
+    Ok(())
+}
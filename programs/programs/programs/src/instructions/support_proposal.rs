@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
 // Importer les états et l'enum d'erreur global
-use crate::state::{EpochManagement, TokenProposal, UserProposalSupport, EpochStatus, ProposalStatus, Treasury}; 
+use crate::state::{EpochManagement, TokenProposal, UserProposalSupport, UserEpochSupport, EpochStatus, ProposalStatus, ProgramConfig, Treasury, EpochLeaderboard, ReferralAccount}; 
 use crate::error::ErrorCode; // Utiliser l'enum d'erreur global
-use crate::constants::{SUPPORT_FEE_PERCENTAGE_NUMERATOR, SUPPORT_FEE_PERCENTAGE_DENOMINATOR, TREASURY_SEED};
+use crate::constants::{TREASURY_SEED, LEADERBOARD_SEED, PROPOSAL_VAULT_SEED, REFERRAL_SEED, BPS_DENOMINATOR, USER_EPOCH_SUPPORT_VERSION, USER_PROPOSAL_SUPPORT_VERSION};
 use crate::utils::fee_distribution::{distribute_fees_to_treasury, FeeType};
+use crate::utils::proposal_vault::check_vault_accounting;
+use crate::utils::allowlist::require_allowlisted;
 
 // Définition des comptes requis par l'instruction
 #[derive(Accounts)]
@@ -28,13 +30,21 @@ pub struct SupportProposal<'info> {
 
     // Le compte TokenProposal qui reçoit le support
     #[account(
-        mut, // Mutable car on va augmenter sol_raised et recevoir des SOL
+        mut, // Mutable car on va augmenter sol_raised
         // Contrainte : la proposition doit être active
         constraint = proposal.status == ProposalStatus::Active @ ErrorCode::ProposalNotActive
         // La contrainte `proposal.epoch_id == epoch.epoch_id` est déjà vérifiée ci-dessus
     )]
     pub proposal: Account<'info, TokenProposal>,
 
+    // Le vault SOL de la proposition, qui reçoit le montant net du support
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_vault: SystemAccount<'info>,
+
     // Le compte UserProposalSupport à créer ou mettre à jour (PDA)
     #[account(
         init_if_needed, // Utiliser init_if_needed pour créer ou charger le compte existant
@@ -50,6 +60,35 @@ pub struct SupportProposal<'info> {
     )]
     pub user_support: Account<'info, UserProposalSupport>,
 
+    // Support cumulé de l'utilisateur sur l'époque, pour le plafond par époque
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserEpochSupport::INIT_SPACE,
+        seeds = [
+            b"user_epoch_support",
+            epoch.epoch_id.to_le_bytes().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub user_epoch_support: Account<'info, UserEpochSupport>,
+
+    // Configuration globale contenant les plafonds de support
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    // Classement de l'époque, mis à jour avec le nouveau sol_raised
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, epoch.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
     #[account(
         mut,
         seeds = [TREASURY_SEED],
@@ -57,21 +96,29 @@ pub struct SupportProposal<'info> {
     )]
     pub treasury: Account<'info, Treasury>,
 
+    // Compte de parrainage optionnel du parrain désigné par le supporter
+    #[account(
+        mut,
+        seeds = [REFERRAL_SEED, referral_account.referrer.as_ref()],
+        bump
+    )]
+    pub referral_account: Option<Account<'info, ReferralAccount>>,
+
     // Le programme système, requis pour créer des comptes et transférer des SOL
     pub system_program: Program<'info, System>,
 }
 
 // Logique de l'instruction support_proposal
-pub fn handler(ctx: Context<SupportProposal>, amount: u64) -> Result<()> {
+pub fn handler(ctx: Context<SupportProposal>, amount: u64, allowlist_proof: Vec<[u8; 32]>) -> Result<()> {
     // Vérification de sécurité : s'assurer qu'un montant positif est envoyé
     require!(amount > 0, ErrorCode::AmountMustBeGreaterThanZero);
 
-    // Calculer le montant des frais
-    // Le numérateur est le pourcentage (ex: 5 pour 0.5%), le dénominateur est 1000 pour 0.x% ou 100 pour x%
-    let fee_amount = amount
-        .checked_mul(SUPPORT_FEE_PERCENTAGE_NUMERATOR)
-        .ok_or_else(|| error!(ErrorCode::CalculationOverflow))?
-        .checked_div(SUPPORT_FEE_PERCENTAGE_DENOMINATOR)
+    // Époque réservée aux portefeuilles de sa liste d'autorisation, le cas échéant
+    require_allowlisted(&ctx.accounts.epoch, ctx.accounts.user.key, &allowlist_proof, false)?;
+
+    // Calculer le montant des frais (taux global, ou taux propre à l'époque)
+    let fee_amount = ctx.accounts.epoch.config
+        .support_fee(amount)
         .ok_or_else(|| error!(ErrorCode::CalculationOverflow))?;
 
     // S'assurer que les frais ne sont pas nuls (ce qui arriverait si `amount` est trop petit)
@@ -95,31 +142,87 @@ pub fn handler(ctx: Context<SupportProposal>, amount: u64) -> Result<()> {
     // Anchor initialise les champs à 0 lors de `init_if_needed` si le compte est nouveau.
     let is_new_supporter = user_support.amount == 0;
 
+    // --- Plafonds de support (0 = illimité), éventuellement surchargés par l'époque ---
+    let config = &ctx.accounts.program_config;
+    let epoch_config = &ctx.accounts.epoch.config;
+    let max_per_proposal = epoch_config.max_support_per_wallet_per_proposal(config);
+    let max_per_epoch = epoch_config.max_support_per_wallet_per_epoch(config);
+    let max_sol_raised = epoch_config.max_sol_raised_per_proposal(config);
+    let new_user_amount = user_support.amount.checked_add(net_support_amount)
+        .ok_or_else(|| error!(ErrorCode::Overflow))?;
+    let new_user_epoch_amount = ctx.accounts.user_epoch_support.amount.checked_add(net_support_amount)
+        .ok_or_else(|| error!(ErrorCode::Overflow))?;
+    let new_sol_raised = proposal.sol_raised.checked_add(net_support_amount)
+        .ok_or_else(|| error!(ErrorCode::Overflow))?;
+    require!(
+        max_per_proposal == 0 || new_user_amount <= max_per_proposal,
+        ErrorCode::WalletProposalSupportCapExceeded
+    );
+    require!(
+        max_per_epoch == 0 || new_user_epoch_amount <= max_per_epoch,
+        ErrorCode::WalletEpochSupportCapExceeded
+    );
+    require!(
+        max_sol_raised == 0 || new_sol_raised <= max_sol_raised,
+        ErrorCode::ProposalSolRaisedCapExceeded
+    );
+
     // --- 1. Transférer les SOL de l'utilisateur --- 
 
-    // --- 1.a Transférer le montant net du support vers le compte de la proposition ---
+    // --- 1.a Transférer le montant net du support vers le vault de la proposition ---
     let cpi_context_support = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
         Transfer {
             from: user.to_account_info(),
-            to: proposal.to_account_info(), 
+            to: ctx.accounts.proposal_vault.to_account_info(),
         },
     );
     transfer(cpi_context_support, net_support_amount)?;
 
-    // --- 1.b Transférer les frais vers la trésorerie ---
+    // --- 1.b Prélever la part du parrain sur les frais ---
+    let mut referral_amount = 0u64;
+    if let Some(referral_account) = ctx.accounts.referral_account.as_mut() {
+        require_keys_neq!(referral_account.referrer, user.key(), ErrorCode::SelfReferral);
+        require_keys_neq!(referral_account.referrer, proposal.creator, ErrorCode::ReferrerIsCreator);
+
+        referral_amount = (fee_amount as u128)
+            .checked_mul(config.referral_fee_bps as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(ErrorCode::CalculationOverflow)? as u64;
+
+        let cpi_context_referral = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: user.to_account_info(),
+                to: referral_account.to_account_info(),
+            },
+        );
+        transfer(cpi_context_referral, referral_amount)?;
+
+        referral_account.pending_rewards = referral_account.pending_rewards.checked_add(referral_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        referral_account.total_earned = referral_account.total_earned.checked_add(referral_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        referral_account.referred_supports = referral_account.referred_supports.checked_add(1)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+    let treasury_fee_amount = fee_amount.checked_sub(referral_amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    // --- 1.c Transférer le reste des frais vers la trésorerie ---
     let cpi_accounts_fee_transfer = Transfer {
         from: user.to_account_info(),
         to: ctx.accounts.treasury.to_account_info(),
     };
     let cpi_program_fee_transfer = ctx.accounts.system_program.to_account_info();
     let cpi_context_fee_transfer = CpiContext::new(cpi_program_fee_transfer, cpi_accounts_fee_transfer);
-    transfer(cpi_context_fee_transfer, fee_amount)?;
+    transfer(cpi_context_fee_transfer, treasury_fee_amount)?;
 
-    // --- 1.c Distribuer les frais au sein de la trésorerie ---
+    // --- 1.d Distribuer les frais au sein de la trésorerie ---
     distribute_fees_to_treasury(
         &mut ctx.accounts.treasury,
-        fee_amount,
+        treasury_fee_amount,
         FeeType::ProposalSupport,
     )?;
 
@@ -128,6 +231,8 @@ pub fn handler(ctx: Context<SupportProposal>, amount: u64) -> Result<()> {
     proposal.sol_raised = proposal.sol_raised.checked_add(net_support_amount)
         .ok_or_else(|| error!(ErrorCode::Overflow))?;
 
+    check_vault_accounting(&ctx.accounts.proposal_vault.to_account_info(), proposal)?;
+
     // Incrémenter le nombre total de supporters *uniquement* si c'est un nouveau supporter
     if is_new_supporter {
         proposal.total_contributions = proposal.total_contributions.checked_add(1)
@@ -138,22 +243,36 @@ pub fn handler(ctx: Context<SupportProposal>, amount: u64) -> Result<()> {
         user_support.epoch_id = proposal.epoch_id;
         user_support.user = user.key();
         user_support.proposal = proposal.key();
+        user_support.version = USER_PROPOSAL_SUPPORT_VERSION;
     }
 
-    // --- 3. Mettre à jour le montant cumulé dans UserProposalSupport ---
+    // --- 3. Mettre à jour le montant cumulé dans UserProposalSupport et UserEpochSupport ---
+    let user_epoch_support = &mut ctx.accounts.user_epoch_support;
+    user_epoch_support.epoch_id = proposal.epoch_id;
+    user_epoch_support.user = user.key();
+    user_epoch_support.amount = new_user_epoch_amount;
+    user_epoch_support.version = USER_EPOCH_SUPPORT_VERSION;
+
     // Mettre à jour (cumuler) le montant total supporté par cet utilisateur (montant net)
     user_support.amount = user_support.amount.checked_add(net_support_amount)
         .ok_or_else(|| error!(ErrorCode::Overflow))?;
+    // Frais versés à la trésorerie (hors part du parrain), pour un éventuel remboursement au reclaim
+    user_support.fees_paid = user_support.fees_paid.checked_add(treasury_fee_amount)
+        .ok_or_else(|| error!(ErrorCode::Overflow))?;
 
-    msg!("User {} supported proposal {} with additional {} lamports (net), fee {} lamports. Total user support for this proposal: {} lamports. Epoch: {}",
+    msg!("User {} supported proposal {} with additional {} lamports (net), fee {} lamports (referral share {}). Total user support for this proposal: {} lamports. Epoch: {}",
         user_support.user,
         user_support.proposal,
         net_support_amount, // Montant net de cette transaction
         fee_amount,         // Montant des frais
+        referral_amount,    // Part reversée au parrain
         user_support.amount, // Montant total net cumulé par l'utilisateur pour cette proposition
         user_support.epoch_id
     );
 
+    // --- 4. Mettre à jour le classement de l'époque ---
+    ctx.accounts.leaderboard.load_mut()?.record(proposal.key(), proposal.sol_raised);
+
     Ok(())
 }
 
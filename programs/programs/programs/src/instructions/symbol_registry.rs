@@ -0,0 +1,137 @@
+// Libération et promotion des symboles réservés par les propositions.
+//
+// - release_proposal_symbol : une fois la proposition rejetée ou retirée par la modération, n'importe qui peut fermer
+//   la réservation du symbole pour l'époque (rente rendue au créateur, récompense de crank).
+// - register_launched_symbol : une fois la proposition validée, son symbole est inscrit dans
+//   le registre global des tokens lancés et ne pourra plus être proposé par la suite.
+
+use anchor_lang::prelude::*;
+use crate::constants::{SYMBOL_REGISTRY_VERSION, TREASURY_SEED};
+use crate::error::ErrorCode;
+use crate::state::{EpochManagement, ProgramConfig, ProposalStatus, SymbolRegistry, TokenProposal, Treasury};
+use crate::utils::{normalize_symbol, pay_crank_reward};
+
+#[derive(Accounts)]
+pub struct ReleaseProposalSymbol<'info> {
+    // N'importe quel crank ; reçoit la récompense
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", token_proposal.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_management: Account<'info, EpochManagement>,
+
+    #[account(
+        constraint = matches!(token_proposal.status, ProposalStatus::Rejected | ProposalStatus::Removed) @ ErrorCode::ProposalNotRejected
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"epoch_symbol",
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            normalize_symbol(&token_proposal.token_symbol).as_bytes()
+        ],
+        bump,
+        constraint = epoch_symbol.proposal == token_proposal.key() @ ErrorCode::SymbolRegistryMismatch,
+        close = creator
+    )]
+    pub epoch_symbol: Account<'info, SymbolRegistry>,
+
+    /// CHECK: Reçoit la rente du registre ; doit être le créateur de la proposition.
+    #[account(
+        mut,
+        address = token_proposal.creator @ ErrorCode::NotProposalCreator
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+pub fn release_proposal_symbol(ctx: Context<ReleaseProposalSymbol>) -> Result<()> {
+    msg!("Symbol {} released for epoch {} (proposal {} rejected)",
+        ctx.accounts.epoch_symbol.symbol,
+        ctx.accounts.epoch_symbol.epoch_id,
+        ctx.accounts.token_proposal.key());
+
+    pay_crank_reward(
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.epoch_management,
+        &ctx.accounts.authority.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterLaunchedSymbol<'info> {
+    // Paie la rente du registre global
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = token_proposal.status == ProposalStatus::Validated @ ErrorCode::ProposalNotValidated
+    )]
+    pub token_proposal: Account<'info, TokenProposal>,
+
+    // La réservation de l'époque n'est plus utile : le registre global prend le relais
+    #[account(
+        mut,
+        seeds = [
+            b"epoch_symbol",
+            token_proposal.epoch_id.to_le_bytes().as_ref(),
+            normalize_symbol(&token_proposal.token_symbol).as_bytes()
+        ],
+        bump,
+        constraint = epoch_symbol.proposal == token_proposal.key() @ ErrorCode::SymbolRegistryMismatch,
+        close = creator
+    )]
+    pub epoch_symbol: Account<'info, SymbolRegistry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SymbolRegistry::INIT_SPACE,
+        seeds = [b"launched_symbol", normalize_symbol(&token_proposal.token_symbol).as_bytes()],
+        bump
+    )]
+    pub launched_symbol: Account<'info, SymbolRegistry>,
+
+    /// CHECK: Reçoit la rente du registre de l'époque ; doit être le créateur de la proposition.
+    #[account(
+        mut,
+        address = token_proposal.creator @ ErrorCode::NotProposalCreator
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_launched_symbol(ctx: Context<RegisterLaunchedSymbol>) -> Result<()> {
+    let launched_symbol = &mut ctx.accounts.launched_symbol;
+    launched_symbol.epoch_id = ctx.accounts.epoch_symbol.epoch_id;
+    launched_symbol.symbol = ctx.accounts.epoch_symbol.symbol.clone();
+    launched_symbol.proposal = ctx.accounts.token_proposal.key();
+    launched_symbol.registered_at = Clock::get()?.unix_timestamp;
+    launched_symbol.version = SYMBOL_REGISTRY_VERSION;
+
+    msg!("Symbol {} registered as launched by proposal {}",
+        launched_symbol.symbol, launched_symbol.proposal);
+    Ok(())
+}
@@ -0,0 +1,265 @@
+// Treasury spending proposals for the norug.fun protocol
+// A role holder proposes a payout from a treasury category, past supporters vote on it
+// weighted by their historic support amounts, and anyone can execute it once the voting
+// window is over. The payout only happens if both quorum and majority are reached.
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{
+    EpochHistory, EpochManagement, RoleType, SpendingProposal, SpendingProposalStatus, SpendingVoteRecord,
+    Treasury, TreasuryCategory, TreasuryRoles, UserProposalSupport,
+};
+use crate::utils::withdraw_from_treasury;
+
+/// Only an admin or a CategoryManager of the targeted category can call this instruction.
+#[derive(Accounts)]
+#[instruction(spending_id: u64)]
+pub struct CreateSpendingProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury_roles"],
+        bump
+    )]
+    pub treasury_roles: Account<'info, TreasuryRoles>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + SpendingProposal::INIT_SPACE,
+        seeds = [b"spending", spending_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub spending_proposal: Account<'info, SpendingProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a spending proposal and opens its voting window.
+pub fn create_spending_proposal(
+    ctx: Context<CreateSpendingProposal>,
+    spending_id: u64,
+    category: TreasuryCategory,
+    recipient: Pubkey,
+    amount: u64,
+    description: String,
+) -> Result<()> {
+    let treasury_roles = &ctx.accounts.treasury_roles;
+    let proposer = ctx.accounts.proposer.key();
+
+    // Check that the signer is an admin or manages the targeted category
+    let is_admin = treasury_roles.authorities.contains(&proposer);
+    let manager_role = treasury_roles.roles.iter().find(|r| {
+        r.pubkey == proposer && r.role_type == RoleType::CategoryManager(category.clone())
+    });
+    require!(is_admin || manager_role.is_some(), ErrorCode::Unauthorized);
+
+    // Respect the withdrawal limit attached to the role, if any (admins are not limited)
+    if let Some(limit) = manager_role.and_then(|r| r.withdrawal_limit) {
+        require!(is_admin || amount <= limit, ErrorCode::WithdrawalLimitExceeded);
+    }
+
+    require!(amount > 0, ErrorCode::AmountMustBeGreaterThanZero);
+    require!(description.len() <= 256, ErrorCode::SpendingDescriptionTooLong);
+
+    // The sub-account must be able to cover the payout at creation time (checked again on execution)
+    let sub_account_balance = ctx.accounts.treasury.sub_account(&category).sol_balance;
+    require!(sub_account_balance >= amount, ErrorCode::InsufficientTreasuryBalance);
+
+    let now = Clock::get()?.unix_timestamp;
+    let spending = &mut ctx.accounts.spending_proposal;
+    spending.spending_id = spending_id;
+    spending.proposer = proposer;
+    spending.category = category;
+    spending.recipient = recipient;
+    spending.amount = amount;
+    spending.description = description;
+    spending.voting_start = now;
+    spending.voting_end = now
+        .checked_add(SPENDING_PROPOSAL_VOTING_PERIOD_SECONDS)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    spending.votes_for = 0;
+    spending.votes_against = 0;
+    spending.voters_count = 0;
+    spending.status = SpendingProposalStatus::Voting;
+    spending.version = SPENDING_PROPOSAL_VERSION;
+
+    msg!("Spending proposal {} created: {} lamports from {:?} to {}, voting until {}",
+        spending_id, amount, spending.category, recipient, spending.voting_end);
+    Ok(())
+}
+
+/// Any past supporter can vote once per spending proposal.
+/// The voting weight is provided as remaining accounts: (user_proposal_support, epoch_management) pairs
+/// belonging to the voter, from epochs that were processed before the voting window opened.
+/// For an archived epoch, the epoch_history account takes the place of epoch_management; once its
+/// summary has been evicted from the rolling history, the support no longer carries any weight.
+#[derive(Accounts)]
+pub struct VoteSpendingProposal<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"spending", spending_proposal.spending_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub spending_proposal: Account<'info, SpendingProposal>,
+
+    // One vote record per voter and per spending proposal: a second vote fails on init
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + SpendingVoteRecord::INIT_SPACE,
+        seeds = [b"spending_vote", spending_proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, SpendingVoteRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Records a weighted vote for or against a spending proposal.
+pub fn vote_spending_proposal(ctx: Context<VoteSpendingProposal>, approve: bool) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let voter = ctx.accounts.voter.key();
+    let spending = &mut ctx.accounts.spending_proposal;
+
+    require!(spending.status == SpendingProposalStatus::Voting, ErrorCode::SpendingProposalAlreadyFinalized);
+    require!(now < spending.voting_end, ErrorCode::VotingClosed);
+
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty()
+            && remaining.chunks_exact(2).remainder().is_empty()
+            && remaining.len() / 2 <= SPENDING_PROPOSAL_MAX_SUPPORT_ACCOUNTS_PER_VOTE,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    // Sum the historic support of the voter
+    let (epoch_history_key, _) = Pubkey::find_program_address(&[EPOCH_HISTORY_SEED], &crate::ID);
+    let mut weight: u64 = 0;
+    let mut seen_supports: Vec<Pubkey> = Vec::with_capacity(remaining.len() / 2);
+    for pair in remaining.chunks(2) {
+        let (support_info, epoch_info) = (&pair[0], &pair[1]);
+        require!(
+            support_info.owner == &crate::ID && epoch_info.owner == &crate::ID,
+            ErrorCode::IneligibleSupportAccount
+        );
+        require!(!seen_supports.contains(support_info.key), ErrorCode::DuplicateSupportAccount);
+        seen_supports.push(support_info.key());
+
+        let support = UserProposalSupport::try_deserialize(&mut &support_info.try_borrow_data()?[..])?;
+
+        // The support must come from an epoch that was processed: either still open
+        // (epoch_management) or archived (its summary in epoch_history).
+        let epoch_end_time = if epoch_info.key() == epoch_history_key {
+            let history = EpochHistory::try_deserialize(&mut &epoch_info.try_borrow_data()?[..])?;
+            history.summaries.iter()
+                .find(|summary| summary.epoch_id == support.epoch_id)
+                .map(|summary| summary.end_time)
+                .ok_or(ErrorCode::IneligibleSupportAccount)?
+        } else {
+            let epoch = EpochManagement::try_deserialize(&mut &epoch_info.try_borrow_data()?[..])?;
+            require!(
+                support.epoch_id == epoch.epoch_id && epoch.processed,
+                ErrorCode::IneligibleSupportAccount
+            );
+            epoch.end_time
+        };
+
+        // The support must belong to the voter and its epoch must have ended
+        // before the voting window opened.
+        require!(
+            support.user == voter && epoch_end_time <= spending.voting_start,
+            ErrorCode::IneligibleSupportAccount
+        );
+
+        weight = weight.checked_add(support.amount).ok_or(ErrorCode::CalculationOverflow)?;
+    }
+    require!(weight > 0, ErrorCode::NoVotingWeight);
+
+    if approve {
+        spending.votes_for = spending.votes_for.checked_add(weight).ok_or(ErrorCode::CalculationOverflow)?;
+    } else {
+        spending.votes_against = spending.votes_against.checked_add(weight).ok_or(ErrorCode::CalculationOverflow)?;
+    }
+    spending.voters_count = spending.voters_count.checked_add(1).ok_or(ErrorCode::CalculationOverflow)?;
+
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.spending_proposal = spending.key();
+    vote_record.voter = voter;
+    vote_record.weight = weight;
+    vote_record.approve = approve;
+    vote_record.version = SPENDING_VOTE_RECORD_VERSION;
+
+    msg!("Voter {} voted {} on spending proposal {} with weight {}",
+        voter, if approve { "for" } else { "against" }, spending.spending_id, weight);
+    Ok(())
+}
+
+/// Anyone can finalize a spending proposal once its voting window is over.
+#[derive(Accounts)]
+pub struct ExecuteSpendingProposal<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"spending", spending_proposal.spending_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub spending_proposal: Account<'info, SpendingProposal>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Only receives lamports; must match the recipient stored in the spending proposal.
+    #[account(
+        mut,
+        address = spending_proposal.recipient @ ErrorCode::InvalidRecipient
+    )]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+/// Pays the recipient if quorum and majority are met, otherwise rejects the spending proposal.
+pub fn execute_spending_proposal(ctx: Context<ExecuteSpendingProposal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let spending = &mut ctx.accounts.spending_proposal;
+
+    require!(spending.status == SpendingProposalStatus::Voting, ErrorCode::SpendingProposalAlreadyFinalized);
+    require!(now >= spending.voting_end, ErrorCode::VotingStillOpen);
+
+    let total_votes = spending.votes_for
+        .checked_add(spending.votes_against)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let quorum_reached = total_votes >= SPENDING_PROPOSAL_QUORUM_LAMPORTS;
+    let majority_reached = spending.votes_for > spending.votes_against;
+
+    if quorum_reached && majority_reached {
+        withdraw_from_treasury(
+            &mut ctx.accounts.treasury,
+            &spending.category,
+            &ctx.accounts.recipient.to_account_info(),
+            spending.amount,
+        )?;
+        spending.status = SpendingProposalStatus::Executed;
+    } else {
+        spending.status = SpendingProposalStatus::Rejected;
+    }
+
+    msg!("Spending proposal {} finalized as {:?} (for: {}, against: {}, quorum reached: {})",
+        spending.spending_id, spending.status, spending.votes_for, spending.votes_against, quorum_reached);
+    Ok(())
+}
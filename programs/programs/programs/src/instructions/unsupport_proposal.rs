@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::state::{EpochManagement, TokenProposal, UserProposalSupport, UserEpochSupport, EpochStatus, ProposalStatus, ProgramConfig, Treasury, EpochLeaderboard};
+use crate::error::ErrorCode;
+use crate::constants::{BPS_DENOMINATOR, TREASURY_SEED, LEADERBOARD_SEED, PROPOSAL_VAULT_SEED};
+use crate::utils::fee_distribution::{distribute_fees_to_treasury, FeeType};
+use crate::utils::proposal_vault::{check_vault_accounting, transfer_from_vault};
+
+// Retrait partiel ou total d'un support tant que l'époque est active.
+// Les SOL sont rendus depuis le vault de la proposition, moins une éventuelle pénalité
+// de sortie versée à la trésorerie. Un retrait total ferme le compte de support,
+// ainsi que le compte de suivi de l'époque s'il ne reste plus rien d'engagé.
+// Une proposition signalée par la modération peut être quittée sans pénalité.
+#[derive(Accounts)]
+pub struct UnsupportProposal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        constraint = epoch.status == EpochStatus::Active @ ErrorCode::EpochNotActive,
+        constraint = epoch.epoch_id == proposal.epoch_id @ ErrorCode::ProposalEpochMismatch
+    )]
+    pub epoch: Account<'info, EpochManagement>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            proposal.creator.as_ref(),
+            proposal.epoch_id.to_le_bytes().as_ref(),
+            proposal.token_name.as_bytes(),
+        ],
+        bump,
+        constraint = matches!(proposal.status, ProposalStatus::Active | ProposalStatus::Flagged) @ ErrorCode::ProposalNotActive
+    )]
+    pub proposal: Account<'info, TokenProposal>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_VAULT_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"support",
+            proposal.epoch_id.to_le_bytes().as_ref(),
+            user.key().as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+        constraint = user_support.user == user.key() @ ErrorCode::InvalidAuthority,
+        constraint = user_support.amount > 0 @ ErrorCode::NothingToReclaim
+    )]
+    pub user_support: Account<'info, UserProposalSupport>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"user_epoch_support",
+            proposal.epoch_id.to_le_bytes().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub user_epoch_support: Account<'info, UserEpochSupport>,
+
+    // Classement de l'époque, la proposition y redescend
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, epoch.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UnsupportProposal>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountMustBeGreaterThanZero);
+    require!(amount <= ctx.accounts.user_support.amount, ErrorCode::AmountExceedsSupport);
+
+    // Pénalité de sortie éventuelle (aucune sur une proposition signalée), le reste est rendu à l'utilisateur
+    let penalty_bps = if ctx.accounts.proposal.status == ProposalStatus::Flagged {
+        0
+    } else {
+        ctx.accounts.program_config.unsupport_penalty_bps
+    };
+    let penalty = amount
+        .checked_mul(penalty_bps as u64)
+        .ok_or(ErrorCode::CalculationOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let refund = amount.checked_sub(penalty).ok_or(ErrorCode::CalculationOverflow)?;
+
+    let vault_info = ctx.accounts.proposal_vault.to_account_info();
+    require!(vault_info.lamports() >= amount, ErrorCode::InsufficientProposalFunds);
+
+    // --- Transferts signés depuis le vault de la proposition ---
+    let proposal_key = ctx.accounts.proposal.key();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    transfer_from_vault(&vault_info, ctx.bumps.proposal_vault, &proposal_key,
+        &ctx.accounts.user.to_account_info(), &system_program_info, refund)?;
+    if penalty > 0 {
+        transfer_from_vault(&vault_info, ctx.bumps.proposal_vault, &proposal_key,
+            &ctx.accounts.treasury.to_account_info(), &system_program_info, penalty)?;
+        distribute_fees_to_treasury(&mut ctx.accounts.treasury, penalty, FeeType::UnsupportPenalty)?;
+    }
+
+    // --- Mise à jour des compteurs ---
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.sol_raised = proposal.sol_raised.checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    check_vault_accounting(&vault_info, proposal)?;
+
+    // Une proposition signalée reste masquée du classement
+    if proposal.status == ProposalStatus::Active {
+        ctx.accounts.leaderboard.load_mut()?.record(proposal.key(), proposal.sol_raised);
+    }
+
+    let user_epoch_support = &mut ctx.accounts.user_epoch_support;
+    user_epoch_support.amount = user_epoch_support.amount.saturating_sub(amount);
+
+    let user_support = &mut ctx.accounts.user_support;
+    // Les frais de la part retirée ne sont plus remboursables
+    let withdrawn_fees = user_support.fees_for_amount(amount);
+    user_support.fees_paid = user_support.fees_paid.saturating_sub(withdrawn_fees);
+    user_support.amount = user_support.amount.checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("User {} withdrew {} lamports from proposal {} (refund {}, penalty {}). Remaining support: {} lamports",
+        ctx.accounts.user.key(), amount, proposal.key(), refund, penalty, user_support.amount);
+
+    // Retrait total : le supporter ne compte plus et son compte de support est fermé
+    if user_support.amount == 0 {
+        proposal.total_contributions = proposal.total_contributions.checked_sub(1)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        ctx.accounts.user_support.close(ctx.accounts.user.to_account_info())?;
+    }
+
+    // Plus aucun SOL engagé dans l'époque : le compte de suivi est fermé (recréé au besoin par support_proposal)
+    if ctx.accounts.user_epoch_support.amount == 0 {
+        ctx.accounts.user_epoch_support.close(ctx.accounts.user.to_account_info())?;
+    }
+
+    Ok(())
+}
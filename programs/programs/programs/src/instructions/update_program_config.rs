@@ -0,0 +1,153 @@
+// Instructions de mise à jour des paramètres de ProgramConfig.
+//
+// Chaque groupe de paramètres a sa propre instruction afin que le service backend
+// puisse en ajuster un sans avoir à renvoyer toute la configuration.
+// Seule l'`admin_authority` définie dans ProgramConfig peut les appeler.
+
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::constants::{BPS_DENOMINATOR, MAX_REFERRAL_FEE_BPS, MAX_UNSUPPORT_PENALTY_BPS};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin_authority @ ErrorCode::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    // Doit correspondre à l'admin_authority de ProgramConfig
+    pub admin_authority: Signer<'info>,
+}
+
+pub fn update_crank_reward_config(
+    ctx: Context<UpdateProgramConfig>,
+    crank_reward_lamports: u64,
+    crank_reward_epoch_cap: u64,
+) -> Result<()> {
+    require!(
+        crank_reward_lamports <= crank_reward_epoch_cap,
+        ErrorCode::InvalidCrankRewardConfig
+    );
+
+    let config = &mut ctx.accounts.program_config;
+    config.crank_reward_lamports = crank_reward_lamports;
+    config.crank_reward_epoch_cap = crank_reward_epoch_cap;
+
+    msg!("Crank reward set to {} lamports per call, capped at {} lamports per epoch",
+        crank_reward_lamports, crank_reward_epoch_cap);
+    Ok(())
+}
+
+/// Plafonds de support en lamports nets ; 0 désactive le plafond correspondant.
+pub fn update_support_caps(
+    ctx: Context<UpdateProgramConfig>,
+    max_support_per_wallet_per_proposal: u64,
+    max_support_per_wallet_per_epoch: u64,
+    max_sol_raised_per_proposal: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.program_config;
+    config.max_support_per_wallet_per_proposal = max_support_per_wallet_per_proposal;
+    config.max_support_per_wallet_per_epoch = max_support_per_wallet_per_epoch;
+    config.max_sol_raised_per_proposal = max_sol_raised_per_proposal;
+
+    msg!("Support caps set to {} per wallet per proposal, {} per wallet per epoch, {} per proposal",
+        max_support_per_wallet_per_proposal, max_support_per_wallet_per_epoch, max_sol_raised_per_proposal);
+    Ok(())
+}
+
+/// Seuils minimums (SOL levés nets, supporters distincts) pour qu'une proposition puisse
+/// être validée ; 0 désactive le seuil correspondant.
+pub fn update_launch_thresholds(
+    ctx: Context<UpdateProgramConfig>,
+    min_sol_raised_for_launch: u64,
+    min_contributors_for_launch: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.program_config;
+    config.min_sol_raised_for_launch = min_sol_raised_for_launch;
+    config.min_contributors_for_launch = min_contributors_for_launch;
+
+    msg!("Launch thresholds set to {} lamports raised and {} contributors",
+        min_sol_raised_for_launch, min_contributors_for_launch);
+    Ok(())
+}
+
+/// Pénalité de sortie appliquée par unsupport_proposal, en points de base.
+pub fn update_unsupport_penalty(
+    ctx: Context<UpdateProgramConfig>,
+    unsupport_penalty_bps: u16,
+) -> Result<()> {
+    require!(
+        unsupport_penalty_bps <= MAX_UNSUPPORT_PENALTY_BPS,
+        ErrorCode::InvalidUnsupportPenalty
+    );
+
+    ctx.accounts.program_config.unsupport_penalty_bps = unsupport_penalty_bps;
+
+    msg!("Unsupport penalty set to {} bps", unsupport_penalty_bps);
+    Ok(())
+}
+
+/// Part des frais de support remboursée lors d'un reclaim, en points de base.
+pub fn update_support_fee_refund(
+    ctx: Context<UpdateProgramConfig>,
+    support_fee_refund_bps: u16,
+) -> Result<()> {
+    require!(
+        support_fee_refund_bps as u64 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidSupportFeeRefund
+    );
+
+    ctx.accounts.program_config.support_fee_refund_bps = support_fee_refund_bps;
+
+    msg!("Support fee refund set to {} bps", support_fee_refund_bps);
+    Ok(())
+}
+
+/// Délai de grâce, en secondes, entre le traitement d'une époque et son archivage.
+pub fn update_epoch_archive_grace_period(
+    ctx: Context<UpdateProgramConfig>,
+    epoch_archive_grace_period: i64,
+) -> Result<()> {
+    require!(epoch_archive_grace_period >= 0, ErrorCode::InvalidGracePeriod);
+
+    ctx.accounts.program_config.epoch_archive_grace_period = epoch_archive_grace_period;
+
+    msg!("Epoch archive grace period set to {} seconds", epoch_archive_grace_period);
+    Ok(())
+}
+
+/// Part des frais de support reversée au parrain, en points de base.
+pub fn update_referral_fee(
+    ctx: Context<UpdateProgramConfig>,
+    referral_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        referral_fee_bps <= MAX_REFERRAL_FEE_BPS,
+        ErrorCode::InvalidReferralFee
+    );
+
+    ctx.accounts.program_config.referral_fee_bps = referral_fee_bps;
+
+    msg!("Referral fee set to {} bps", referral_fee_bps);
+    Ok(())
+}
+
+/// Caution des créateurs : montant déposé à chaque proposition et seuil de levée qui la rend
+/// avant le lancement. S'applique aux propositions créées ensuite.
+pub fn update_creator_bond(
+    ctx: Context<UpdateProgramConfig>,
+    creator_bond_lamports: u64,
+    creator_bond_min_raise: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.program_config;
+    config.creator_bond_lamports = creator_bond_lamports;
+    config.creator_bond_min_raise = creator_bond_min_raise;
+
+    msg!("Creator bond set to {} lamports, released from {} lamports raised",
+        creator_bond_lamports, creator_bond_min_raise);
+    Ok(())
+}
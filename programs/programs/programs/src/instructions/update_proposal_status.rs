@@ -1,18 +1,24 @@
 use anchor_lang::prelude::*;
-use crate::state::{EpochManagement, EpochStatus, TokenProposal, ProposalStatus, ProgramConfig};
+use crate::state::{EpochLeaderboard, EpochManagement, EpochStatus, TokenProposal, ProposalStatus, ProgramConfig};
+use crate::constants::LEADERBOARD_SEED;
 // Placeholder pour un compte de configuration global - à définir dans state/mod.rs
 // use crate::state::ProgramConfig;
 use crate::error::ErrorCode;
 
 #[derive(Accounts)]
 pub struct UpdateProposalStatus<'info> {
-    // L'autorité doit signer et correspondre à l'autorité configurée dans ProgramConfig
+    // L'autorité doit signer et correspondre à l'autorité configurée dans ProgramConfig.
     pub authority: Signer<'info>,
     // Le compte contenant la configuration globale (et l'autorité admin)
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
     pub program_config: Account<'info, ProgramConfig>,
 
-    // L'époque doit être fermée
+    // L'époque doit être fermée (mutable pour ses statistiques)
     #[account(
+        mut,
         constraint = epoch_management.status == EpochStatus::Closed @ ErrorCode::EpochNotClosed
     )]
     pub epoch_management: Account<'info, EpochManagement>,
@@ -24,30 +30,99 @@ pub struct UpdateProposalStatus<'info> {
     )]
     pub proposal: Account<'info, TokenProposal>,
 
+    // Classement de l'époque, dont la proposition sort une fois finalisée
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, epoch_management.epoch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, EpochLeaderboard>,
+
     // On a besoin de system_program pour la création potentielle de comptes PDA,
     // même s'il n'est pas directement utilisé ici, il est souvent nécessaire
     // pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<UpdateProposalStatus>, new_status: ProposalStatus) -> Result<()> {
+pub fn handler(ctx: Context<UpdateProposalStatus>, mut new_status: ProposalStatus) -> Result<()> {
     // Modification: Ajout de la vérification d'autorité ici
     require!(
         ctx.accounts.authority.key() == ctx.accounts.program_config.admin_authority,
         ErrorCode::Unauthorized
     );
 
+    // Une proposition signalée doit d'abord être tranchée par la modération : retirée (caution
+    // confisquée) ou rétablie. La rejeter ici rendrait la caution dès le seuil de levée atteint.
+    require!(
+        ctx.accounts.proposal.status != ProposalStatus::Flagged,
+        ErrorCode::ProposalIsFlagged
+    );
+
     // Vérifier que la proposition est actuellement active avant de la finaliser
-    require!(ctx.accounts.proposal.status == ProposalStatus::Active, ErrorCode::ProposalAlreadyFinalized);
+    require!(
+        ctx.accounts.proposal.status == ProposalStatus::Active,
+        ErrorCode::ProposalAlreadyFinalized
+    );
 
-    // La vérification de l'autorité est gérée par la contrainte.
-    // La vérification que new_status est Validated ou Rejected pourrait être ajoutée ici si nécessaire,
-    // mais on suppose que le service off-chain envoie une valeur correcte.
+    // Le crank ne peut que finaliser la proposition : Cancelled est réservé au créateur.
+    require!(
+        matches!(new_status, ProposalStatus::Validated | ProposalStatus::Rejected),
+        ErrorCode::InvalidProposalStatusUpdate
+    );
+
+    // Une proposition sous les seuils d'éligibilité ne peut pas être lancée :
+    // elle est rejetée d'office et ses supporters peuvent réclamer leurs fonds.
+    if new_status == ProposalStatus::Validated {
+        let config = &ctx.accounts.program_config;
+        let epoch_config = &ctx.accounts.epoch_management.config;
+        let proposal = &ctx.accounts.proposal;
+        let enough_sol = proposal.sol_raised >= epoch_config.min_sol_raised_for_launch(config);
+        let enough_contributors = proposal.total_contributions >= epoch_config.min_contributors_for_launch(config);
+        if !enough_sol || !enough_contributors {
+            msg!("Proposal {} is not eligible for launch ({} lamports raised, {} contributors): rejecting it",
+                 proposal.key(), proposal.sol_raised, proposal.total_contributions);
+            new_status = ProposalStatus::Rejected;
+        }
+    }
+
+    // Une fois le nombre de gagnants de l'époque atteint, les validations suivantes sont rejetées
+    // d'office de la même façon. Les places doivent aller aux propositions les mieux classées : les
+    // propositions finalisées sortent du classement, et une validation doit avoir levé au moins
+    // autant que la tête des propositions restantes. Une proposition que le classement plein ne
+    // suit pas (voir `EpochLeaderboard::record`) n'est comparée qu'à cette tête.
+    if new_status == ProposalStatus::Validated {
+        let epoch = &ctx.accounts.epoch_management;
+        if let Some(max_winners) = epoch.config.max_winners {
+            let sol_raised = ctx.accounts.proposal.sol_raised;
+            let leaderboard = ctx.accounts.leaderboard.load()?;
+            if let Some(best) = leaderboard.ranking().first() {
+                require!(sol_raised >= best.sol_raised, ErrorCode::ProposalOutOfRankOrder);
+            }
+            if epoch.winners_count >= max_winners {
+                msg!("Epoch {} already has its {} winners: rejecting proposal {}",
+                     epoch.epoch_id, epoch.winners_count, ctx.accounts.proposal.key());
+                new_status = ProposalStatus::Rejected;
+            }
+        }
+    }
 
     msg!("Updating proposal {} status from {:?} to {:?}", 
          ctx.accounts.proposal.key(), 
          ctx.accounts.proposal.status, // Log l'ancien statut (devrait être Active)
          new_status);
+    // Statistiques de l'époque : une proposition validée est lancée et ne sera jamais fermée
+    let raised = ctx.accounts.proposal.sol_raised;
+    let epoch = &mut ctx.accounts.epoch_management;
+    epoch.total_raised = epoch.total_raised.checked_add(raised)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    if new_status == ProposalStatus::Validated {
+        epoch.winners_count = epoch.winners_count.checked_add(1)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        epoch.proposals_settled = epoch.proposals_settled.checked_add(1)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
     ctx.accounts.proposal.status = new_status;
+    ctx.accounts.leaderboard.load_mut()?.remove(ctx.accounts.proposal.key());
 
     Ok(())
 } 
\ No newline at end of file
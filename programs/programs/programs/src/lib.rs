@@ -29,6 +29,68 @@ pub mod programs {
         instructions::initialize_program_config::handler(ctx, admin_authority)
     }
 
+    // --- Mise à jour des paramètres de ProgramConfig ---
+    pub fn update_crank_reward_config(
+        ctx: Context<UpdateProgramConfig>,
+        crank_reward_lamports: u64,
+        crank_reward_epoch_cap: u64,
+    ) -> Result<()> {
+        update_program_config::update_crank_reward_config(ctx, crank_reward_lamports, crank_reward_epoch_cap)
+    }
+
+    pub fn update_support_caps(
+        ctx: Context<UpdateProgramConfig>,
+        max_support_per_wallet_per_proposal: u64,
+        max_support_per_wallet_per_epoch: u64,
+        max_sol_raised_per_proposal: u64,
+    ) -> Result<()> {
+        update_program_config::update_support_caps(ctx, max_support_per_wallet_per_proposal, max_support_per_wallet_per_epoch, max_sol_raised_per_proposal)
+    }
+
+    pub fn update_launch_thresholds(
+        ctx: Context<UpdateProgramConfig>,
+        min_sol_raised_for_launch: u64,
+        min_contributors_for_launch: u64,
+    ) -> Result<()> {
+        update_program_config::update_launch_thresholds(ctx, min_sol_raised_for_launch, min_contributors_for_launch)
+    }
+
+    pub fn update_unsupport_penalty(
+        ctx: Context<UpdateProgramConfig>,
+        unsupport_penalty_bps: u16,
+    ) -> Result<()> {
+        update_program_config::update_unsupport_penalty(ctx, unsupport_penalty_bps)
+    }
+
+    pub fn update_support_fee_refund(
+        ctx: Context<UpdateProgramConfig>,
+        support_fee_refund_bps: u16,
+    ) -> Result<()> {
+        update_program_config::update_support_fee_refund(ctx, support_fee_refund_bps)
+    }
+
+    pub fn update_epoch_archive_grace_period(
+        ctx: Context<UpdateProgramConfig>,
+        epoch_archive_grace_period: i64,
+    ) -> Result<()> {
+        update_program_config::update_epoch_archive_grace_period(ctx, epoch_archive_grace_period)
+    }
+
+    pub fn update_referral_fee(
+        ctx: Context<UpdateProgramConfig>,
+        referral_fee_bps: u16,
+    ) -> Result<()> {
+        update_program_config::update_referral_fee(ctx, referral_fee_bps)
+    }
+
+    pub fn update_creator_bond(
+        ctx: Context<UpdateProgramConfig>,
+        creator_bond_lamports: u64,
+        creator_bond_min_raise: u64,
+    ) -> Result<()> {
+        update_program_config::update_creator_bond(ctx, creator_bond_lamports, creator_bond_min_raise)
+    }
+
     pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
         initialize::handler(_ctx)
     }
@@ -38,10 +100,21 @@ pub mod programs {
         epoch_id: u64,
         start_time: i64,
         end_time: i64,
+        config: Option<EpochConfig>, // Règles propres à l'époque (None = règles globales)
     ) -> Result<()>{
-        start_epoch::handler(ctx, epoch_id, start_time, end_time)
+        start_epoch::handler(ctx, epoch_id, start_time, end_time, config)
+    }
+
+    // Liste d'autorisation Merkle de l'époque (None = époque ouverte)
+    pub fn set_epoch_allowlist(
+        ctx: Context<SetEpochAllowlist>,
+        allowlist_root: Option<[u8; 32]>,
+        gates_creators: bool,
+    ) -> Result<()> {
+        epoch_allowlist::set_epoch_allowlist(ctx, allowlist_root, gates_creators)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_proposal(
         ctx: Context<CreateTokenProposal>,
         token_name: String,
@@ -51,12 +124,55 @@ pub mod programs {
         total_supply: u64,
         creator_allocation: u8,
         lockup_period: i64,
+        allowlist_proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-       create_token_proposal::handler(ctx, token_name, token_symbol, description, image_url, total_supply, creator_allocation, lockup_period)
+       create_token_proposal::handler(ctx, token_name, token_symbol, description, image_url, total_supply, creator_allocation, lockup_period, allowlist_proof)
     }
 
-    pub fn support_proposal(ctx: Context<SupportProposal>, amount: u64) -> Result<()> {
-        support_proposal::handler(ctx, amount)
+    // --- Gestion d'une proposition par son créateur ---
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_proposal_metadata(
+        ctx: Context<UpdateProposalMetadata>,
+        description: Option<String>,
+        image_url: Option<String>,
+        clear_image: bool,
+        total_supply: Option<u64>,
+        creator_allocation: Option<u8>,
+        lockup_period: Option<i64>,
+    ) -> Result<()> {
+        manage_proposal::update_proposal_metadata(ctx, description, image_url, clear_image, total_supply, creator_allocation, lockup_period)
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        manage_proposal::cancel_proposal(ctx)
+    }
+
+    pub fn support_proposal(ctx: Context<SupportProposal>, amount: u64, allowlist_proof: Vec<[u8; 32]>) -> Result<()> {
+        support_proposal::handler(ctx, amount, allowlist_proof)
+    }
+
+    pub fn unsupport_proposal(ctx: Context<UnsupportProposal>, amount: u64) -> Result<()> {
+        unsupport_proposal::handler(ctx, amount)
+    }
+
+    pub fn reallocate_support(ctx: Context<ReallocateSupport>, amount: u64) -> Result<()> {
+        reallocate_support::handler(ctx, amount)
+    }
+
+    // Passage d'une époque planifiée à Active, sans permission une fois start_time atteint
+    pub fn activate_epoch(
+        ctx: Context<ActivateEpoch>,
+        epoch_id: u64,
+    ) -> Result<()> {
+        activate_epoch::handler(ctx, epoch_id)
+    }
+
+    // Création sans permission du classement d'une époque antérieure à EpochLeaderboard
+    pub fn init_epoch_leaderboard(
+        ctx: Context<InitEpochLeaderboard>,
+        epoch_id: u64,
+    ) -> Result<()> {
+        init_epoch_leaderboard::handler(ctx, epoch_id)
     }
 
     pub fn end_epoch(
@@ -88,6 +204,95 @@ pub mod programs {
         reclaim_support::handler(ctx)
     }
 
+    // Réclamation groupée : paires (token_proposal, user_proposal_support) en remaining accounts
+    pub fn reclaim_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReclaimMany<'info>>
+    ) -> Result<()> {
+        reclaim_many::handler(ctx)
+    }
+
+    // Remboursement permissionless d'un supporter par le crank
+    pub fn push_refund(ctx: Context<PushRefund>) -> Result<()> {
+        push_refund::handler(ctx)
+    }
+
+    // Fermeture d'une proposition rejetée dont tous les supporters ont été remboursés
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        close_proposal::handler(ctx)
+    }
+
+    // --- Modération des propositions ---
+    pub fn flag_proposal(ctx: Context<FlagProposal>, reason: ModerationReason) -> Result<()> {
+        moderation::flag_proposal(ctx, reason)
+    }
+
+    pub fn appeal_proposal_flag(ctx: Context<AppealProposalFlag>) -> Result<()> {
+        moderation::appeal_proposal_flag(ctx)
+    }
+
+    pub fn unflag_proposal(ctx: Context<UnflagProposal>) -> Result<()> {
+        moderation::unflag_proposal(ctx)
+    }
+
+    pub fn remove_proposal(ctx: Context<RemoveProposal>) -> Result<()> {
+        moderation::remove_proposal(ctx)
+    }
+
+    // Règlement de la caution du créateur (rendue ou confisquée selon l'issue de la proposition)
+    pub fn settle_creator_bond(ctx: Context<SettleCreatorBond>) -> Result<()> {
+        settle_creator_bond::handler(ctx)
+    }
+
+    // Archivage d'une époque traitée dont toutes les propositions sont lancées ou fermées
+    pub fn archive_epoch(ctx: Context<ArchiveEpoch>) -> Result<()> {
+        archive_epoch::handler(ctx)
+    }
+
+    // --- Migrations des comptes versionnés ---
+    pub fn migrate_program_config(ctx: Context<MigrateProgramConfig>) -> Result<()> {
+        migrate_accounts::migrate_program_config(ctx)
+    }
+
+    pub fn migrate_treasury(ctx: Context<MigrateTreasury>) -> Result<()> {
+        migrate_accounts::migrate_treasury(ctx)
+    }
+
+    pub fn migrate_treasury_roles(ctx: Context<MigrateTreasuryRoles>) -> Result<()> {
+        migrate_accounts::migrate_treasury_roles(ctx)
+    }
+
+    pub fn migrate_epoch_management(ctx: Context<MigrateEpochManagement>, epoch_id: u64) -> Result<()> {
+        migrate_accounts::migrate_epoch_management(ctx, epoch_id)
+    }
+
+    pub fn backfill_legacy_epoch(
+        ctx: Context<BackfillLegacyEpoch>,
+        epoch_id: u64,
+        proposals_count: u64,
+        proposals_settled: u64,
+        winners_count: u64,
+        total_raised: u64,
+    ) -> Result<()> {
+        migrate_accounts::backfill_legacy_epoch(ctx, epoch_id, proposals_count, proposals_settled, winners_count, total_raised)
+    }
+
+    pub fn migrate_token_proposal(ctx: Context<MigrateTokenProposal>) -> Result<()> {
+        migrate_accounts::migrate_token_proposal(ctx)
+    }
+
+    pub fn migrate_user_proposal_support(ctx: Context<MigrateUserProposalSupport>) -> Result<()> {
+        migrate_accounts::migrate_user_proposal_support(ctx)
+    }
+
+    // --- Registre des symboles ---
+    pub fn release_proposal_symbol(ctx: Context<ReleaseProposalSymbol>) -> Result<()> {
+        symbol_registry::release_proposal_symbol(ctx)
+    }
+
+    pub fn register_launched_symbol(ctx: Context<RegisterLaunchedSymbol>) -> Result<()> {
+        symbol_registry::register_launched_symbol(ctx)
+    }
+
     pub fn initialize_treasury_roles(
         ctx: Context<InitializeTreasuryRoles>,
         authorities: Vec<Pubkey>,
@@ -140,4 +345,58 @@ pub mod programs {
     ) -> Result<()> {
         manage_treasury_role::update_treasury_role(ctx, role_type, pubkey, withdrawal_limit, withdrawal_period)
     }
+
+    // --- Propositions de dépenses de la trésorerie votées par les co-fondateurs ---
+    pub fn create_spending_proposal(
+        ctx: Context<CreateSpendingProposal>,
+        spending_id: u64,
+        category: TreasuryCategory,
+        recipient: Pubkey,
+        amount: u64,
+        description: String,
+    ) -> Result<()> {
+        treasury_spending_proposal::create_spending_proposal(ctx, spending_id, category, recipient, amount, description)
+    }
+
+    pub fn vote_spending_proposal(
+        ctx: Context<VoteSpendingProposal>,
+        approve: bool,
+    ) -> Result<()> {
+        treasury_spending_proposal::vote_spending_proposal(ctx, approve)
+    }
+
+    pub fn execute_spending_proposal(
+        ctx: Context<ExecuteSpendingProposal>,
+    ) -> Result<()> {
+        treasury_spending_proposal::execute_spending_proposal(ctx)
+    }
+
+    // --- Flux de paiement récurrents depuis la trésorerie ---
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        stream_id: u64,
+        category: TreasuryCategory,
+        recipient: Pubkey,
+        rate_per_second: u64,
+        end_time: i64,
+    ) -> Result<()> {
+        payment_stream::create_stream(ctx, stream_id, category, recipient, rate_per_second, end_time)
+    }
+
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        payment_stream::claim_stream(ctx)
+    }
+
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        payment_stream::cancel_stream(ctx)
+    }
+
+    // --- Parrainage ---
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        referral::register_referrer(ctx)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        referral::claim_referral_rewards(ctx)
+    }
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::constants::{BPS_DENOMINATOR, EPOCH_HISTORY_CAPACITY, LEADERBOARD_CAPACITY, MAX_EPOCH_ALLOWED_CREATORS, PROPOSAL_CREATION_FEE_LAMPORTS, SUPPORT_FEE_PERCENTAGE_DENOMINATOR, SUPPORT_FEE_PERCENTAGE_NUMERATOR};
 
 #[account]
 #[derive(InitSpace)]
@@ -8,6 +9,171 @@ pub struct EpochManagement {
     pub end_time: i64,                // Epoch end timestamp
     pub status: EpochStatus,          // Enum indicating the epoch status
     pub processed: bool,              // Indicates if the epoch has been processed
+    pub crank_rewards_paid: u64,      // Crank rewards already paid for maintenance of this epoch
+    pub processed_at: i64,            // Timestamp du passage à processed (début du délai de grâce avant archivage)
+    pub proposals_count: u64,         // Nombre de propositions créées pendant l'époque
+    pub proposals_settled: u64,       // Propositions lancées (validées) ou fermées
+    pub winners_count: u64,           // Nombre de propositions validées
+    pub total_raised: u64,            // SOL levé par les propositions finalisées de l'époque
+    pub version: u8,                  // Version du format du compte (voir migrate_epoch_management)
+    pub allowlist_root: Option<[u8; 32]>, // Racine Merkle des portefeuilles autorisés (None = époque ouverte)
+    pub allowlist_gates_creators: bool,   // La liste restreint aussi la création de propositions
+    pub config: EpochConfig,          // Règles propres à l'époque, fixées par start_epoch
+}
+
+// Règles d'une époque remplaçant celles de ProgramConfig (None = valeur globale).
+// Permet par exemple une mini-époque à 3 gagnants et frais réduits à côté d'une époque classique.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace, Debug)]
+pub struct EpochConfig {
+    pub max_winners: Option<u64>,                         // Nombre maximum de propositions validées (au-delà : rejetées)
+    pub min_sol_raised_for_launch: Option<u64>,
+    pub min_contributors_for_launch: Option<u64>,
+    pub max_support_per_wallet_per_proposal: Option<u64>, // 0 = illimité, comme dans ProgramConfig
+    pub max_support_per_wallet_per_epoch: Option<u64>,
+    pub max_sol_raised_per_proposal: Option<u64>,
+    pub proposal_creation_fee_lamports: Option<u64>,
+    pub support_fee_bps: Option<u16>,                     // Frais de support en points de base
+    #[max_len(MAX_EPOCH_ALLOWED_CREATORS)]
+    pub allowed_creators: Vec<Pubkey>,                    // Créateurs autorisés (vide = tous)
+}
+
+impl EpochConfig {
+    pub fn min_sol_raised_for_launch(&self, program_config: &ProgramConfig) -> u64 {
+        self.min_sol_raised_for_launch.unwrap_or(program_config.min_sol_raised_for_launch)
+    }
+
+    pub fn min_contributors_for_launch(&self, program_config: &ProgramConfig) -> u64 {
+        self.min_contributors_for_launch.unwrap_or(program_config.min_contributors_for_launch)
+    }
+
+    pub fn max_support_per_wallet_per_proposal(&self, program_config: &ProgramConfig) -> u64 {
+        self.max_support_per_wallet_per_proposal.unwrap_or(program_config.max_support_per_wallet_per_proposal)
+    }
+
+    pub fn max_support_per_wallet_per_epoch(&self, program_config: &ProgramConfig) -> u64 {
+        self.max_support_per_wallet_per_epoch.unwrap_or(program_config.max_support_per_wallet_per_epoch)
+    }
+
+    pub fn max_sol_raised_per_proposal(&self, program_config: &ProgramConfig) -> u64 {
+        self.max_sol_raised_per_proposal.unwrap_or(program_config.max_sol_raised_per_proposal)
+    }
+
+    pub fn proposal_creation_fee(&self) -> u64 {
+        self.proposal_creation_fee_lamports.unwrap_or(PROPOSAL_CREATION_FEE_LAMPORTS)
+    }
+
+    /// Frais prélevés sur un support de `amount` lamports (None en cas de dépassement).
+    pub fn support_fee(&self, amount: u64) -> Option<u64> {
+        match self.support_fee_bps {
+            Some(bps) => amount.checked_mul(bps as u64)?.checked_div(BPS_DENOMINATOR),
+            None => amount
+                .checked_mul(SUPPORT_FEE_PERCENTAGE_NUMERATOR)?
+                .checked_div(SUPPORT_FEE_PERCENTAGE_DENOMINATOR),
+        }
+    }
+
+    pub fn is_creator_allowed(&self, creator: &Pubkey) -> bool {
+        self.allowed_creators.is_empty() || self.allowed_creators.contains(creator)
+    }
+}
+
+// Entrée du classement : une proposition et le SOL qu'elle a levé
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct LeaderboardEntry {
+    pub proposal: Pubkey,
+    pub sol_raised: u64,
+}
+
+// Classement zero-copy des propositions d'une époque, trié par sol_raised décroissant.
+// Seules les LEADERBOARD_CAPACITY meilleures propositions sont suivies : le crank et le front
+// lisent le classement en direct depuis ce seul compte au lieu de charger chaque TokenProposal.
+// Le classement est approximatif une fois plein : voir `record`. Les époques antérieures à ce
+// compte reçoivent un classement vide via init_epoch_leaderboard. Après la fermeture de l'époque,
+// update_proposal_status en retire chaque proposition finalisée.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct EpochLeaderboard {
+    pub epoch_id: u64,
+    pub len: u32,                     // Nombre d'entrées utilisées dans `entries`
+    pub version: u8,                  // Version du format (voir EPOCH_LEADERBOARD_VERSION)
+    pub _padding: [u8; 3],
+    pub entries: [LeaderboardEntry; LEADERBOARD_CAPACITY],
+}
+
+impl EpochLeaderboard {
+    /// Entrées utilisées, de la mieux classée à la moins bien classée.
+    pub fn ranking(&self) -> &[LeaderboardEntry] {
+        &self.entries[..self.len as usize]
+    }
+
+    /// Enregistre le nouveau montant levé par une proposition et maintient l'ordre décroissant.
+    /// Une proposition absente d'un classement plein n'y entre que si elle dépasse la dernière.
+    ///
+    /// Approximatif après une baisse : quand le classement est plein, une entrée dont le montant
+    /// diminue (retrait de support) garde sa place même si une proposition non suivie a levé
+    /// davantage ; celle-ci n'y entre qu'à son prochain support. Le classement donne donc un
+    /// aperçu des meilleures propositions, pas un résultat : le traitement de l'époque relit
+    /// chaque TokenProposal.
+    pub fn record(&mut self, proposal: Pubkey, sol_raised: u64) {
+        let len = self.len as usize;
+        let mut index = match self.entries[..len].iter().position(|entry| entry.proposal == proposal) {
+            Some(position) => position,
+            None if len < LEADERBOARD_CAPACITY => {
+                self.len += 1;
+                len
+            }
+            None => {
+                if sol_raised <= self.entries[len - 1].sol_raised {
+                    return;
+                }
+                len - 1
+            }
+        };
+        self.entries[index] = LeaderboardEntry { proposal, sol_raised };
+
+        // Remonter ou descendre l'entrée jusqu'à sa place
+        while index > 0 && self.entries[index - 1].sol_raised < self.entries[index].sol_raised {
+            self.entries.swap(index - 1, index);
+            index -= 1;
+        }
+        let len = self.len as usize;
+        while index + 1 < len && self.entries[index + 1].sol_raised > self.entries[index].sol_raised {
+            self.entries.swap(index, index + 1);
+            index += 1;
+        }
+    }
+
+    /// Retire une proposition du classement (proposition masquée par la modération).
+    pub fn remove(&mut self, proposal: Pubkey) {
+        let len = self.len as usize;
+        if let Some(index) = self.entries[..len].iter().position(|entry| entry.proposal == proposal) {
+            self.entries.copy_within(index + 1..len, index);
+            self.entries[len - 1] = LeaderboardEntry::default();
+            self.len -= 1;
+        }
+    }
+}
+
+// Résumé compact d'une époque archivée
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct EpochSummary {
+    pub epoch_id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub processed_at: i64,
+    pub winners_count: u64,
+    pub total_raised: u64,
+}
+
+// Historique glissant des dernières époques archivées (les plus anciennes sont évincées)
+#[account]
+#[derive(InitSpace)]
+pub struct EpochHistory {
+    pub total_archived: u64,          // Nombre total d'époques archivées depuis le début
+    #[max_len(EPOCH_HISTORY_CAPACITY)]
+    pub summaries: Vec<EpochSummary>, // Du plus ancien au plus récent
+    pub version: u8,                  // Version du format (voir EPOCH_HISTORY_VERSION)
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
@@ -26,10 +192,6 @@ pub struct TokenProposal {
     pub token_name: String,           // Token name
     #[max_len(8)]
     pub token_symbol: String,  
-    #[max_len(512)]
-    pub description : String,       // Token symbol
-    #[max_len(256)]
-    pub image_url: Option<String>,    // URL d'une image illustrative (optionnel)
     pub total_supply: u64,            // Total token supply
     pub creator_allocation: u8,       // % of supply for the creator (max 10%)
     pub supporter_allocation: u8,     // % for supporters = ceil((100 - creator_allocation) / 2)
@@ -38,6 +200,21 @@ pub struct TokenProposal {
     pub lockup_period: i64,           // Lock-up period in seconds during which the creator cannot sell
     pub creation_timestamp: i64,      // Timestamp of the proposal creation
     pub status: ProposalStatus,       // Enum indicating the proposal status
+    pub sol_refunded: u64,            // SOL rendu aux supporters après rejet ou annulation
+    pub version: u8,                  // Version du format du compte (voir migrate_token_proposal)
+}
+
+// Textes descriptifs d'une proposition, rarement lus on-chain : PDA [b"proposal_metadata", proposal].
+// Séparés de TokenProposal pour que chaque support ne (dé)sérialise que l'état économique.
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalMetadata {
+    pub proposal: Pubkey,             // TokenProposal décrite
+    #[max_len(512)]
+    pub description: String,          // Description du token
+    #[max_len(256)]
+    pub image_url: Option<String>,    // URL d'une image illustrative (optionnel)
+    pub version: u8,                  // Version du format (voir PROPOSAL_METADATA_VERSION)
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)] // Added Debug to allow logging the status using msg! with {:?}
@@ -45,6 +222,9 @@ pub enum ProposalStatus {
     Active,
     Validated,
     Rejected,
+    Cancelled, // Retirée par son créateur pendant l'époque
+    Flagged,   // Signalée par un modérateur : masquée, plus de nouveaux supports
+    Removed,   // Retirée par la modération, supporters remboursables immédiatement
 }
 
 #[account]
@@ -54,6 +234,29 @@ pub struct UserProposalSupport {
     pub user: Pubkey,                 // The user who contributed
     pub proposal: Pubkey,             // The targeted TokenProposal
     pub amount: u64,                  // SOL invested
+    pub fees_paid: u64,               // Frais de support versés à la trésorerie pour ce support (remboursables en partie)
+    pub version: u8,                  // Version du format (voir USER_PROPOSAL_SUPPORT_VERSION)
+}
+
+impl UserProposalSupport {
+    /// Part des frais payés correspondant à `amount` lamports nets du support (au prorata).
+    pub fn fees_for_amount(&self, amount: u64) -> u64 {
+        if self.amount == 0 {
+            return 0;
+        }
+        ((self.fees_paid as u128) * (amount.min(self.amount) as u128) / (self.amount as u128)) as u64
+    }
+}
+
+// Support net cumulé d'un wallet sur toutes les propositions d'une époque
+// PDA [b"user_epoch_support", epoch_id, user], utilisé pour le plafond par époque
+#[account]
+#[derive(InitSpace)]
+pub struct UserEpochSupport {
+    pub epoch_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,                  // SOL net engagé dans l'époque
+    pub version: u8,                  // Version du format (voir USER_EPOCH_SUPPORT_VERSION)
 }
 
 // --- Catégories de la Trésorerie ---
@@ -83,6 +286,31 @@ pub struct Treasury {
     pub operations: TreasurySubAccount,
     pub investments: TreasurySubAccount,
     pub crank: TreasurySubAccount,
+    pub version: u8,                // Version du format du compte (voir migrate_treasury)
+}
+
+impl Treasury {
+    /// Returns the sub-account backing the given treasury category.
+    pub fn sub_account(&self, category: &TreasuryCategory) -> &TreasurySubAccount {
+        match category {
+            TreasuryCategory::Marketing => &self.marketing,
+            TreasuryCategory::Team => &self.team,
+            TreasuryCategory::Operations => &self.operations,
+            TreasuryCategory::Investments => &self.investments,
+            TreasuryCategory::Crank => &self.crank,
+        }
+    }
+
+    /// Mutable counterpart of `sub_account`.
+    pub fn sub_account_mut(&mut self, category: &TreasuryCategory) -> &mut TreasurySubAccount {
+        match category {
+            TreasuryCategory::Marketing => &mut self.marketing,
+            TreasuryCategory::Team => &mut self.team,
+            TreasuryCategory::Operations => &mut self.operations,
+            TreasuryCategory::Investments => &mut self.investments,
+            TreasuryCategory::Crank => &mut self.crank,
+        }
+    }
 }
 
 // --- Types de rôles pour la gestion ---
@@ -91,6 +319,7 @@ pub enum RoleType {
     Admin,
     CategoryManager(TreasuryCategory),
     Withdrawer(TreasuryCategory),
+    ProposalModerator, // Peut signaler et retirer des propositions
 }
 
 // --- Structure d'un rôle ---
@@ -111,6 +340,15 @@ pub struct TreasuryRoles {
     pub authorities: Vec<Pubkey>,
     #[max_len(16)]
     pub roles: Vec<TreasuryRole>,   // Liste des rôles attribués
+    pub version: u8,                // Version du format (voir TREASURY_ROLES_VERSION)
+}
+
+impl TreasuryRoles {
+    /// Les admins modèrent aussi, en plus des détenteurs du rôle ProposalModerator.
+    pub fn is_proposal_moderator(&self, key: &Pubkey) -> bool {
+        self.authorities.contains(key)
+            || self.roles.iter().any(|r| r.role_type == RoleType::ProposalModerator && r.pubkey == *key)
+    }
 }
 
 // --- Nouveau compte de configuration globale ---
@@ -119,5 +357,165 @@ pub struct TreasuryRoles {
 pub struct ProgramConfig {
     // Clé publique de l'autorité autorisée à exécuter certaines instructions (ex: update_proposal_status)
     pub admin_authority: Pubkey,
-    // On pourrait ajouter d'autres paramètres globaux ici si nécessaire
+    // Récompense (en lamports) versée au signataire de chaque instruction de maintenance
+    pub crank_reward_lamports: u64,
+    // Plafond des récompenses de crank versées pour une même époque
+    pub crank_reward_epoch_cap: u64,
+    // Plafonds de support contre la capture par les baleines (0 = illimité), en lamports nets
+    pub max_support_per_wallet_per_proposal: u64,
+    pub max_support_per_wallet_per_epoch: u64,
+    pub max_sol_raised_per_proposal: u64,
+    // Seuils d'éligibilité au lancement vérifiés à la finalisation (0 = désactivé)
+    pub min_sol_raised_for_launch: u64,
+    pub min_contributors_for_launch: u64,
+    // Pénalité de sortie (en points de base) prélevée par unsupport_proposal et versée à la trésorerie
+    pub unsupport_penalty_bps: u16,
+    // Part des frais de support remboursée par la trésorerie lors d'un reclaim (points de base, 0 = aucune)
+    pub support_fee_refund_bps: u16,
+    // Délai (secondes) après processed avant qu'une époque puisse être archivée
+    pub epoch_archive_grace_period: i64,
+    // Version du format du compte (voir migrate_program_config)
+    pub version: u8,
+    // Part des frais de support reversée au parrain du supporter (points de base, 0 = aucune)
+    pub referral_fee_bps: u16,
+    // Caution déposée par le créateur à chaque proposition (lamports, 0 = aucune)
+    pub creator_bond_lamports: u64,
+    // SOL levés nets à partir desquels la caution est rendue sans lancement, une fois l'époque fermée (0 = au lancement seulement)
+    pub creator_bond_min_raise: u64,
+}
+
+// --- Propositions de dépenses de la trésorerie, votées par les co-fondateurs ---
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum SpendingProposalStatus {
+    Voting,
+    Executed,
+    Rejected,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SpendingProposal {
+    pub spending_id: u64,             // Identifiant choisi par le proposeur (utilisé dans les seeds)
+    pub proposer: Pubkey,             // Détenteur du rôle ayant soumis la dépense
+    pub category: TreasuryCategory,   // Sous-compte de la trésorerie à débiter
+    pub recipient: Pubkey,            // Bénéficiaire du paiement
+    pub amount: u64,                  // Montant demandé en lamports
+    #[max_len(256)]
+    pub description: String,          // Justification de la dépense
+    pub voting_start: i64,            // Début de la fenêtre de vote
+    pub voting_end: i64,              // Fin de la fenêtre de vote
+    pub votes_for: u64,               // Poids cumulé des votes favorables (lamports soutenus)
+    pub votes_against: u64,           // Poids cumulé des votes défavorables
+    pub voters_count: u64,            // Nombre de votants
+    pub status: SpendingProposalStatus,
+    pub version: u8,                  // Version du format (voir SPENDING_PROPOSAL_VERSION)
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SpendingVoteRecord {
+    pub spending_proposal: Pubkey,    // Proposition de dépense votée
+    pub voter: Pubkey,                // Co-fondateur ayant voté
+    pub weight: u64,                  // Poids du vote (somme des supports historiques)
+    pub approve: bool,                // Sens du vote
+    pub version: u8,                  // Version du format (voir SPENDING_VOTE_RECORD_VERSION)
+}
+
+// --- Flux de paiement récurrents depuis la trésorerie ---
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentStream {
+    pub stream_id: u64,               // Identifiant du flux (utilisé dans les seeds)
+    pub creator: Pubkey,              // Admin ayant créé le flux
+    pub category: TreasuryCategory,   // Sous-compte de la trésorerie à débiter
+    pub recipient: Pubkey,            // Bénéficiaire du flux
+    pub rate_per_second: u64,         // Lamports acquis par seconde
+    pub start_time: i64,              // Début de l'acquisition
+    pub end_time: i64,                // Fin de l'acquisition
+    pub total_claimed: u64,           // Lamports déjà réclamés par le bénéficiaire
+    pub version: u8,                  // Version du format (voir PAYMENT_STREAM_VERSION)
+}
+
+impl PaymentStream {
+    /// Lamports vested at `now` and not yet claimed.
+    pub fn claimable_amount(&self, now: i64) -> Result<u64> {
+        let vesting_end = now.min(self.end_time);
+        if vesting_end <= self.start_time {
+            return Ok(0);
+        }
+        let elapsed = (vesting_end - self.start_time) as u64;
+        let vested = elapsed
+            .checked_mul(self.rate_per_second)
+            .ok_or(crate::error::ErrorCode::CalculationOverflow)?;
+        Ok(vested.saturating_sub(self.total_claimed))
+    }
+}
+
+// --- Registre des symboles de token ---
+// Réserve un symbole normalisé (ASCII en majuscules) pour une seule proposition.
+// Registre par époque : PDA [b"epoch_symbol", epoch_id, symbole], libéré quand la proposition
+// est annulée ou rejetée. Registre global des tokens lancés : PDA [b"launched_symbol", symbole].
+#[account]
+#[derive(InitSpace)]
+pub struct SymbolRegistry {
+    pub epoch_id: u64,                // Époque de la proposition qui a réservé le symbole
+    #[max_len(8)]
+    pub symbol: String,               // Symbole normalisé
+    pub proposal: Pubkey,             // Proposition propriétaire du symbole
+    pub registered_at: i64,           // Date de la réservation
+    pub version: u8,                  // Version du format (voir SYMBOL_REGISTRY_VERSION)
+}
+
+// --- Parrainage ---
+// Compte d'un parrain : PDA [REFERRAL_SEED, referrer], ouvert par le parrain lui-même.
+// Sa part des frais de support y est versée en lamports et reste réclamable à tout moment.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralAccount {
+    pub referrer: Pubkey,             // Portefeuille du parrain, seul à pouvoir réclamer
+    pub pending_rewards: u64,         // Récompenses accumulées non encore réclamées
+    pub total_earned: u64,            // Cumul des récompenses perçues
+    pub referred_supports: u64,       // Nombre de supports passés avec ce parrain
+    pub version: u8,                  // Version du format (voir REFERRAL_ACCOUNT_VERSION)
+}
+
+// --- Caution des créateurs ---
+// Caution déposée par le créateur à la création d'une proposition : PDA [CREATOR_BOND_SEED, proposal],
+// qui détient les lamports de la caution en plus de sa rente. Rendue au créateur au lancement ou
+// dès que la proposition atteint le seuil de levée, confisquée au profit de la trésorerie si la
+// proposition est rejetée ou annulée en dessous de ce seuil.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorBond {
+    pub proposal: Pubkey,             // Proposition couverte par la caution
+    pub creator: Pubkey,              // Créateur, destinataire de la caution et de la rente
+    pub amount: u64,                  // Montant de la caution, figé à la création
+    pub min_raise: u64,               // Levée qui libère la caution, figée à la création (0 : aucune)
+    pub posted_at: i64,               // Date du dépôt
+    pub version: u8,                  // Version du format (voir CREATOR_BOND_VERSION)
+}
+
+// --- Modération des propositions ---
+// Motif d'un signalement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ModerationReason {
+    Impersonation,    // Usurpation d'un projet ou d'une personne
+    OffensiveContent, // Nom, symbole ou image offensants
+    Scam,             // Arnaque manifeste
+    Spam,             // Propositions en série sans contenu
+    Other,
+}
+
+// Signalement en cours d'une proposition : PDA [MODERATION_SEED, proposal]. Créé par flag_proposal,
+// fermé par unflag_proposal ou remove_proposal, sa rente revenant au modérateur qui l'a créé.
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalModeration {
+    pub proposal: Pubkey,             // Proposition signalée
+    pub moderator: Pubkey,            // Modérateur à l'origine du signalement
+    pub reason: ModerationReason,     // Motif du signalement
+    pub flagged_at: i64,              // Date du signalement
+    pub appealed: bool,               // Le créateur a fait appel du signalement
+    pub appealed_at: i64,             // Date de l'appel (0 sans appel)
+    pub version: u8,                  // Version du format (voir PROPOSAL_MODERATION_VERSION)
 }
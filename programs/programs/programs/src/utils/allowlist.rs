@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+use crate::constants::{ALLOWLIST_LEAF_PREFIX, ALLOWLIST_NODE_PREFIX};
+use crate::error::ErrorCode;
+use crate::state::EpochManagement;
+
+// Arbre de Merkle des listes d'autorisation (keccak256, paires triées) :
+// - feuille = keccak(0x00 || portefeuille)
+// - nœud    = keccak(0x01 || min(gauche, droite) || max(gauche, droite))
+// Les préfixes empêchent de présenter un nœud interne comme une feuille. Le tri des paires
+// dispense la preuve d'indiquer le côté de chaque frère. Mêmes fonctions que l'outil
+// tools/allowlist-tool qui construit l'arbre et les preuves depuis un CSV.
+
+pub fn allowlist_leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[ALLOWLIST_LEAF_PREFIX, wallet.as_ref()]).to_bytes()
+}
+
+pub fn allowlist_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[ALLOWLIST_NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify_allowlist_proof(root: &[u8; 32], wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(allowlist_leaf(wallet), |node, sibling| allowlist_node(&node, sibling));
+    computed == *root
+}
+
+// Vérifie l'appartenance du portefeuille à la liste de l'époque, s'il y en a une.
+// Les supports sont toujours filtrés ; la création de propositions seulement si l'époque le demande.
+pub fn require_allowlisted(
+    epoch: &EpochManagement,
+    wallet: &Pubkey,
+    proof: &[[u8; 32]],
+    is_creator: bool,
+) -> Result<()> {
+    let Some(root) = epoch.allowlist_root.as_ref() else {
+        return Ok(());
+    };
+    if is_creator && !epoch.allowlist_gates_creators {
+        return Ok(());
+    }
+    require!(verify_allowlist_proof(root, wallet, proof), ErrorCode::NotAllowlisted);
+    Ok(())
+}
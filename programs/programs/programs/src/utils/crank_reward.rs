@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{EpochManagement, ProgramConfig, Treasury, TreasuryCategory};
+use crate::error::ErrorCode;
+use crate::utils::withdraw_from_treasury;
+
+// Fonction utilitaire pour rémunérer le signataire d'une instruction de maintenance.
+// La récompense est prélevée sur le sous-compte Crank et plafonnée par époque, afin
+// qu'une seule époque ne puisse pas vider le sous-compte. Elle n'échoue jamais faute
+// de fonds : la maintenance reste possible même si aucune récompense n'est versée.
+pub fn pay_crank_reward<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    program_config: &ProgramConfig,
+    epoch: &mut EpochManagement,
+    recipient: &AccountInfo<'info>,
+) -> Result<u64> {
+    let remaining_epoch_budget = program_config.crank_reward_epoch_cap
+        .saturating_sub(epoch.crank_rewards_paid);
+
+    // Ne jamais descendre sous le minimum d'exemption de rente de la trésorerie
+    let treasury_info = treasury.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let spendable_lamports = treasury_info.lamports().saturating_sub(rent_minimum);
+
+    let reward = program_config.crank_reward_lamports
+        .min(remaining_epoch_budget)
+        .min(treasury.crank.sol_balance)
+        .min(spendable_lamports);
+
+    if reward == 0 {
+        msg!("No crank reward paid for epoch {} (budget or balance exhausted).", epoch.epoch_id);
+        return Ok(0);
+    }
+
+    withdraw_from_treasury(treasury, &TreasuryCategory::Crank, recipient, reward)?;
+    epoch.crank_rewards_paid = epoch.crank_rewards_paid
+        .checked_add(reward)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("Crank reward of {} lamports paid to {} for epoch {} (total paid: {})",
+        reward, recipient.key(), epoch.epoch_id, epoch.crank_rewards_paid);
+    Ok(reward)
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::{CreatorBond, EpochManagement, EpochStatus, ProposalStatus, TokenProposal, Treasury};
+use crate::utils::fee_distribution::{distribute_fees_to_treasury, FeeType};
+
+// Issue de la caution d'un créateur au vu de l'état de sa proposition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondOutcome {
+    Released, // Rendue au créateur
+    Slashed,  // Confisquée au profit de la trésorerie
+}
+
+// La caution est rendue au lancement ou si la levée atteint le seuil figé à sa création ; elle
+// est confisquée si la proposition est rejetée ou annulée en dessous, ou retirée par la
+// modération quel que soit son montant levé. Le seuil n'est jugé que sur un montant définitif :
+// statut final, ou époque fermée pour une proposition encore active (plus aucun support ni
+// retrait possible). Une proposition active pendant l'époque, ou signalée en attente de
+// décision, n'a pas d'issue.
+pub fn bond_outcome(proposal: &TokenProposal, bond: &CreatorBond, epoch: &EpochManagement) -> Option<BondOutcome> {
+    let reached_min_raise = bond.min_raise > 0 && proposal.sol_raised >= bond.min_raise;
+    match proposal.status {
+        ProposalStatus::Flagged => None,
+        ProposalStatus::Removed => Some(BondOutcome::Slashed),
+        ProposalStatus::Validated => Some(BondOutcome::Released),
+        ProposalStatus::Active if epoch.status != EpochStatus::Closed => None,
+        _ if reached_min_raise => Some(BondOutcome::Released),
+        ProposalStatus::Rejected | ProposalStatus::Cancelled => Some(BondOutcome::Slashed),
+        ProposalStatus::Active => None,
+    }
+}
+
+// Règle la caution puis ferme son compte : le montant va au créateur ou à la trésorerie,
+// la rente du compte revient toujours au créateur. Prend l'AccountInfo pour servir aussi à
+// close_proposal, où la caution peut déjà avoir été réglée.
+pub fn settle_creator_bond<'info>(
+    bond_info: &AccountInfo<'info>,
+    proposal: &TokenProposal,
+    epoch: &EpochManagement,
+    creator: &AccountInfo<'info>,
+    treasury: &mut Account<'info, Treasury>,
+) -> Result<BondOutcome> {
+    require_keys_eq!(*bond_info.owner, crate::ID, anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+    let bond = CreatorBond::try_deserialize(&mut &bond_info.try_borrow_data()?[..])?;
+    require_keys_eq!(bond.creator, creator.key(), ErrorCode::NotProposalCreator);
+    let outcome = bond_outcome(proposal, &bond, epoch).ok_or(ErrorCode::CreatorBondNotSettleable)?;
+
+    // Le compte appartient au programme : débit direct de ses lamports
+    **bond_info.try_borrow_mut_lamports()? -= bond.amount;
+    match outcome {
+        BondOutcome::Released => {
+            **creator.try_borrow_mut_lamports()? += bond.amount;
+        }
+        BondOutcome::Slashed => {
+            **treasury.to_account_info().try_borrow_mut_lamports()? += bond.amount;
+            distribute_fees_to_treasury(treasury, bond.amount, FeeType::SlashedBond)?;
+        }
+    }
+
+    // Fermeture du compte : la rente revient au créateur
+    let rent = bond_info.lamports();
+    **bond_info.try_borrow_mut_lamports()? = 0;
+    **creator.try_borrow_mut_lamports()? += rent;
+    bond_info.assign(&System::id());
+    bond_info.realloc(0, false)?;
+
+    msg!("Creator bond of {} lamports for proposal {}: {:?}", bond.amount, bond.proposal, outcome);
+    Ok(outcome)
+}
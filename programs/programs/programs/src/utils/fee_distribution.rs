@@ -1,13 +1,17 @@
 use anchor_lang::prelude::*;
-use crate::state::Treasury;
+use crate::state::{Treasury, TreasuryCategory};
 use crate::constants::*;
 use crate::error::ErrorCode;
+use crate::utils::treasury_payout::debit_treasury;
 
 // Énumération pour spécifier le type de frais à distribuer
 pub enum FeeType {
     ProposalCreation, // Frais fixes de création de proposition
     ProposalSupport,  // Frais en pourcentage du support de proposition
     PoolCreation,     // (Futur) Frais de création de pool
+    UnsupportPenalty, // Pénalité de sortie prélevée lors d'un retrait de support
+    ProposalResidual, // Lamports résiduels balayés à la fermeture d'une proposition
+    SlashedBond,      // Caution d'un créateur confisquée
 }
 
 // Fonction utilitaire pour distribuer les frais dans la trésorerie
@@ -17,45 +21,17 @@ pub fn distribute_fees_to_treasury(
     fee_type: FeeType,
 ) -> Result<()> {
     match fee_type {
-        FeeType::ProposalCreation => {
-            // Pour les frais de création de proposition (faibles), 100% vont aux opérations
+        FeeType::ProposalCreation | FeeType::ProposalResidual => {
+            // Pour les frais de création de proposition et les résidus (faibles), 100% vont aux opérations
             treasury.operations.sol_balance = treasury.operations.sol_balance
                 .checked_add(fee_amount)
                 .ok_or(ErrorCode::CalculationOverflow)?;
-            msg!("Proposal creation fee ({} lamports) allocated 100% to Operations treasury.", fee_amount);
+            msg!("Proposal creation fee or residual ({} lamports) allocated 100% to Operations treasury.", fee_amount);
         }
-        FeeType::ProposalSupport | FeeType::PoolCreation => {
+        FeeType::ProposalSupport | FeeType::PoolCreation | FeeType::UnsupportPenalty | FeeType::SlashedBond => {
             // Pour les autres types de frais (potentiellement plus élevés), appliquer la distribution standard
-            let marketing_share = fee_amount
-                .checked_mul(TREASURY_DISTRIBUTION_MARKETING_PERCENT as u64)
-                .ok_or(ErrorCode::CalculationOverflow)?
-                .checked_div(100)
-                .ok_or(ErrorCode::CalculationOverflow)?;
-
-            let team_share = fee_amount
-                .checked_mul(TREASURY_DISTRIBUTION_TEAM_PERCENT as u64)
-                .ok_or(ErrorCode::CalculationOverflow)?
-                .checked_div(100)
-                .ok_or(ErrorCode::CalculationOverflow)?;
-
-            let operations_share = fee_amount
-                .checked_mul(TREASURY_DISTRIBUTION_OPERATIONS_PERCENT as u64)
-                .ok_or(ErrorCode::CalculationOverflow)?
-                .checked_div(100)
-                .ok_or(ErrorCode::CalculationOverflow)?;
-
-            let investments_share = fee_amount
-                .checked_mul(TREASURY_DISTRIBUTION_INVESTMENTS_PERCENT as u64)
-                .ok_or(ErrorCode::CalculationOverflow)?
-                .checked_div(100)
-                .ok_or(ErrorCode::CalculationOverflow)?;
-
-            // Le reste va au crank pour éviter les poussières de lamports
-            let mut crank_share = fee_amount;
-            crank_share = crank_share.saturating_sub(marketing_share);
-            crank_share = crank_share.saturating_sub(team_share);
-            crank_share = crank_share.saturating_sub(operations_share);
-            crank_share = crank_share.saturating_sub(investments_share);
+            let [marketing_share, team_share, operations_share, investments_share, crank_share] =
+                standard_fee_shares(fee_amount)?;
 
             treasury.marketing.sol_balance = treasury.marketing.sol_balance
                 .checked_add(marketing_share)
@@ -82,4 +58,61 @@ pub fn distribute_fees_to_treasury(
         }
     }
     Ok(())
-} 
\ No newline at end of file
+}
+
+// Répartition standard d'un montant de frais entre les sous-comptes, dans l'ordre
+// Marketing, Team, Operations, Investments, Crank. Le reste va au crank pour éviter les poussières.
+pub fn standard_fee_shares(fee_amount: u64) -> Result<[u64; 5]> {
+    let share = |percent: u8| -> Result<u64> {
+        Ok(fee_amount
+            .checked_mul(percent as u64)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(100)
+            .ok_or(ErrorCode::CalculationOverflow)?)
+    };
+
+    let marketing_share = share(TREASURY_DISTRIBUTION_MARKETING_PERCENT)?;
+    let team_share = share(TREASURY_DISTRIBUTION_TEAM_PERCENT)?;
+    let operations_share = share(TREASURY_DISTRIBUTION_OPERATIONS_PERCENT)?;
+    let investments_share = share(TREASURY_DISTRIBUTION_INVESTMENTS_PERCENT)?;
+
+    let crank_share = fee_amount
+        .saturating_sub(marketing_share)
+        .saturating_sub(team_share)
+        .saturating_sub(operations_share)
+        .saturating_sub(investments_share);
+
+    Ok([marketing_share, team_share, operations_share, investments_share, crank_share])
+}
+
+// Rembourse un montant de frais de support en le reprenant dans les sous-comptes selon la
+// répartition standard. Chaque part est plafonnée au solde du sous-compte (les fonds ont pu
+// être dépensés entre-temps) : le montant effectivement remboursé est renvoyé.
+// Un remboursement ne date pas le dernier retrait des sous-comptes.
+pub fn refund_fees_from_treasury<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    recipient: &AccountInfo<'info>,
+    fee_amount: u64,
+) -> Result<u64> {
+    let categories = [
+        TreasuryCategory::Marketing,
+        TreasuryCategory::Team,
+        TreasuryCategory::Operations,
+        TreasuryCategory::Investments,
+        TreasuryCategory::Crank,
+    ];
+    let shares = standard_fee_shares(fee_amount)?;
+
+    let mut refunded: u64 = 0;
+    for (category, share) in categories.iter().zip(shares.iter()) {
+        let payable = (*share).min(treasury.sub_account(category).sol_balance);
+        if payable == 0 {
+            continue;
+        }
+        debit_treasury(treasury, category, recipient, payable)?;
+        refunded = refunded.checked_add(payable).ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    msg!("Support fee refund: {} of {} lamports returned to {}", refunded, fee_amount, recipient.key());
+    Ok(refunded)
+}
@@ -0,0 +1,478 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::{Discriminator, Space};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{
+    EpochManagement, ProgramConfig, ProposalMetadata, ProposalStatus, TokenProposal, Treasury, TreasuryRole,
+    TreasuryRoles, UserProposalSupport,
+};
+use crate::utils::proposal_vault::{check_vault_accounting, outstanding_support, vault_rent_reserve};
+
+// Cadre de migration des comptes versionnés.
+//
+// Les champs sont toujours ajoutés en fin de structure : un compte créé avec un format plus
+// ancien est un préfixe du format actuel. La migration agrandit le compte à la taille actuelle
+// (les octets ajoutés sont à zéro, la rente est complétée par le payeur), le désérialise avec
+// le format actuel puis applique les étapes `upgrade` de sa version jusqu'à la version courante.
+//
+// Exception : TokenProposal v2 a perdu sa description et son image au profit de ProposalMetadata,
+// et ses SOL sont passés dans un vault dédié. L'ancien format n'est plus un préfixe du nouveau,
+// d'où la migration dédiée `upgrade_legacy_token_proposal` plus bas.
+
+pub trait Versioned {
+    const CURRENT_VERSION: u8;
+
+    fn version(&self) -> u8;
+    fn set_version(&mut self, version: u8);
+
+    // Étapes de mise à niveau depuis `from_version`. Les champs absents de l'ancien format
+    // valent zéro : c'est ici qu'on leur donne leur valeur par défaut.
+    fn upgrade(&mut self, _from_version: u8) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Versioned for ProgramConfig {
+    const CURRENT_VERSION: u8 = PROGRAM_CONFIG_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn upgrade(&mut self, from_version: u8) -> Result<()> {
+        // v0 : seule admin_authority existait. Les plafonds, seuils, pénalité et remboursement
+        // valent 0 par défaut (désactivés) ; les récompenses de crank et le délai d'archivage non.
+        if from_version < 1 {
+            self.crank_reward_lamports = DEFAULT_CRANK_REWARD_LAMPORTS;
+            self.crank_reward_epoch_cap = DEFAULT_CRANK_REWARD_EPOCH_CAP;
+            self.epoch_archive_grace_period = DEFAULT_EPOCH_ARCHIVE_GRACE_PERIOD;
+        }
+        if from_version < 2 {
+            self.referral_fee_bps = DEFAULT_REFERRAL_FEE_BPS;
+        }
+        if from_version < 3 {
+            self.creator_bond_lamports = DEFAULT_CREATOR_BOND_LAMPORTS;
+            self.creator_bond_min_raise = DEFAULT_CREATOR_BOND_MIN_RAISE;
+        }
+        Ok(())
+    }
+}
+
+impl Versioned for Treasury {
+    const CURRENT_VERSION: u8 = TREASURY_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl Versioned for EpochManagement {
+    const CURRENT_VERSION: u8 = EPOCH_MANAGEMENT_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn upgrade(&mut self, from_version: u8) -> Result<()> {
+        // v0 : aucun compteur de propositions. À zéro, l'époque semblerait sans proposition
+        // ouverte et pourrait être archivée avant le remboursement de ses supporters.
+        if from_version < 1 {
+            self.proposals_count = LEGACY_EPOCH_UNKNOWN_PROPOSALS;
+        }
+        Ok(())
+    }
+}
+
+// v0 : compte créé avant `fees_paid`. Les frais d'origine sont inconnus, fees_paid reste à 0
+// (aucun remboursement de frais au reclaim).
+impl Versioned for UserProposalSupport {
+    const CURRENT_VERSION: u8 = USER_PROPOSAL_SUPPORT_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl Versioned for TreasuryRoles {
+    const CURRENT_VERSION: u8 = TREASURY_ROLES_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+// TreasuryRoles v0 : créé avec un espace calculé à la main, plus petit que `8 + INIT_SPACE`, et
+// sans version. Ses listes étant de longueur variable, la version se lit juste après les rôles,
+// où un compte v0 peut garder les octets d'un rôle retiré depuis : ils sont effacés avant la
+// migration générique pour que la version lue soit 0. Un compte v0 se reconnaît à sa taille.
+#[derive(AnchorDeserialize)]
+struct LegacyTreasuryRoles {
+    _authorities: Vec<Pubkey>,
+    _roles: Vec<TreasuryRole>,
+}
+
+pub fn clear_legacy_treasury_roles_tail(account: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*account.owner, crate::ID, anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+    if account.data_len() >= 8 + TreasuryRoles::INIT_SPACE {
+        return Ok(());
+    }
+    let mut data = account.try_borrow_mut_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == TreasuryRoles::DISCRIMINATOR[..],
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    clear_after_legacy_roles(&mut data[8..])
+}
+
+// Met à zéro les octets qui suivent les listes d'un TreasuryRoles v0 (données après le discriminateur)
+fn clear_after_legacy_roles(data: &mut [u8]) -> Result<()> {
+    let mut remaining: &[u8] = data;
+    LegacyTreasuryRoles::deserialize(&mut remaining)?;
+    let end = data.len() - remaining.len();
+    data[end..].fill(0);
+    Ok(())
+}
+
+// Migre `account` (déjà vérifié par ses seeds si le type en a de statiques) vers le format courant.
+// Renvoie la version d'origine.
+pub fn migrate_account<'info, T>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u8>
+where
+    T: AccountSerialize + AccountDeserialize + Discriminator + Space + Versioned,
+{
+    // 1. Le compte doit appartenir au programme et porter le discriminateur du type
+    require_keys_eq!(*account.owner, crate::ID, anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+    {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == T::DISCRIMINATOR[..],
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    // 2. Agrandir le compte à la taille du format courant
+    let target_len = 8 + T::INIT_SPACE;
+    if account.data_len() < target_len {
+        let required_lamports = Rent::get()?.minimum_balance(target_len);
+        let top_up = required_lamports.saturating_sub(account.lamports());
+        if top_up > 0 {
+            let cpi_context = CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            );
+            transfer(cpi_context, top_up)?;
+        }
+        account.realloc(target_len, true)?;
+    }
+
+    // 3. Désérialiser avec le format courant et appliquer les étapes de mise à niveau
+    let mut value = T::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    let from_version = value.version();
+    require!(from_version <= T::CURRENT_VERSION, ErrorCode::UnsupportedAccountVersion);
+    require!(from_version < T::CURRENT_VERSION, ErrorCode::AccountAlreadyMigrated);
+
+    value.upgrade(from_version)?;
+    value.set_version(T::CURRENT_VERSION);
+
+    // 4. Réécrire le compte (discriminateur compris)
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    value.try_serialize(&mut writer)?;
+
+    msg!("Account {} migrated from version {} to {}", account.key(), from_version, T::CURRENT_VERSION);
+    Ok(from_version)
+}
+
+// Format de TokenProposal avant la version 2 (versions 0 et 1), description et image incluses.
+// Le format v0 s'arrête à `status` ; sol_refunded et version ont été ajoutés en fin de compte
+// ensuite et sont lus à part (voir `read_legacy_token_proposal`).
+#[derive(AnchorDeserialize)]
+struct LegacyTokenProposal {
+    epoch_id: u64,
+    creator: Pubkey,
+    token_name: String,
+    token_symbol: String,
+    description: String,
+    image_url: Option<String>,
+    total_supply: u64,
+    creator_allocation: u8,
+    supporter_allocation: u8,
+    sol_raised: u64,
+    total_contributions: u64,
+    lockup_period: i64,
+    creation_timestamp: i64,
+    status: ProposalStatus,
+    #[borsh_skip]
+    sol_refunded: u64,
+    #[borsh_skip]
+    version: u8,
+}
+
+// Lit une TokenProposal v0/v1 (données après le discriminateur).
+// Les chaînes étant sérialisées au plus court, les champs de fin d'un compte v0 lisent en
+// général le remplissage à zéro ; un compte v0 dont les chaînes occupent toute l'allocation
+// d'origine s'arrête en revanche à `status`, et les champs absents valent 0.
+fn read_legacy_token_proposal(mut data: &[u8]) -> Result<LegacyTokenProposal> {
+    let mut legacy = LegacyTokenProposal::deserialize(&mut data)?;
+    if data.len() >= 8 {
+        legacy.sol_refunded = u64::deserialize(&mut data)?;
+    }
+    if !data.is_empty() {
+        legacy.version = u8::deserialize(&mut data)?;
+    }
+    Ok(legacy)
+}
+
+// Migre une TokenProposal v0/v1 vers le format v2 :
+// - les textes sont copiés dans `metadata` (déjà initialisé par l'instruction) ;
+// - le payeur ouvre le vault avec sa réserve de rente et le SOL encore dû aux supporters
+//   y est déplacé depuis le compte de données ;
+// - le compte est réécrit au format courant puis réduit. La rente libérée revient au payeur,
+//   qui a financé le compte de métadonnées et la réserve du vault.
+// Renvoie la version d'origine.
+pub fn upgrade_legacy_token_proposal<'info>(
+    account: &AccountInfo<'info>,
+    metadata: &mut ProposalMetadata,
+    vault: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u8> {
+    require_keys_eq!(*account.owner, crate::ID, anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+    {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == TokenProposal::DISCRIMINATOR[..],
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    // Un compte déjà à la taille du format courant a été créé ou migré en v2
+    let target_len = 8 + TokenProposal::INIT_SPACE;
+    if account.data_len() <= target_len {
+        let current = TokenProposal::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(current.version <= TOKEN_PROPOSAL_VERSION, ErrorCode::UnsupportedAccountVersion);
+        return err!(ErrorCode::AccountAlreadyMigrated);
+    }
+
+    let legacy = read_legacy_token_proposal(&account.try_borrow_data()?[8..])?;
+    require!(legacy.version < TOKEN_PROPOSAL_VERSION, ErrorCode::UnsupportedAccountVersion);
+
+    metadata.proposal = account.key();
+    metadata.description = legacy.description;
+    metadata.image_url = legacy.image_url;
+    metadata.version = PROPOSAL_METADATA_VERSION;
+
+    let proposal = TokenProposal {
+        epoch_id: legacy.epoch_id,
+        creator: legacy.creator,
+        token_name: legacy.token_name,
+        token_symbol: legacy.token_symbol,
+        total_supply: legacy.total_supply,
+        creator_allocation: legacy.creator_allocation,
+        supporter_allocation: legacy.supporter_allocation,
+        sol_raised: legacy.sol_raised,
+        total_contributions: legacy.total_contributions,
+        lockup_period: legacy.lockup_period,
+        creation_timestamp: legacy.creation_timestamp,
+        status: legacy.status,
+        sol_refunded: legacy.sol_refunded,
+        version: TOKEN_PROPOSAL_VERSION,
+    };
+
+    // Réserve de rente du vault, payée par le payeur
+    let cpi_context = CpiContext::new(
+        system_program.clone(),
+        Transfer {
+            from: payer.clone(),
+            to: vault.clone(),
+        },
+    );
+    transfer(cpi_context, vault_rent_reserve()?)?;
+
+    // Le SOL des supporters quitte le compte de données pour le vault. Seule la différence de
+    // rente est rendue au payeur ; un éventuel surplus reste sur le compte (rendu au créateur
+    // à la fermeture).
+    let rent = Rent::get()?;
+    let freed_rent = rent.minimum_balance(account.data_len()).saturating_sub(rent.minimum_balance(target_len));
+    let outstanding = outstanding_support(&proposal)?;
+    let available = account.lamports().saturating_sub(rent.minimum_balance(account.data_len()));
+    require!(available >= outstanding, ErrorCode::InsufficientProposalFunds);
+
+    account.realloc(target_len, false)?;
+    {
+        let mut data = account.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        proposal.try_serialize(&mut writer)?;
+    }
+    **account.try_borrow_mut_lamports()? -= outstanding + freed_rent;
+    **vault.try_borrow_mut_lamports()? += outstanding;
+    **payer.try_borrow_mut_lamports()? += freed_rent;
+    check_vault_accounting(vault, &proposal)?;
+
+    msg!("Proposal {} migrated from version {} to {}: metadata split out, {} lamports moved to its vault",
+        account.key(), legacy.version, TOKEN_PROPOSAL_VERSION, outstanding);
+    Ok(legacy.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Compte v0 tel que le voit `upgrade` : données d'origine complétées par des zéros
+    fn grown_to_current<T: AccountDeserialize + Discriminator + Space>(legacy_fields: &[u8]) -> T {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(legacy_fields);
+        data.resize(8 + T::INIT_SPACE, 0);
+        T::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn program_config_v0_gets_current_defaults() {
+        let admin = Pubkey::new_unique();
+        let mut config: ProgramConfig = grown_to_current(admin.as_ref());
+        assert_eq!(config.version(), 0);
+
+        config.upgrade(0).unwrap();
+
+        assert_eq!(config.admin_authority, admin);
+        assert_eq!(config.crank_reward_lamports, DEFAULT_CRANK_REWARD_LAMPORTS);
+        assert_eq!(config.crank_reward_epoch_cap, DEFAULT_CRANK_REWARD_EPOCH_CAP);
+        assert_eq!(config.epoch_archive_grace_period, DEFAULT_EPOCH_ARCHIVE_GRACE_PERIOD);
+        assert_eq!(config.max_support_per_wallet_per_proposal, DEFAULT_MAX_SUPPORT_PER_WALLET_PER_PROPOSAL);
+        assert_eq!(config.min_sol_raised_for_launch, DEFAULT_MIN_SOL_RAISED_FOR_LAUNCH);
+        assert_eq!(config.unsupport_penalty_bps, DEFAULT_UNSUPPORT_PENALTY_BPS);
+        assert_eq!(config.support_fee_refund_bps, DEFAULT_SUPPORT_FEE_REFUND_BPS);
+        assert_eq!(config.referral_fee_bps, DEFAULT_REFERRAL_FEE_BPS);
+        assert_eq!(config.creator_bond_lamports, DEFAULT_CREATOR_BOND_LAMPORTS);
+        assert_eq!(config.creator_bond_min_raise, DEFAULT_CREATOR_BOND_MIN_RAISE);
+    }
+
+    #[test]
+    fn program_config_v1_keeps_its_crank_settings() {
+        let mut config: ProgramConfig = grown_to_current(Pubkey::new_unique().as_ref());
+        config.crank_reward_lamports = 42;
+        config.epoch_archive_grace_period = 7;
+
+        config.upgrade(1).unwrap();
+
+        assert_eq!(config.crank_reward_lamports, 42);
+        assert_eq!(config.epoch_archive_grace_period, 7);
+    }
+
+    #[test]
+    fn epoch_management_v0_has_unknown_proposal_counters() {
+        let mut legacy = Vec::new();
+        legacy.extend_from_slice(&41u64.to_le_bytes()); // epoch_id
+        legacy.extend_from_slice(&1_700_000_000i64.to_le_bytes()); // start_time
+        legacy.extend_from_slice(&1_700_086_400i64.to_le_bytes()); // end_time
+        legacy.push(2); // status = Closed
+        legacy.push(1); // processed
+        let mut epoch: EpochManagement = grown_to_current(&legacy);
+
+        epoch.upgrade(0).unwrap();
+
+        assert_eq!(epoch.epoch_id, 41);
+        assert!(epoch.processed);
+        assert_eq!(epoch.proposals_count, LEGACY_EPOCH_UNKNOWN_PROPOSALS);
+        assert_eq!(epoch.proposals_settled, 0);
+    }
+
+    // Champs v0 d'une TokenProposal, de epoch_id à status
+    fn legacy_proposal_fields(name: &str, symbol: &str, description: &str, image_url: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&41u64.to_le_bytes()); // epoch_id
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // creator
+        for text in [name, symbol, description] {
+            data.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            data.extend_from_slice(text.as_bytes());
+        }
+        data.push(1); // image_url = Some
+        data.extend_from_slice(&(image_url.len() as u32).to_le_bytes());
+        data.extend_from_slice(image_url.as_bytes());
+        data.extend_from_slice(&1_000_000u64.to_le_bytes()); // total_supply
+        data.push(5); // creator_allocation
+        data.push(48); // supporter_allocation
+        data.extend_from_slice(&0u64.to_le_bytes()); // sol_raised
+        data.extend_from_slice(&0u64.to_le_bytes()); // total_contributions
+        data.extend_from_slice(&0i64.to_le_bytes()); // lockup_period
+        data.extend_from_slice(&1_700_000_100i64.to_le_bytes()); // creation_timestamp
+        data.push(2); // status = Rejected
+        data
+    }
+
+    #[test]
+    fn token_proposal_v0_at_max_length_has_no_trailing_fields() {
+        let data = legacy_proposal_fields(&"N".repeat(32), &"S".repeat(8), &"D".repeat(512), &"I".repeat(256));
+        // Allocation d'origine : `8 + TokenProposal::INIT_SPACE` avant sol_refunded et version
+        assert_eq!(8 + data.len(), 916);
+
+        let legacy = read_legacy_token_proposal(&data).unwrap();
+
+        assert_eq!(legacy.description.len(), 512);
+        assert_eq!(legacy.image_url.map(|url| url.len()), Some(256));
+        assert_eq!(legacy.status, ProposalStatus::Rejected);
+        assert_eq!(legacy.sol_refunded, 0);
+        assert_eq!(legacy.version, 0);
+    }
+
+    #[test]
+    fn token_proposal_v1_reads_its_trailing_fields() {
+        let mut data = legacy_proposal_fields("Token", "TOK", "Description", "https://example.com");
+        data.extend_from_slice(&7u64.to_le_bytes()); // sol_refunded
+        data.push(1); // version
+        data.resize(925 - 8, 0); // allocation v1, discriminateur exclu
+
+        let legacy = read_legacy_token_proposal(&data).unwrap();
+
+        assert_eq!(legacy.sol_refunded, 7);
+        assert_eq!(legacy.version, 1);
+    }
+
+    #[test]
+    fn treasury_roles_v0_ignores_the_bytes_of_a_removed_role() {
+        // Compte v0 à sa taille d'origine (8 + 32 * 3 + 4 + 50 * 16), un admin et un rôle retiré :
+        // les octets du rôle retiré restent après la liste vide
+        let admin = Pubkey::new_unique();
+        let mut legacy = Vec::new();
+        legacy.extend_from_slice(&1u32.to_le_bytes());
+        legacy.extend_from_slice(admin.as_ref());
+        legacy.extend_from_slice(&0u32.to_le_bytes());
+        legacy.push(3); // role_type d'un rôle retiré
+        legacy.extend_from_slice(Pubkey::new_unique().as_ref());
+        legacy.resize(908 - 8, 0);
+
+        clear_after_legacy_roles(&mut legacy).unwrap();
+        let roles: TreasuryRoles = grown_to_current(&legacy);
+
+        assert_eq!(roles.version(), 0);
+        assert_eq!(roles.authorities, vec![admin]);
+        assert!(roles.roles.is_empty());
+    }
+}
@@ -1,3 +1,16 @@
+pub mod allowlist;
+pub mod crank_reward;
+pub mod creator_bond;
 pub mod fee_distribution;
+pub mod migration;
+pub mod proposal_validation;
+pub mod proposal_vault;
+pub mod treasury_payout;
 
-pub use fee_distribution::*; 
\ No newline at end of file
+pub use allowlist::*;
+pub use crank_reward::*;
+pub use creator_bond::*;
+pub use fee_distribution::*;
+pub use proposal_validation::*;
+pub use proposal_vault::*;
+pub use treasury_payout::*;
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+// Fonctions utilitaires de validation des champs d'une proposition.
+// Les longueurs correspondent aux #[max_len] de TokenProposal : sans ces vérifications,
+// une chaîne trop longue échoue plus tard avec une erreur de sérialisation peu explicite.
+
+// Seed du PDA de la proposition. Vérifie la longueur du nom dès la dérivation des comptes :
+// au-delà de 32 octets, la dérivation échouerait avant le handler sans erreur explicite.
+pub fn token_name_seed(token_name: &str) -> Result<&[u8]> {
+    require!(token_name.len() <= MAX_TOKEN_NAME_LENGTH, ErrorCode::TokenNameTooLong);
+    Ok(token_name.as_bytes())
+}
+
+pub fn validate_token_name(token_name: &str) -> Result<()> {
+    require!(!token_name.is_empty(), ErrorCode::TokenNameEmpty);
+    token_name_seed(token_name)?;
+    Ok(())
+}
+
+pub fn validate_token_symbol(token_symbol: &str) -> Result<()> {
+    require!(!token_symbol.is_empty(), ErrorCode::TokenSymbolEmpty);
+    require!(token_symbol.len() <= MAX_TOKEN_SYMBOL_LENGTH, ErrorCode::TokenSymbolTooLong);
+    // Seuls les caractères ASCII alphanumériques sont autorisés (pas d'espaces ni d'emojis)
+    require!(
+        token_symbol.bytes().all(|c| c.is_ascii_alphanumeric()),
+        ErrorCode::InvalidTokenSymbol
+    );
+    Ok(())
+}
+
+// Forme normalisée d'un symbole utilisée comme seed des registres de symboles :
+// "doge2", "Doge2" et "DOGE2" désignent le même token.
+pub fn normalize_symbol(token_symbol: &str) -> String {
+    token_symbol.to_ascii_uppercase()
+}
+
+pub fn validate_description(description: &str) -> Result<()> {
+    require!(description.len() <= MAX_DESCRIPTION_LENGTH, ErrorCode::DescriptionTooLong);
+    Ok(())
+}
+
+pub fn validate_image_url(image_url: &str) -> Result<()> {
+    require!(image_url.len() <= MAX_IMAGE_URL_LENGTH, ErrorCode::ImageUrlTooLong);
+    // Le front stocke les images sur IPFS ; les liens https sont aussi acceptés
+    let has_allowed_scheme = ALLOWED_IMAGE_URL_SCHEMES
+        .iter()
+        .any(|scheme| image_url.len() > scheme.len() && image_url.starts_with(scheme));
+    require!(has_allowed_scheme, ErrorCode::InvalidImageUrl);
+    Ok(())
+}
+
+pub fn validate_total_supply(total_supply: u64) -> Result<()> {
+    require!(total_supply > 0, ErrorCode::InvalidTotalSupply);
+    require!(total_supply <= MAX_TOKEN_TOTAL_SUPPLY, ErrorCode::TotalSupplyTooHigh);
+    Ok(())
+}
+
+pub fn validate_creator_allocation(creator_allocation: u8) -> Result<()> {
+    require!(creator_allocation <= 10, ErrorCode::CreatorAllocationTooHigh);
+    Ok(())
+}
+
+pub fn validate_lockup_period(lockup_period: i64) -> Result<()> {
+    require!(lockup_period >= 0, ErrorCode::NegativeLockupPeriod);
+    // 0 = pas de lockup ; toute autre valeur doit atteindre le minimum
+    require!(
+        lockup_period == 0 || lockup_period >= MIN_LOCKUP_PERIOD_SECONDS,
+        ErrorCode::LockupPeriodTooShort
+    );
+    require!(lockup_period <= MAX_LOCKUP_PERIOD_SECONDS, ErrorCode::LockupPeriodTooLong);
+    Ok(())
+}
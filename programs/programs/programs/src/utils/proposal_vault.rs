@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::constants::PROPOSAL_VAULT_SEED;
+use crate::error::ErrorCode;
+use crate::state::TokenProposal;
+
+// Vault SOL d'une proposition : PDA [PROPOSAL_VAULT_SEED, proposal] appartenant au System Program,
+// sans données. Il reçoit les supports nets et ne sort des fonds que par des transferts signés
+// par le programme. Le créateur y dépose à la création la réserve d'exemption de rente d'un
+// compte vide, pour que le vault existe quel que soit le montant des supports ; cette réserve
+// lui est rendue à la fermeture de la proposition.
+
+// Réserve de rente d'un compte système sans données
+pub fn vault_rent_reserve() -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(0))
+}
+
+// Transfert sortant du vault, signé avec les seeds du PDA.
+pub fn transfer_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    vault_bump: u8,
+    proposal: &Pubkey,
+    recipient: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let signer_seeds: &[&[&[u8]]] = &[&[PROPOSAL_VAULT_SEED, proposal.as_ref(), &[vault_bump]]];
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.clone(),
+        Transfer {
+            from: vault.clone(),
+            to: recipient.clone(),
+        },
+        signer_seeds,
+    );
+    transfer(cpi_context, amount)
+}
+
+// SOL des supporters encore dus par la proposition
+pub fn outstanding_support(proposal: &TokenProposal) -> Result<u64> {
+    proposal.sol_raised.checked_sub(proposal.sol_refunded)
+        .ok_or_else(|| error!(ErrorCode::CalculationOverflow))
+}
+
+// Contrôle de comptabilité, appelé après chaque mouvement du vault :
+// lamports du vault = réserve de rente + sol_raised - sol_refunded.
+// Un dépôt direct sur le vault ne peut qu'ajouter un surplus, balayé vers la trésorerie à la
+// fermeture ; un déficit trahit une erreur de comptabilité et fait échouer l'instruction.
+// Renvoie le surplus.
+pub fn check_vault_accounting(vault: &AccountInfo, proposal: &TokenProposal) -> Result<u64> {
+    let expected = vault_rent_reserve()?
+        .checked_add(outstanding_support(proposal)?)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let balance = vault.lamports();
+    require!(balance >= expected, ErrorCode::VaultAccountingMismatch);
+    Ok(balance - expected)
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::{Treasury, TreasuryCategory};
+use crate::error::ErrorCode;
+
+// Montant qu'un sous-compte peut payer : son sol_balance, dans la limite des lamports
+// du compte Treasury au-dessus de son minimum d'exemption de rente.
+pub fn available_in_treasury(treasury: &Account<Treasury>, category: &TreasuryCategory) -> Result<u64> {
+    let treasury_info = treasury.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let spendable = treasury_info.lamports().saturating_sub(rent_minimum);
+    Ok(treasury.sub_account(category).sol_balance.min(spendable))
+}
+
+// Fonction utilitaire pour payer un bénéficiaire depuis un sous-compte de la trésorerie.
+// Le compte Treasury appartient au programme : les lamports sont débités directement,
+// sans jamais descendre sous le minimum d'exemption de rente du compte.
+pub fn withdraw_from_treasury<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    category: &TreasuryCategory,
+    recipient: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    debit_treasury(treasury, category, recipient, amount)?;
+    treasury.sub_account_mut(category).last_withdrawal = Clock::get()?.unix_timestamp;
+
+    msg!("Treasury paid {} lamports from {:?} to {}", amount, category, recipient.key());
+    Ok(())
+}
+
+// Débite un sous-compte et verse le montant au bénéficiaire, sans dater de retrait :
+// sert aussi aux remboursements de frais, qui ne sont pas des dépenses de la trésorerie.
+pub fn debit_treasury<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    category: &TreasuryCategory,
+    recipient: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    // 1. Débiter la comptabilité du sous-compte
+    let sub_account = treasury.sub_account_mut(category);
+    sub_account.sol_balance = sub_account.sol_balance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientTreasuryBalance)?;
+
+    // 2. Vérifier que le compte reste exempté de rente après le paiement
+    let treasury_info = treasury.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let remaining_lamports = treasury_info.lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientTreasuryBalance)?;
+    require!(remaining_lamports >= rent_minimum, ErrorCode::InsufficientTreasuryBalance);
+
+    // 3. Transfert manuel des lamports
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
@@ -0,0 +1,195 @@
+// generate-legacy-fixtures.js
+//
+// Génère les comptes de test au format antérieur au champ `version` (version 0),
+// chargés par le validateur de test (voir [[test.validator.account]] dans Anchor.toml)
+// et migrés par tests/integration/06_account_migrations.
+// Usage : node scripts/generate-legacy-fixtures.js
+
+import fs from "fs";
+import path from "path";
+import crypto from "crypto";
+
+const PROGRAM_ID = "3HBzNutk8DrRfffCS74S55adJAjgY8NHrWXgRtABaSbF";
+const OUTPUT_DIR = path.resolve("tests/fixtures");
+
+export const LEGACY_EPOCH_ID = 41_000_000_001n;
+// Créateur fictif de la proposition (clé publique arbitraire)
+const LEGACY_CREATOR = Buffer.alloc(32, 7);
+const LEGACY_TOKEN_NAME = "Legacy Token";
+
+// --- Base58 ---
+const ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+function base58Encode(buffer) {
+  let value = BigInt("0x" + (buffer.toString("hex") || "0"));
+  let out = "";
+  while (value > 0n) {
+    out = ALPHABET[Number(value % 58n)] + out;
+    value /= 58n;
+  }
+  for (const byte of buffer) {
+    if (byte !== 0) break;
+    out = "1" + out;
+  }
+  return out;
+}
+function base58Decode(text) {
+  let value = 0n;
+  for (const char of text) value = value * 58n + BigInt(ALPHABET.indexOf(char));
+  let hex = value.toString(16);
+  if (hex.length % 2) hex = "0" + hex;
+  const leading = text.match(/^1*/)[0].length;
+  return Buffer.concat([Buffer.alloc(leading), Buffer.from(hex, "hex")]);
+}
+
+// --- PDA (findProgramAddress) ---
+const P = 2n ** 255n - 19n;
+const D = (-121665n * modInverse(121666n)) % P;
+function mod(a) {
+  return ((a % P) + P) % P;
+}
+function modPow(base, exp) {
+  let result = 1n;
+  base = mod(base);
+  while (exp > 0n) {
+    if (exp & 1n) result = (result * base) % P;
+    base = (base * base) % P;
+    exp >>= 1n;
+  }
+  return result;
+}
+function modInverse(a) {
+  return modPow(a, P - 2n);
+}
+function isOnCurve(bytes) {
+  const copy = Buffer.from(bytes);
+  copy[31] &= 0x7f;
+  const y = mod(BigInt("0x" + Buffer.from(copy).reverse().toString("hex")));
+  const y2 = (y * y) % P;
+  const x2 = mod((y2 - 1n) * modInverse(mod(D * y2 + 1n)));
+  if (x2 === 0n) return true;
+  let x = modPow(x2, (P + 3n) / 8n);
+  if ((x * x) % P === x2) return true;
+  x = (x * modPow(2n, (P - 1n) / 4n)) % P;
+  return (x * x) % P === x2;
+}
+function findProgramAddress(seeds, programId) {
+  for (let bump = 255; bump >= 0; bump--) {
+    const hash = crypto
+      .createHash("sha256")
+      .update(Buffer.concat([...seeds, Buffer.from([bump]), programId, Buffer.from("ProgramDerivedAddress")]))
+      .digest();
+    if (!isOnCurve(hash)) return hash;
+  }
+  throw new Error("No valid bump found");
+}
+
+// --- Borsh ---
+const u8 = (v) => Buffer.from([v]);
+const u64 = (v) => {
+  const b = Buffer.alloc(8);
+  b.writeBigUInt64LE(BigInt(v));
+  return b;
+};
+const i64 = (v) => {
+  const b = Buffer.alloc(8);
+  b.writeBigInt64LE(BigInt(v));
+  return b;
+};
+const str = (s) => {
+  const bytes = Buffer.from(s, "utf8");
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length);
+  return Buffer.concat([len, bytes]);
+};
+const discriminator = (name) => crypto.createHash("sha256").update(`account:${name}`).digest().subarray(0, 8);
+
+// Rente exemptée : (128 octets d'en-tête + données) * 3480 lamports/octet/an * 2 ans
+const rentExempt = (len) => (128 + len) * 3480 * 2;
+
+function writeFixture(fileName, address, data, space) {
+  const padded = Buffer.concat([data, Buffer.alloc(space - data.length)]);
+  const fixture = {
+    pubkey: base58Encode(address),
+    account: {
+      lamports: rentExempt(space),
+      data: [padded.toString("base64"), "base64"],
+      owner: PROGRAM_ID,
+      executable: false,
+      rentEpoch: 0,
+      space,
+    },
+  };
+  fs.writeFileSync(path.join(OUTPUT_DIR, fileName), JSON.stringify(fixture, null, 2) + "\n");
+  console.log(`✅ ${fileName} -> ${fixture.pubkey}`);
+}
+
+const programId = base58Decode(PROGRAM_ID);
+fs.mkdirSync(OUTPUT_DIR, { recursive: true });
+
+// EpochManagement version 0 : époque fermée et traitée, sans compteurs de propositions
+const epochAddress = findProgramAddress([Buffer.from("epoch"), u64(LEGACY_EPOCH_ID)], programId);
+const epochData = Buffer.concat([
+  discriminator("EpochManagement"),
+  u64(LEGACY_EPOCH_ID), // epoch_id
+  i64(1_700_000_000), // start_time
+  i64(1_700_086_400), // end_time
+  u8(2), // status = Closed
+  u8(1), // processed
+]);
+writeFixture("legacy_epoch_management.json", epochAddress, epochData, epochData.length);
+
+// TokenProposal version 0 : format d'origine, qui s'arrête à `status` (ni sol_refunded ni version)
+function legacyProposal(tokenName, tokenSymbol, description, imageUrl) {
+  const address = findProgramAddress(
+    [Buffer.from("proposal"), LEGACY_CREATOR, u64(LEGACY_EPOCH_ID), Buffer.from(tokenName)],
+    programId
+  );
+  const data = Buffer.concat([
+    discriminator("TokenProposal"),
+    u64(LEGACY_EPOCH_ID), // epoch_id
+    LEGACY_CREATOR, // creator
+    str(tokenName), // token_name
+    str(tokenSymbol), // token_symbol
+    str(description), // description
+    imageUrl === null ? u8(0) : Buffer.concat([u8(1), str(imageUrl)]), // image_url
+    u64(1_000_000), // total_supply
+    u8(5), // creator_allocation
+    u8(48), // supporter_allocation
+    u64(0), // sol_raised
+    u64(0), // total_contributions
+    i64(0), // lockup_period
+    i64(1_700_000_100), // creation_timestamp
+    u8(2), // status = Rejected
+  ]);
+  return { address, data };
+}
+// Même allocation que `8 + TokenProposal::INIT_SPACE` dans le format d'origine
+const LEGACY_TOKEN_PROPOSAL_SPACE = 8 + 8 + 32 + (4 + 32) + (4 + 8) + (4 + 512) + (1 + 4 + 256) + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 1;
+
+// Proposition rejetée de l'époque ci-dessus
+const proposal = legacyProposal(LEGACY_TOKEN_NAME, "LEGACY", "Proposal serialized before account versioning.", null);
+const proposalAddress = proposal.address;
+writeFixture("legacy_token_proposal.json", proposalAddress, proposal.data, LEGACY_TOKEN_PROPOSAL_SPACE);
+
+// Proposition dont les chaînes atteignent leur longueur maximale : les données remplissent
+// toute l'allocation, sans remplissage à zéro après `status`
+const maxLengthProposal = legacyProposal("M".repeat(32), "MAXSYMBL", "D".repeat(512), "https://example.com/" + "i".repeat(236));
+if (maxLengthProposal.data.length !== LEGACY_TOKEN_PROPOSAL_SPACE) {
+  throw new Error(`Max-length proposal is ${maxLengthProposal.data.length} bytes, expected ${LEGACY_TOKEN_PROPOSAL_SPACE}`);
+}
+writeFixture("legacy_token_proposal_max_length.json", maxLengthProposal.address, maxLengthProposal.data, LEGACY_TOKEN_PROPOSAL_SPACE);
+
+// UserProposalSupport version 0 : support créé avant le suivi des frais (`fees_paid`) et `version`
+const LEGACY_SUPPORTER = Buffer.alloc(32, 9);
+const supportAddress = findProgramAddress(
+  [Buffer.from("support"), u64(LEGACY_EPOCH_ID), LEGACY_SUPPORTER, proposalAddress],
+  programId
+);
+const supportData = Buffer.concat([
+  discriminator("UserProposalSupport"),
+  u64(LEGACY_EPOCH_ID), // epoch_id
+  LEGACY_SUPPORTER, // user
+  proposalAddress, // proposal
+  u64(250_000_000), // amount
+]);
+writeFixture("legacy_user_proposal_support.json", supportAddress, supportData, supportData.length);
@@ -51,7 +51,7 @@ export function runProgramConfigAuthorizationTests(getTestContext: () => TestCon
             it("should allow admin_authority to call start_epoch", async () => {
                 const [epochManagementAddress] = getEpochManagementPda(epochId);
                 await ctx.program.methods
-                    .startEpoch(epochId, startTime, endTime) 
+                    .startEpoch(epochId, startTime, endTime, null) 
                     .accounts({
                         authority: ctx.adminKeypair.publicKey,
                         programConfig: ctx.programConfigAddress!,
@@ -63,7 +63,7 @@ export function runProgramConfigAuthorizationTests(getTestContext: () => TestCon
                 
                 const epochAccount = await ctx.program.account.epochManagement.fetch(epochManagementAddress);
                 expect(epochAccount.epochId.eq(epochId)).to.be.true;
-                expect(epochAccount.status.active).to.exist; 
+                expect(epochAccount.status.pending).to.exist; 
             });
     
             it("should prevent non_admin_authority from calling start_epoch and return Unauthorized error", async () => {
@@ -71,7 +71,7 @@ export function runProgramConfigAuthorizationTests(getTestContext: () => TestCon
                 const [epochManagementAddress] = getEpochManagementPda(currentEpochId);
                 try {
                     await ctx.program.methods
-                        .startEpoch(currentEpochId, startTime, endTime) 
+                        .startEpoch(currentEpochId, startTime, endTime, null) 
                         .accounts({
                             authority: nonAdminKeypair.publicKey,
                             programConfig: ctx.programConfigAddress!,
@@ -103,7 +103,7 @@ export function runProgramConfigAuthorizationTests(getTestContext: () => TestCon
                 );
     
                 await ctx.program.methods
-                    .startEpoch(testEpochId, startTime, endTime)
+                    .startEpoch(testEpochId, startTime, endTime, null)
                     .accounts({
                         authority: ctx.adminKeypair.publicKey,
                         programConfig: ctx.programConfigAddress!,
@@ -173,7 +173,7 @@ export function runProgramConfigAuthorizationTests(getTestContext: () => TestCon
     
                 // 1. Start Epoch
                 await ctx.program.methods
-                    .startEpoch(testEpochIdPS, startTime, endTime)
+                    .startEpoch(testEpochIdPS, startTime, endTime, null)
                     .accounts({ 
                         authority: ctx.adminKeypair.publicKey, 
                         programConfig: ctx.programConfigAddress!,
@@ -185,7 +185,7 @@ export function runProgramConfigAuthorizationTests(getTestContext: () => TestCon
     
                 // 2. Create Proposal
                 await ctx.program.methods
-                    .createProposal(tokenNamePS, "TPA", "Test Auth", null, new anchor.BN(1000), 10, new anchor.BN(0))
+                    .createProposal(tokenNamePS, "TPA", "Test Auth", null, new anchor.BN(1000), 10, new anchor.BN(0), [])
                     .accounts({
                         creator: ctx.adminKeypair.publicKey,
                         tokenProposal: proposalAddressPS,
@@ -262,7 +262,7 @@ export function runProgramConfigAuthorizationTests(getTestContext: () => TestCon
     
                 // 1. Start Epoch
                 await ctx.program.methods
-                    .startEpoch(testEpochIdMEP, startTime, endTime)
+                    .startEpoch(testEpochIdMEP, startTime, endTime, null)
                     .accounts({ 
                         authority: ctx.adminKeypair.publicKey, 
                         programConfig: ctx.programConfigAddress!,
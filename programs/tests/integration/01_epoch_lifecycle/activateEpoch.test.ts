@@ -0,0 +1,94 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { TestContext, getInitializedContext } from '../../setup';
+import { ensureEpochExists, activateEpochOnChain, closeEpochOnChain } from '../../setup/epochSetup';
+import { generateRandomId } from '../../utils_for_tests/helpers';
+
+/**
+ * Exécute les tests pour l'instruction `activate_epoch`.
+ */
+export function runActivateEpochTests() {
+    describe('Instruction: activate_epoch', () => {
+        let ctx: TestContext;
+        let crankKeypair: Keypair;
+
+        before(async () => {
+            ctx = getInitializedContext();
+            crankKeypair = Keypair.generate();
+            const sig = await ctx.provider.connection.requestAirdrop(crankKeypair.publicKey, 0.1 * LAMPORTS_PER_SOL);
+            await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+        });
+
+        async function scheduleEpoch(startOffset: number, endOffset: number): Promise<[anchor.BN, PublicKey]> {
+            const now = Math.floor(Date.now() / 1000);
+            const epochId = new anchor.BN(generateRandomId());
+            const epochPda = await ensureEpochExists(ctx, epochId, new anchor.BN(now + startOffset), new anchor.BN(now + endOffset));
+            return [epochId, epochPda];
+        }
+
+        it('should let any crank activate a pending epoch once started', async () => {
+            const [epochId, epochPda] = await scheduleEpoch(-60, 3600);
+
+            await activateEpochOnChain(ctx, epochId, crankKeypair);
+
+            const epochAccount = await ctx.program.account.epochManagement.fetch(epochPda);
+            expect(JSON.stringify(epochAccount.status)).to.equal(JSON.stringify({ active: {} }));
+        });
+
+        it('should keep several scheduled epochs pending before their start time', async () => {
+            const [firstId, firstPda] = await scheduleEpoch(3600, 7200);
+            const [, secondPda] = await scheduleEpoch(7200, 10800);
+
+            for (const epochPda of [firstPda, secondPda]) {
+                const epochAccount = await ctx.program.account.epochManagement.fetch(epochPda);
+                expect(JSON.stringify(epochAccount.status)).to.equal(JSON.stringify({ pending: {} }));
+            }
+
+            try {
+                await activateEpochOnChain(ctx, firstId, crankKeypair);
+                expect.fail("  [ActivateEpochTests] Transaction should have failed because the epoch has not started.");
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal("EpochNotStarted");
+            }
+        });
+
+        it('should fail to activate an epoch that is not pending', async () => {
+            const [epochId] = await scheduleEpoch(-60, 3600);
+            await activateEpochOnChain(ctx, epochId, crankKeypair);
+
+            try {
+                await activateEpochOnChain(ctx, epochId, crankKeypair);
+                expect.fail("  [ActivateEpochTests] Transaction should have failed because the epoch is already active.");
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal("EpochNotPending");
+            }
+        });
+
+        it('should refuse to activate an epoch whose window has elapsed and let a crank end it', async () => {
+            const [epochId, epochPda] = await scheduleEpoch(-7200, -3600);
+
+            try {
+                await activateEpochOnChain(ctx, epochId, crankKeypair);
+                expect.fail("  [ActivateEpochTests] Transaction should have failed because the epoch end time has passed.");
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal("EpochWindowElapsed");
+            }
+
+            // Expirée : n'importe quel crank peut la fermer sans l'avoir activée
+            await closeEpochOnChain(ctx, epochId, crankKeypair);
+
+            const epochAccount = await ctx.program.account.epochManagement.fetch(epochPda);
+            expect(JSON.stringify(epochAccount.status)).to.equal(JSON.stringify({ closed: {} }));
+        });
+
+        it('should let the admin cancel a scheduled epoch', async () => {
+            const [epochId, epochPda] = await scheduleEpoch(3600, 7200);
+
+            await closeEpochOnChain(ctx, epochId);
+
+            const epochAccount = await ctx.program.account.epochManagement.fetch(epochPda);
+            expect(JSON.stringify(epochAccount.status)).to.equal(JSON.stringify({ closed: {} }));
+        });
+    });
+}
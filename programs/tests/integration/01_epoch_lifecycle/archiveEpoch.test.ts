@@ -0,0 +1,210 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, markEpochAsProcessedOnChain, getLeaderboardPda } from '../../setup/epochSetup';
+import { ensureTreasuryRolesInitialized, getSpendingProposalPda, getSpendingVoteRecordPda } from '../../setup/treasurySetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    updateProposalStatusOnChain,
+    getProposalMetadataPda,
+    getCreatorBondPda,
+    getProposalVaultPda,
+    getEpochSymbolPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+const DEFAULT_EPOCH_ARCHIVE_GRACE_PERIOD = 30 * 24 * 60 * 60;
+
+export function runArchiveEpochTests() {
+    describe('Instruction: archive_epoch', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+        let crankKeypair: Keypair;
+        let epochHistoryPda: PublicKey;
+
+        let epochId: anchor.BN;
+        let epochPda: PublicKey;
+
+        async function setGracePeriod(seconds: number) {
+            await program.methods
+                .updateEpochArchiveGracePeriod(new anchor.BN(seconds))
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: ctx.adminKeypair.publicKey,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        }
+
+        async function archiveEpoch() {
+            await program.methods
+                .archiveEpoch()
+                .accounts({
+                    authority: crankKeypair.publicKey,
+                    programConfig: ctx.programConfigAddress,
+                    epochManagement: epochPda,
+                    leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                    epochHistory: epochHistoryPda,
+                    adminAuthority: ctx.adminKeypair.publicKey,
+                    treasury: ctx.treasuryAddress,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([crankKeypair])
+                .rpc();
+        }
+
+        async function createProposal(symbol: string): Promise<PublicKey> {
+            const details: TokenProposalDetails = {
+                epochId,
+                name: `Archive Token ${symbol}`,
+                symbol,
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test epoch archiving.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            return createProposalOnChain(ctx, creatorKeypair, details, epochPda);
+        }
+
+        async function expectArchiveError(expectedCode: string) {
+            try {
+                await archiveEpoch();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal(expectedCode);
+            }
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+            [epochHistoryPda] = PublicKey.findProgramAddressSync([Buffer.from("epoch_history")], program.programId);
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            crankKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair, crankKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 3 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+            await setGracePeriod(0);
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            epochPda = await ensureEpochIsActive(ctx, epochId);
+        });
+
+        after(async () => {
+            await setGracePeriod(DEFAULT_EPOCH_ARCHIVE_GRACE_PERIOD);
+        });
+
+        it('should close an empty processed epoch and append its summary to the history', async () => {
+            await closeEpochOnChain(ctx, epochId);
+            await markEpochAsProcessedOnChain(ctx, epochId);
+
+            await archiveEpoch();
+
+            expect(await ctx.provider.connection.getAccountInfo(epochPda)).to.be.null;
+            expect(await ctx.provider.connection.getAccountInfo(getLeaderboardPda(program.programId, epochId)[0])).to.be.null;
+            const history = await program.account.epochHistory.fetch(epochHistoryPda);
+            const summary = history.summaries[history.summaries.length - 1];
+            expect(summary.epochId.eq(epochId)).to.be.true;
+            expect(summary.winnersCount.isZero()).to.be.true;
+            expect(history.summaries.length).to.be.at.most(32);
+        });
+
+        it('should fail before the epoch is processed', async () => {
+            await closeEpochOnChain(ctx, epochId);
+            await expectArchiveError('EpochNotProcessedYet');
+        });
+
+        it('should fail while the grace period has not elapsed', async () => {
+            await setGracePeriod(24 * 60 * 60);
+            try {
+                await closeEpochOnChain(ctx, epochId);
+                await markEpochAsProcessedOnChain(ctx, epochId);
+                await expectArchiveError('EpochGracePeriodNotElapsed');
+            } finally {
+                await setGracePeriod(0);
+            }
+        });
+
+        it('should wait for rejected proposals to be closed and record the winners', async () => {
+            const winnerPda = await createProposal("AWIN");
+            const loserPda = await createProposal("ALOSE");
+            const winnerSupportPda = await supportProposalOnChain(ctx, supporterKeypair, winnerPda, epochId, epochPda, new anchor.BN(LAMPORTS_PER_SOL / 2));
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, winnerPda, epochPda, { validated: {} });
+            await updateProposalStatusOnChain(ctx, loserPda, epochPda, { rejected: {} });
+            await markEpochAsProcessedOnChain(ctx, epochId);
+
+            await expectArchiveError('EpochHasOpenProposals');
+
+            // La proposition rejetée n'a aucun supporter : elle peut être fermée tout de suite
+            await program.methods
+                .closeProposal()
+                .accounts({
+                    authority: crankKeypair.publicKey,
+                    programConfig: ctx.programConfigAddress,
+                    epochManagement: epochPda,
+                    tokenProposal: loserPda,
+                    proposalVault: getProposalVaultPda(program.programId, loserPda)[0],
+                    proposalMetadata: getProposalMetadataPda(program.programId, loserPda)[0],
+                    creatorBond: getCreatorBondPda(program.programId, loserPda)[0],
+                    epochSymbol: getEpochSymbolPda(program.programId, epochId, "ALOSE")[0],
+                    creator: creatorKeypair.publicKey,
+                    treasury: ctx.treasuryAddress,
+                } as any)
+                .signers([crankKeypair])
+                .rpc();
+
+            const epoch = await program.account.epochManagement.fetch(epochPda);
+            await archiveEpoch();
+
+            const history = await program.account.epochHistory.fetch(epochHistoryPda);
+            const summary = history.summaries[history.summaries.length - 1];
+            expect(summary.epochId.eq(epochId)).to.be.true;
+            expect(summary.winnersCount.toNumber()).to.equal(1);
+            expect(summary.totalRaised.eq(epoch.totalRaised)).to.be.true;
+            expect(summary.totalRaised.toNumber()).to.be.greaterThan(0);
+
+            // Le résumé archivé remplace le compte d'époque pour le poids des votes de dépenses
+            await ensureTreasuryRolesInitialized(ctx, [ctx.adminKeypair.publicKey]);
+            const spendingId = generateRandomBN();
+            const [archivedSpendingPda] = getSpendingProposalPda(program.programId, spendingId);
+            await program.methods
+                .createSpendingProposal(spendingId, { operations: {} } as any, crankKeypair.publicKey, new anchor.BN(1000), "Archived epoch weight")
+                .accounts({
+                    proposer: ctx.adminKeypair.publicKey,
+                    treasuryRoles: ctx.treasuryRolesAddress,
+                    treasury: ctx.treasuryAddress,
+                    spendingProposal: archivedSpendingPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+            await program.methods
+                .voteSpendingProposal(true)
+                .accounts({
+                    voter: supporterKeypair.publicKey,
+                    spendingProposal: archivedSpendingPda,
+                    voteRecord: getSpendingVoteRecordPda(program.programId, archivedSpendingPda, supporterKeypair.publicKey)[0],
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .remainingAccounts([winnerSupportPda, epochHistoryPda].map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+                .signers([supporterKeypair])
+                .rpc();
+            const support = await program.account.userProposalSupport.fetch(winnerSupportPda);
+            const spending = await program.account.spendingProposal.fetch(archivedSpendingPda);
+            expect(spending.votesFor.eq(support.amount)).to.be.true;
+        });
+    });
+}
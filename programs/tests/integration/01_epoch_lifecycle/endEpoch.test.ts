@@ -1,8 +1,8 @@
 import * as anchor from '@coral-xyz/anchor';
-import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from '@solana/web3.js';
 import { expect } from 'chai';
 import { TestContext, getInitializedContext } from '../../setup';
-import { ensureEpochIsActive, closeEpochOnChain, getEpochManagementPda } from '../../setup/epochSetup';
+import { ensureEpochIsActive, ensureEpochExists, closeEpochOnChain, getEpochManagementPda } from '../../setup/epochSetup';
 import { generateRandomId } from '../../utils_for_tests/helpers';
 
 /**
@@ -39,6 +39,7 @@ export function runEndEpochTests() {
                     authority: ctx.adminKeypair.publicKey,
                     programConfig: ctx.programConfigAddress!,
                     epochManagement: activeEpochPda,
+                    treasury: ctx.treasuryAddress,
                     systemProgram: SystemProgram.programId,
                 } as any)
                 .signers([ctx.adminKeypair])
@@ -62,6 +63,7 @@ export function runEndEpochTests() {
                         authority: ctx.adminKeypair.publicKey,
                         programConfig: ctx.programConfigAddress!,
                         epochManagement: activeEpochPda,
+                        treasury: ctx.treasuryAddress,
                         systemProgram: SystemProgram.programId,
                     } as any)
                     .signers([ctx.adminKeypair])
@@ -84,6 +86,7 @@ export function runEndEpochTests() {
                         authority: ctx.adminKeypair.publicKey,
                         programConfig: ctx.programConfigAddress!,
                         epochManagement: nonExistentEpochPda, 
+                        treasury: ctx.treasuryAddress,
                         systemProgram: SystemProgram.programId,
                     } as any)
                     .signers([ctx.adminKeypair])
@@ -99,6 +102,44 @@ export function runEndEpochTests() {
             }
         });
         
+        it('should let anyone close an epoch once its end time has passed', async () => {
+            const crankKeypair = Keypair.generate();
+            const sig = await ctx.provider.connection.requestAirdrop(crankKeypair.publicKey, 0.1 * LAMPORTS_PER_SOL);
+            await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+
+            const now = Math.floor(Date.now() / 1000);
+            const expiredEpochId = new anchor.BN(generateRandomId());
+            const expiredEpochPda = await ensureEpochExists(ctx, expiredEpochId, new anchor.BN(now - 120), new anchor.BN(now - 60));
+
+            await closeEpochOnChain(ctx, expiredEpochId, crankKeypair);
+
+            const epochAccount = await ctx.program.account.epochManagement.fetch(expiredEpochPda);
+            expect(JSON.stringify(epochAccount.status)).to.equal(JSON.stringify({ closed: {} }));
+        });
+
+        it('should fail when a non-admin closes an epoch before its end time', async () => {
+            const crankKeypair = Keypair.generate();
+            const sig = await ctx.provider.connection.requestAirdrop(crankKeypair.publicKey, 0.1 * LAMPORTS_PER_SOL);
+            await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+
+            try {
+                await ctx.program.methods
+                    .endEpoch(activeEpochId)
+                    .accounts({
+                        authority: crankKeypair.publicKey,
+                        programConfig: ctx.programConfigAddress!,
+                        epochManagement: activeEpochPda,
+                        treasury: ctx.treasuryAddress,
+                        systemProgram: SystemProgram.programId,
+                    } as any)
+                    .signers([crankKeypair])
+                    .rpc();
+                expect.fail("  [EndEpochTests] Transaction should have failed because the epoch has not expired.");
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal("Unauthorized");
+            }
+        });
+
         it('closeEpochOnChain helper should correctly close an active epoch', async () => {
             await closeEpochOnChain(ctx, activeEpochId);
             const epochAccount = await ctx.program.account.epochManagement.fetch(activeEpochPda);
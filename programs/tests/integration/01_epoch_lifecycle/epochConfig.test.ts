@@ -0,0 +1,166 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, getLeaderboardPda } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    updateProposalStatusOnChain,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runEpochConfigTests() {
+    describe('Instruction: start_epoch (Epoch configuration overrides)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+
+        let epochId: anchor.BN;
+
+        // Mini-époque : un seul gagnant, frais de support réduits à 0,1%
+        function miniEpochConfig(overrides: object = {}) {
+            return {
+                maxWinners: new anchor.BN(1),
+                minSolRaisedForLaunch: null,
+                minContributorsForLaunch: null,
+                maxSupportPerWalletPerProposal: null,
+                maxSupportPerWalletPerEpoch: null,
+                maxSolRaisedPerProposal: null,
+                proposalCreationFeeLamports: null,
+                supportFeeBps: 10,
+                allowedCreators: [],
+                ...overrides,
+            };
+        }
+
+        function details(symbol: string): TokenProposalDetails {
+            return {
+                epochId,
+                name: `Mini ${symbol}`,
+                symbol,
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token proposed during a mini epoch.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 5 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(() => {
+            epochId = generateRandomBN();
+        });
+
+        it('should store the epoch configuration', async () => {
+            const epochPda = await ensureEpochIsActive(ctx, epochId, undefined, miniEpochConfig());
+
+            const epoch = await program.account.epochManagement.fetch(epochPda);
+            expect(epoch.config.maxWinners!.toNumber()).to.equal(1);
+            expect(epoch.config.supportFeeBps).to.equal(10);
+            expect(epoch.config.minSolRaisedForLaunch).to.be.null;
+        });
+
+        it('should keep the global rules when no configuration is given', async () => {
+            const epochPda = await ensureEpochIsActive(ctx, epochId);
+
+            const epoch = await program.account.epochManagement.fetch(epochPda);
+            expect(epoch.config.maxWinners).to.be.null;
+            expect(epoch.config.supportFeeBps).to.be.null;
+            expect(epoch.config.allowedCreators).to.be.empty;
+        });
+
+        it('should reject a zero support fee', async () => {
+            try {
+                await ensureEpochIsActive(ctx, epochId, undefined, miniEpochConfig({ supportFeeBps: 0 }));
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidEpochConfig');
+            }
+        });
+
+        it('should charge the support fee of the epoch', async () => {
+            const epochPda = await ensureEpochIsActive(ctx, epochId, undefined, miniEpochConfig());
+            const proposalPda = await createProposalOnChain(ctx, creatorKeypair, details("MFEE"), epochPda);
+
+            const amount = LAMPORTS_PER_SOL;
+            const userSupportPda = await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, epochPda, new anchor.BN(amount));
+
+            const support = await program.account.userProposalSupport.fetch(userSupportPda);
+            const expectedFee = amount * 10 / 10_000;
+            expect(support.feesPaid.toNumber()).to.equal(expectedFee);
+            expect(support.amount.toNumber()).to.equal(amount - expectedFee);
+        });
+
+        it('should only let allowed creators propose', async () => {
+            const epochPda = await ensureEpochIsActive(ctx, epochId, undefined, miniEpochConfig({
+                allowedCreators: [creatorKeypair.publicKey],
+            }));
+            await createProposalOnChain(ctx, creatorKeypair, details("MOK"), epochPda);
+
+            try {
+                await createProposalOnChain(ctx, supporterKeypair, details("MKO"), epochPda);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('CreatorNotAllowedInEpoch');
+            }
+        });
+
+        it('should reject further validations once the epoch has its winners', async () => {
+            const epochPda = await ensureEpochIsActive(ctx, epochId, undefined, miniEpochConfig());
+            const firstPda: PublicKey = await createProposalOnChain(ctx, creatorKeypair, details("MWIN"), epochPda);
+            const secondPda: PublicKey = await createProposalOnChain(ctx, creatorKeypair, details("MTWO"), epochPda);
+            await closeEpochOnChain(ctx, epochId);
+
+            await updateProposalStatusOnChain(ctx, firstPda, epochPda, { validated: {} });
+            // Le gagnant suivant est rejeté d'office, comme sous le seuil de lancement
+            await updateProposalStatusOnChain(ctx, secondPda, epochPda, { validated: {} });
+
+            const second = await program.account.tokenProposal.fetch(secondPda);
+            expect(second.status).to.deep.equal({ rejected: {} });
+            const epoch = await program.account.epochManagement.fetch(epochPda);
+            expect(epoch.winnersCount.toNumber()).to.equal(1);
+        });
+
+        it('should give the winner places in leaderboard order', async () => {
+            const epochPda = await ensureEpochIsActive(ctx, epochId, undefined, miniEpochConfig());
+            const topPda: PublicKey = await createProposalOnChain(ctx, creatorKeypair, details("MTOP"), epochPda);
+            const lowPda: PublicKey = await createProposalOnChain(ctx, creatorKeypair, details("MLOW"), epochPda);
+            await supportProposalOnChain(ctx, supporterKeypair, topPda, epochId, epochPda, new anchor.BN(LAMPORTS_PER_SOL));
+            await supportProposalOnChain(ctx, supporterKeypair, lowPda, epochId, epochPda, new anchor.BN(LAMPORTS_PER_SOL / 2));
+            await closeEpochOnChain(ctx, epochId);
+
+            // La moins bien classée ne peut pas prendre la place avant la tête du classement
+            try {
+                await updateProposalStatusOnChain(ctx, lowPda, epochPda, { validated: {} });
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('ProposalOutOfRankOrder');
+            }
+
+            await updateProposalStatusOnChain(ctx, topPda, epochPda, { validated: {} });
+            await updateProposalStatusOnChain(ctx, lowPda, epochPda, { validated: {} });
+
+            const top = await program.account.tokenProposal.fetch(topPda);
+            const low = await program.account.tokenProposal.fetch(lowPda);
+            expect(top.status).to.deep.equal({ validated: {} });
+            expect(low.status).to.deep.equal({ rejected: {} });
+            const leaderboard = await program.account.epochLeaderboard.fetch(getLeaderboardPda(program.programId, epochId)[0]);
+            expect(leaderboard.len).to.equal(0);
+        });
+    });
+}
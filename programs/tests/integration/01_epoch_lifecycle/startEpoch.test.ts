@@ -2,7 +2,7 @@ import * as anchor from '@coral-xyz/anchor';
 import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
 import { expect } from 'chai';
 import { TestContext, getInitializedContext } from '../../setup';
-import { ensureEpochExists, getEpochManagementPda } from '../../setup/epochSetup';
+import { ensureEpochExists, getEpochManagementPda, getLeaderboardPda } from '../../setup/epochSetup';
 import { generateRandomId } from '../../utils_for_tests/helpers';
 
 /**
@@ -28,17 +28,18 @@ export function runStartEpochTests() {
             // console.log(`  [StartEpochTests] beforeEach: newEpochId=${newEpochId}, newEpochPda=${newEpochPda.toBase58()}`);
         });
 
-        it('should successfully create a new epoch with valid times', async () => {
+        it('should successfully create a new pending epoch with valid times', async () => {
             const now = Math.floor(Date.now() / 1000);
             const startTime = new anchor.BN(now - 60); 
             const endTime = new anchor.BN(now + 3600); 
 
             await ctx.program.methods
-                .startEpoch(newEpochId, startTime, endTime)
+                .startEpoch(newEpochId, startTime, endTime, null)
                 .accounts({
                     authority: ctx.adminKeypair.publicKey,
                     programConfig: ctx.programConfigAddress!,
                     epochManagement: newEpochPda,
+                    leaderboard: getLeaderboardPda(ctx.program.programId, newEpochId)[0],
                     systemProgram: SystemProgram.programId,
                 } as any)
                 .signers([ctx.adminKeypair])
@@ -48,7 +49,7 @@ export function runStartEpochTests() {
             expect(epochAccount.epochId.eq(newEpochId)).to.be.true;
             expect(epochAccount.startTime.eq(startTime)).to.be.true;
             expect(epochAccount.endTime.eq(endTime)).to.be.true;
-            expect(JSON.stringify(epochAccount.status)).to.equal(JSON.stringify({ active: {} }));
+            expect(JSON.stringify(epochAccount.status)).to.equal(JSON.stringify({ pending: {} }));
             expect(epochAccount.processed).to.be.false;
             console.log(`  [StartEpochTests] Epoch ${newEpochId} created successfully.`);
         });
@@ -60,11 +61,12 @@ export function runStartEpochTests() {
 
             try {
                 await ctx.program.methods
-                    .startEpoch(newEpochId, startTime, endTime)
+                    .startEpoch(newEpochId, startTime, endTime, null)
                     .accounts({
                         authority: ctx.adminKeypair.publicKey,
                         programConfig: ctx.programConfigAddress!,
                         epochManagement: newEpochPda,
+                        leaderboard: getLeaderboardPda(ctx.program.programId, newEpochId)[0],
                         systemProgram: SystemProgram.programId,
                     } as any)
                     .signers([ctx.adminKeypair])
@@ -82,11 +84,12 @@ export function runStartEpochTests() {
             const endTime = new anchor.BN(now + 3600);
 
             await ctx.program.methods
-                .startEpoch(newEpochId, startTime, endTime)
+                .startEpoch(newEpochId, startTime, endTime, null)
                 .accounts({
                     authority: ctx.adminKeypair.publicKey,
                     programConfig: ctx.programConfigAddress!,
                     epochManagement: newEpochPda,
+                    leaderboard: getLeaderboardPda(ctx.program.programId, newEpochId)[0],
                     systemProgram: SystemProgram.programId,
                 } as any)
                 .signers([ctx.adminKeypair])
@@ -95,11 +98,12 @@ export function runStartEpochTests() {
 
             try {
                 await ctx.program.methods
-                    .startEpoch(newEpochId, startTime, endTime) 
+                    .startEpoch(newEpochId, startTime, endTime, null) 
                     .accounts({
                         authority: ctx.adminKeypair.publicKey,
                         programConfig: ctx.programConfigAddress!,
                         epochManagement: newEpochPda,
+                        leaderboard: getLeaderboardPda(ctx.program.programId, newEpochId)[0],
                         systemProgram: SystemProgram.programId,
                     } as any)
                     .signers([ctx.adminKeypair])
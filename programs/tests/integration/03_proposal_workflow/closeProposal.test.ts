@@ -0,0 +1,147 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, markEpochAsProcessedOnChain } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    updateProposalStatusOnChain,
+    getProposalMetadataPda,
+    getCreatorBondPda,
+    getProposalVaultPda,
+    getEpochSymbolPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runCloseProposalTests() {
+    describe('Instruction: close_proposal', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+        let crankKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+        let userSupportPda: PublicKey;
+
+        async function closeProposal() {
+            await program.methods
+                .closeProposal()
+                .accounts({
+                    authority: crankKeypair.publicKey,
+                    programConfig: ctx.programConfigAddress,
+                    epochManagement: activeEpochPda,
+                    tokenProposal: proposalPda,
+                    proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                    proposalMetadata: getProposalMetadataPda(program.programId, proposalPda)[0],
+                    creatorBond: getCreatorBondPda(program.programId, proposalPda)[0],
+                    epochSymbol: getEpochSymbolPda(program.programId, epochId, "CLOSE")[0],
+                    creator: creatorKeypair.publicKey,
+                    treasury: ctx.treasuryAddress,
+                } as any)
+                .signers([crankKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            crankKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair, crankKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 3 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            const details: TokenProposalDetails = {
+                epochId,
+                name: "Closable Token",
+                symbol: "CLOSE",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test proposal closing.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+            userSupportPda = await supportProposalOnChain(
+                ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL)
+            );
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { rejected: {} });
+            await markEpochAsProcessedOnChain(ctx, epochId);
+        });
+
+        it('should fail while supporters have not been refunded', async () => {
+            try {
+                await closeProposal();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('ProposalNotFullyRefunded');
+            }
+        });
+
+        it('should track refunds, return the rent to the creator and sweep the residual to the treasury', async () => {
+            await program.methods.reclaimSupport()
+                .accounts({
+                    user: supporterKeypair.publicKey,
+                    tokenProposal: proposalPda,
+                    proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                    userProposalSupport: userSupportPda,
+                    epochManagement: activeEpochPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([supporterKeypair])
+                .rpc();
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.solRefunded.eq(proposal.solRaised)).to.be.true;
+
+            // Dépôt direct sur le vault : un surplus au-delà de sa réserve de rente
+            const [vaultPda] = getProposalVaultPda(program.programId, proposalPda);
+            const vaultReserve = await ctx.provider.connection.getMinimumBalanceForRentExemption(0);
+            expect(await ctx.provider.connection.getBalance(vaultPda)).to.equal(vaultReserve);
+            const residual = 12_345;
+            const depositTx = new anchor.web3.Transaction().add(
+                SystemProgram.transfer({ fromPubkey: crankKeypair.publicKey, toPubkey: vaultPda, lamports: residual })
+            );
+            await ctx.provider.sendAndConfirm(depositTx, [crankKeypair]);
+
+            const proposalLamports = await ctx.provider.connection.getBalance(proposalPda);
+            const [metadataPda] = getProposalMetadataPda(program.programId, proposalPda);
+            const metadataLamports = await ctx.provider.connection.getBalance(metadataPda);
+            // Caution nulle par défaut : seule la rente de son compte revient au créateur
+            const [bondPda] = getCreatorBondPda(program.programId, proposalPda);
+            const bondLamports = await ctx.provider.connection.getBalance(bondPda);
+            // Le symbole n'a pas été libéré : sa réservation est fermée avec la proposition
+            const [epochSymbolPda] = getEpochSymbolPda(program.programId, epochId, "CLOSE");
+            const epochSymbolLamports = await ctx.provider.connection.getBalance(epochSymbolPda);
+            expect(epochSymbolLamports).to.be.greaterThan(0);
+            const creatorBalanceBefore = await ctx.provider.connection.getBalance(creatorKeypair.publicKey);
+            const treasuryBefore = await program.account.treasury.fetch(ctx.treasuryAddress!);
+
+            await closeProposal();
+
+            expect(await ctx.provider.connection.getAccountInfo(proposalPda)).to.be.null;
+            expect(await ctx.provider.connection.getAccountInfo(metadataPda)).to.be.null;
+            expect(await ctx.provider.connection.getAccountInfo(bondPda)).to.be.null;
+            expect(await ctx.provider.connection.getAccountInfo(epochSymbolPda)).to.be.null;
+            expect(await ctx.provider.connection.getBalance(vaultPda)).to.equal(0);
+            const creatorBalanceAfter = await ctx.provider.connection.getBalance(creatorKeypair.publicKey);
+            expect(creatorBalanceAfter - creatorBalanceBefore).to.equal(proposalLamports + metadataLamports + bondLamports + epochSymbolLamports + vaultReserve);
+            const treasuryAfter = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            expect(treasuryAfter.operations.solBalance.toNumber() - treasuryBefore.operations.solBalance.toNumber()).to.equal(residual);
+        });
+    });
+}
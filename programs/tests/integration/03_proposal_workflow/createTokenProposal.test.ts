@@ -7,6 +7,7 @@ import { TestContext, getInitializedContext, generateRandomBN, shortenAddress }
 import { ensureEpochIsActive } from '../../setup/epochSetup';
 import {
     createProposalOnChain,
+    getProposalMetadataPda,
     TokenProposalDetails,
 } from '../../setup/proposalSetup';
 
@@ -43,7 +44,7 @@ export function runCreateTokenProposalTests() {
                 totalSupply: new anchor.BN(1000000),
                 creatorAllocationPercentage: 10,
                 description: "A great token for testing purposes.",
-                imageUrl: "http://example.com/image.png",
+                imageUrl: "https://example.com/image.png",
                 lockupPeriod: new anchor.BN(86400 * 7),
             };
 
@@ -57,12 +58,16 @@ export function runCreateTokenProposalTests() {
             expect(proposalAccount.tokenName).to.equal(proposalDetails.name);
             expect(proposalAccount.tokenSymbol).to.equal(proposalDetails.symbol);
             expect(proposalAccount.creatorAllocation).to.equal(proposalDetails.creatorAllocationPercentage);
-            expect(proposalAccount.description).to.equal(proposalDetails.description);
-            expect(proposalAccount.imageUrl).to.equal(proposalDetails.imageUrl);
             expect(proposalAccount.totalSupply.eq(proposalDetails.totalSupply)).to.be.true;
             expect(proposalAccount.lockupPeriod.eq(proposalDetails.lockupPeriod)).to.be.true;
 
             expect(proposalAccount.solRaised.isZero()).to.be.true;
+
+            // Les textes sont stockés à part, dans le PDA de métadonnées
+            const metadata = await program.account.proposalMetadata.fetch(getProposalMetadataPda(program.programId, proposalPda)[0]);
+            expect(metadata.proposal.equals(proposalPda)).to.be.true;
+            expect(metadata.description).to.equal(proposalDetails.description);
+            expect(metadata.imageUrl).to.equal(proposalDetails.imageUrl);
             expect(proposalAccount.totalContributions.isZero()).to.be.true;
             expect((proposalAccount.status as any)).to.deep.equal({ active: {} });
             expect(proposalAccount.creationTimestamp.gt(new anchor.BN(0))).to.be.true;
@@ -70,11 +75,104 @@ export function runCreateTokenProposalTests() {
             console.log(`  [CreateProposalTests] Proposal "${proposalDetails.name}" created and initial state verified.`);
         });
 
+        describe('Input validation', () => {
+            const validDetails = (): TokenProposalDetails => ({
+                epochId: currentEpochId,
+                name: "Validation Token",
+                symbol: "VAL",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test input validation.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            });
+
+            async function expectCreationError(overrides: Partial<TokenProposalDetails>, expectedCode: string) {
+                try {
+                    await createProposalOnChain(ctx, proposerKeypair, { ...validDetails(), ...overrides }, activeEpochPda);
+                    expect.fail('Transaction should have failed but succeeded.');
+                } catch (error) {
+                    expect((error as anchor.AnchorError).error.errorCode.code).to.equal(expectedCode);
+                }
+            }
+
+            it('should accept an ipfs:// image URL', async () => {
+                const proposalPda = await createProposalOnChain(
+                    ctx, proposerKeypair, { ...validDetails(), imageUrl: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi" }, activeEpochPda
+                );
+                const metadata = await program.account.proposalMetadata.fetch(getProposalMetadataPda(program.programId, proposalPda)[0]);
+                expect(metadata.imageUrl).to.equal("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+            });
+
+            it('should fail with an empty token name', async () => {
+                await expectCreationError({ name: "" }, 'TokenNameEmpty');
+            });
+
+            it('should fail with a token name longer than 32 bytes', async () => {
+                // Le nom est une seed du PDA : le client ne peut pas le dériver au-delà de 32 octets,
+                // le programme doit refuser le nom avant de dériver le compte
+                try {
+                    await createProposalOnChain(
+                        ctx, proposerKeypair, { ...validDetails(), name: "N".repeat(33) }, activeEpochPda, [], Keypair.generate().publicKey
+                    );
+                    expect.fail('Transaction should have failed but succeeded.');
+                } catch (error) {
+                    expect((error as anchor.AnchorError).error.errorCode.code).to.equal('TokenNameTooLong');
+                }
+            });
+
+            it('should fail with an empty token symbol', async () => {
+                await expectCreationError({ symbol: "" }, 'TokenSymbolEmpty');
+            });
+
+            it('should fail with a token symbol longer than 8 characters', async () => {
+                await expectCreationError({ symbol: "TOOLONGSYM" }, 'TokenSymbolTooLong');
+            });
+
+            it('should fail with a token symbol containing invalid characters', async () => {
+                await expectCreationError({ symbol: "TK-1" }, 'InvalidTokenSymbol');
+            });
+
+            it('should fail with a description longer than 512 bytes', async () => {
+                await expectCreationError({ description: "d".repeat(513) }, 'DescriptionTooLong');
+            });
+
+            it('should fail with an image URL longer than 256 bytes', async () => {
+                await expectCreationError({ imageUrl: "https://example.com/" + "i".repeat(240) }, 'ImageUrlTooLong');
+            });
+
+            it('should fail with an image URL using an unsupported scheme', async () => {
+                await expectCreationError({ imageUrl: "http://example.com/image.png" }, 'InvalidImageUrl');
+            });
+
+            it('should fail with a zero total supply', async () => {
+                await expectCreationError({ totalSupply: new anchor.BN(0) }, 'InvalidTotalSupply');
+            });
+
+            it('should fail with a total supply above the maximum', async () => {
+                await expectCreationError({ totalSupply: new anchor.BN("1000000000001") }, 'TotalSupplyTooHigh');
+            });
+
+            it('should fail with a creator allocation above 10%', async () => {
+                await expectCreationError({ creatorAllocationPercentage: 11 }, 'CreatorAllocationTooHigh');
+            });
+
+            it('should fail with a negative lockup period', async () => {
+                await expectCreationError({ lockupPeriod: new anchor.BN(-1) }, 'NegativeLockupPeriod');
+            });
+
+            it('should fail with a non-zero lockup period shorter than one day', async () => {
+                await expectCreationError({ lockupPeriod: new anchor.BN(86400 - 1) }, 'LockupPeriodTooShort');
+            });
+
+            it('should fail with a lockup period longer than one year', async () => {
+                await expectCreationError({ lockupPeriod: new anchor.BN(365 * 86400 + 1) }, 'LockupPeriodTooLong');
+            });
+        });
+
         // TODO: Ajouter des tests pour les cas d'erreur:
         // - Époque non active / inexistante
         // - Frais de création (si ProgramConfig les définit et que le proposeur n'a pas assez de SOL - nécessite de simuler cela)
-        // - Dépassement de la taille max pour les strings (name, symbol, description, imageUrl)
-        // - Valeurs invalides (ex: creatorAllocationPercentage > 100)
         // - Tentative de création d'une proposition avec le même nom par le même proposeur dans la même époque (devrait échouer à cause du PDA)
     });
 } 
\ No newline at end of file
@@ -0,0 +1,178 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    updateProposalStatusOnChain,
+    getCreatorBondPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runCreatorBondTests() {
+    describe('Instruction: settle_creator_bond (Creator bond)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+        let bondPda: PublicKey;
+
+        const bondLamports = 0.1 * LAMPORTS_PER_SOL;
+        const minRaise = 0.5 * LAMPORTS_PER_SOL;
+
+        async function setCreatorBond(lamports: number, releaseFrom: number) {
+            await program.methods
+                .updateCreatorBond(new anchor.BN(lamports), new anchor.BN(releaseFrom))
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: ctx.adminKeypair.publicKey,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        }
+
+        async function settleBond() {
+            await program.methods
+                .settleCreatorBond()
+                .accounts({
+                    authority: supporterKeypair.publicKey,
+                    epochManagement: activeEpochPda,
+                    tokenProposal: proposalPda,
+                    creatorBond: bondPda,
+                    creator: creatorKeypair.publicKey,
+                    treasury: ctx.treasuryAddress,
+                } as any)
+                .signers([supporterKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 5 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+            await setCreatorBond(bondLamports, minRaise);
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            const details: TokenProposalDetails = {
+                epochId,
+                name: "Bonded Token",
+                symbol: "BOND",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test the creator bond.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+            [bondPda] = getCreatorBondPda(program.programId, proposalPda);
+        });
+
+        after(async () => {
+            await setCreatorBond(0, 0);
+        });
+
+        it('should hold the bond posted by the creator', async () => {
+            const bond = await program.account.creatorBond.fetch(bondPda);
+            expect(bond.proposal.equals(proposalPda)).to.be.true;
+            expect(bond.creator.equals(creatorKeypair.publicKey)).to.be.true;
+            expect(bond.amount.toNumber()).to.equal(bondLamports);
+
+            const info = await ctx.provider.connection.getAccountInfo(bondPda);
+            const rent = await ctx.provider.connection.getMinimumBalanceForRentExemption(info!.data.length);
+            expect(info!.lamports).to.equal(rent + bondLamports);
+        });
+
+        it('should fail to settle an active proposal below the release threshold', async () => {
+            try {
+                await settleBond();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('CreatorBondNotSettleable');
+            }
+        });
+
+        it('should release the bond on the minimum raise only once the epoch is closed', async () => {
+            await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+
+            // Les supporters peuvent encore se retirer : la levée n'est pas définitive
+            try {
+                await settleBond();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('CreatorBondNotSettleable');
+            }
+
+            await closeEpochOnChain(ctx, epochId);
+            const bondAccountLamports = await ctx.provider.connection.getBalance(bondPda);
+            const creatorBefore = await ctx.provider.connection.getBalance(creatorKeypair.publicKey);
+
+            await settleBond();
+
+            expect(await ctx.provider.connection.getAccountInfo(bondPda)).to.be.null;
+            const creatorAfter = await ctx.provider.connection.getBalance(creatorKeypair.publicKey);
+            expect(creatorAfter - creatorBefore).to.equal(bondAccountLamports);
+        });
+
+        it('should keep the release threshold in force when the proposal was created', async () => {
+            const bond = await program.account.creatorBond.fetch(bondPda);
+            expect(bond.minRaise.toNumber()).to.equal(minRaise);
+
+            // Un seuil relevé ensuite ne concerne que les nouvelles propositions
+            await setCreatorBond(bondLamports, 10 * LAMPORTS_PER_SOL);
+            try {
+                await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+                await closeEpochOnChain(ctx, epochId);
+                await settleBond();
+            } finally {
+                await setCreatorBond(bondLamports, minRaise);
+            }
+            expect(await ctx.provider.connection.getAccountInfo(bondPda)).to.be.null;
+        });
+
+        it('should release the bond when the proposal is launched', async () => {
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { validated: {} });
+            const bondAccountLamports = await ctx.provider.connection.getBalance(bondPda);
+            const creatorBefore = await ctx.provider.connection.getBalance(creatorKeypair.publicKey);
+
+            await settleBond();
+
+            const creatorAfter = await ctx.provider.connection.getBalance(creatorKeypair.publicKey);
+            expect(creatorAfter - creatorBefore).to.equal(bondAccountLamports);
+        });
+
+        it('should slash the bond to the treasury when the proposal is rejected below the threshold', async () => {
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { rejected: {} });
+            const bondAccountLamports = await ctx.provider.connection.getBalance(bondPda);
+            const creatorBefore = await ctx.provider.connection.getBalance(creatorKeypair.publicKey);
+            const treasuryBefore = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+
+            await settleBond();
+
+            expect(await ctx.provider.connection.getAccountInfo(bondPda)).to.be.null;
+            const treasuryAfter = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            expect(treasuryAfter - treasuryBefore).to.equal(bondLamports);
+            // Seule la rente du compte revient au créateur
+            const creatorAfter = await ctx.provider.connection.getBalance(creatorKeypair.publicKey);
+            expect(creatorAfter - creatorBefore).to.equal(bondAccountLamports - bondLamports);
+        });
+    });
+}
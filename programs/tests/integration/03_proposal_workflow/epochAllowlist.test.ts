@@ -0,0 +1,149 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { keccak_256 } from '@noble/hashes/sha3';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+// Même hachage que programs::utils::allowlist (et tools/allowlist-tool)
+function allowlistLeaf(wallet: PublicKey): Buffer {
+    return Buffer.from(keccak_256(Buffer.concat([Buffer.from([0]), wallet.toBuffer()])));
+}
+
+function allowlistNode(a: Buffer, b: Buffer): Buffer {
+    const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+    return Buffer.from(keccak_256(Buffer.concat([Buffer.from([1]), left, right])));
+}
+
+export function runEpochAllowlistTests() {
+    describe('Instruction: set_epoch_allowlist (Gated epochs)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let memberKeypair: Keypair;
+        let outsiderKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let root: number[];
+        let memberProof: number[][];
+        let creatorProof: number[][];
+
+        const supportAmount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+
+        function details(symbol: string): TokenProposalDetails {
+            return {
+                epochId,
+                name: `Gated ${symbol}`,
+                symbol,
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token proposed during a gated epoch.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+        }
+
+        async function setAllowlist(allowlistRoot: number[] | null, gatesCreators: boolean) {
+            await program.methods
+                .setEpochAllowlist(allowlistRoot, gatesCreators)
+                .accounts({
+                    authority: ctx.adminKeypair.publicKey,
+                    programConfig: ctx.programConfigAddress,
+                    epochManagement: activeEpochPda,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            memberKeypair = Keypair.generate();
+            outsiderKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, memberKeypair, outsiderKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 5 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+
+            // Arbre à deux feuilles : la preuve de chaque membre est la feuille de l'autre
+            const memberLeaf = allowlistLeaf(memberKeypair.publicKey);
+            const creatorLeaf = allowlistLeaf(creatorKeypair.publicKey);
+            root = [...allowlistNode(memberLeaf, creatorLeaf)];
+            memberProof = [[...creatorLeaf]];
+            creatorProof = [[...memberLeaf]];
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+        });
+
+        it('should store the allowlist root on the epoch', async () => {
+            await setAllowlist(root, true);
+
+            const epoch = await program.account.epochManagement.fetch(activeEpochPda);
+            expect(epoch.allowlistRoot).to.deep.equal(root);
+            expect(epoch.allowlistGatesCreators).to.be.true;
+
+            await setAllowlist(null, true);
+            const cleared = await program.account.epochManagement.fetch(activeEpochPda);
+            expect(cleared.allowlistRoot).to.be.null;
+            expect(cleared.allowlistGatesCreators).to.be.false;
+        });
+
+        it('should fail when called by a non-admin', async () => {
+            try {
+                await program.methods
+                    .setEpochAllowlist(root, false)
+                    .accounts({
+                        authority: outsiderKeypair.publicKey,
+                        programConfig: ctx.programConfigAddress,
+                        epochManagement: activeEpochPda,
+                    } as any)
+                    .signers([outsiderKeypair])
+                    .rpc();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('Unauthorized');
+            }
+        });
+
+        it('should only accept supports from allowlisted wallets', async () => {
+            const proposalPda = await createProposalOnChain(ctx, creatorKeypair, details("GSUP"), activeEpochPda);
+            await setAllowlist(root, false);
+
+            await supportProposalOnChain(ctx, memberKeypair, proposalPda, epochId, activeEpochPda, supportAmount, null, memberProof);
+
+            try {
+                await supportProposalOnChain(ctx, outsiderKeypair, proposalPda, epochId, activeEpochPda, supportAmount, null, memberProof);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('NotAllowlisted');
+            }
+        });
+
+        it('should only gate creators when the epoch asks for it', async () => {
+            await setAllowlist(root, false);
+            await createProposalOnChain(ctx, outsiderKeypair, details("GOPEN"), activeEpochPda);
+
+            await setAllowlist(root, true);
+            await createProposalOnChain(ctx, creatorKeypair, details("GMEM"), activeEpochPda, creatorProof);
+            try {
+                await createProposalOnChain(ctx, outsiderKeypair, details("GOUT"), activeEpochPda);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('NotAllowlisted');
+            }
+        });
+    });
+}
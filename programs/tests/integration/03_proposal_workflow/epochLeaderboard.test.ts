@@ -0,0 +1,136 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, getLeaderboardPda } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    getSupportPda,
+    getUserEpochSupportPda,
+    getProposalVaultPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runEpochLeaderboardTests() {
+    describe('Account: EpochLeaderboard', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let leaderboardPda: PublicKey;
+
+        async function createProposal(name: string, symbol: string): Promise<PublicKey> {
+            const details: TokenProposalDetails = {
+                epochId,
+                name,
+                symbol,
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test the epoch leaderboard.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            return createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+        }
+
+        async function support(proposal: PublicKey, lamports: number) {
+            await supportProposalOnChain(ctx, supporterKeypair, proposal, epochId, activeEpochPda, new anchor.BN(lamports));
+        }
+
+        async function ranking(): Promise<{ proposal: PublicKey; solRaised: anchor.BN }[]> {
+            const leaderboard = await program.account.epochLeaderboard.fetch(leaderboardPda);
+            return leaderboard.entries.slice(0, leaderboard.len);
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 5 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            [leaderboardPda] = getLeaderboardPda(program.programId, epochId);
+        });
+
+        it('should be created empty by start_epoch', async () => {
+            const leaderboard = await program.account.epochLeaderboard.fetch(leaderboardPda);
+            expect(leaderboard.epochId.eq(epochId)).to.be.true;
+            expect(leaderboard.len).to.equal(0);
+        });
+
+        it('should register new proposals with zero SOL raised', async () => {
+            const first = await createProposal("Board One", "BONE");
+            const second = await createProposal("Board Two", "BTWO");
+
+            const entries = await ranking();
+            expect(entries.map((e) => e.proposal.toBase58())).to.have.members([first.toBase58(), second.toBase58()]);
+            entries.forEach((e) => expect(e.solRaised.isZero()).to.be.true);
+        });
+
+        it('should keep proposals sorted by SOL raised after supports', async () => {
+            const low = await createProposal("Board Low", "BLOW");
+            const high = await createProposal("Board High", "BHIGH");
+            const mid = await createProposal("Board Mid", "BMID");
+
+            await support(low, 0.1 * LAMPORTS_PER_SOL);
+            await support(mid, 0.5 * LAMPORTS_PER_SOL);
+            await support(high, 1 * LAMPORTS_PER_SOL);
+
+            const entries = await ranking();
+            expect(entries.map((e) => e.proposal.toBase58())).to.deep.equal([high, mid, low].map((p) => p.toBase58()));
+            for (const entry of entries) {
+                const proposal = await program.account.tokenProposal.fetch(entry.proposal);
+                expect(entry.solRaised.eq(proposal.solRaised)).to.be.true;
+            }
+
+            // Un nouveau support fait remonter la dernière proposition en tête
+            await support(low, 2 * LAMPORTS_PER_SOL);
+            const reordered = await ranking();
+            expect(reordered[0].proposal.equals(low)).to.be.true;
+        });
+
+        it('should move a proposal down after an unsupport', async () => {
+            const first = await createProposal("Board First", "BFIRST");
+            const second = await createProposal("Board Second", "BSECOND");
+            await support(first, 1 * LAMPORTS_PER_SOL);
+            await support(second, 0.5 * LAMPORTS_PER_SOL);
+
+            const [userSupportPda] = getSupportPda(program.programId, epochId, supporterKeypair.publicKey, first);
+            const userSupport = await program.account.userProposalSupport.fetch(userSupportPda);
+            await program.methods
+                .unsupportProposal(userSupport.amount)
+                .accounts({
+                    user: supporterKeypair.publicKey,
+                    epoch: activeEpochPda,
+                    proposal: first,
+                    proposalVault: getProposalVaultPda(program.programId, first)[0],
+                    userSupport: userSupportPda,
+                    userEpochSupport: getUserEpochSupportPda(program.programId, epochId, supporterKeypair.publicKey)[0],
+                    leaderboard: leaderboardPda,
+                    programConfig: ctx.programConfigAddress,
+                    treasury: ctx.treasuryAddress,
+                } as any)
+                .signers([supporterKeypair])
+                .rpc();
+
+            const entries = await ranking();
+            expect(entries[0].proposal.equals(second)).to.be.true;
+            expect(entries[1].proposal.equals(first)).to.be.true;
+            expect(entries[1].solRaised.isZero()).to.be.true;
+        });
+    });
+}
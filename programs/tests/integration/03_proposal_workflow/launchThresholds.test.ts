@@ -0,0 +1,132 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, markEpochAsProcessedOnChain } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    updateProposalStatusOnChain,
+    getProposalVaultPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runLaunchThresholdsTests() {
+    describe('Launch eligibility thresholds (update_launch_thresholds / update_proposal_status)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let adminKeypair: Keypair;
+        let creatorKeypair: Keypair;
+        let supporters: Keypair[];
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+
+        async function setLaunchThresholds(signer: Keypair, minSolRaised: anchor.BN, minContributors: number) {
+            await program.methods
+                .updateLaunchThresholds(minSolRaised, new anchor.BN(minContributors))
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: signer.publicKey,
+                } as any)
+                .signers([signer])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+            adminKeypair = ctx.adminKeypair;
+
+            creatorKeypair = Keypair.generate();
+            supporters = [Keypair.generate(), Keypair.generate()];
+            for (const kp of [creatorKeypair, ...supporters]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 3 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+
+            // 1 SOL net et 2 supporters distincts minimum
+            await setLaunchThresholds(adminKeypair, new anchor.BN(LAMPORTS_PER_SOL), 2);
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            const details: TokenProposalDetails = {
+                epochId,
+                name: "Threshold Token",
+                symbol: "THT",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test launch thresholds.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+        });
+
+        after(async () => {
+            await setLaunchThresholds(adminKeypair, new anchor.BN(0), 0);
+        });
+
+        it('should fail to update the thresholds when the signer is not the admin', async () => {
+            try {
+                await setLaunchThresholds(creatorKeypair, new anchor.BN(1), 1);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('Unauthorized');
+            }
+        });
+
+        it('should validate a proposal that meets both thresholds', async () => {
+            for (const supporter of supporters) {
+                await supportProposalOnChain(ctx, supporter, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+            }
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { validated: {} });
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.status).to.deep.equal({ validated: {} });
+        });
+
+        it('should reject a proposal with too few contributors even if asked to validate it', async () => {
+            await supportProposalOnChain(ctx, supporters[0], proposalPda, epochId, activeEpochPda, new anchor.BN(2 * LAMPORTS_PER_SOL));
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { validated: {} });
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.status).to.deep.equal({ rejected: {} });
+        });
+
+        it('should reject an under-funded proposal and let its supporters reclaim', async () => {
+            const userSupportPdas: PublicKey[] = [];
+            for (const supporter of supporters) {
+                userSupportPdas.push(
+                    await supportProposalOnChain(ctx, supporter, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL / 10))
+                );
+            }
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { validated: {} });
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.status).to.deep.equal({ rejected: {} });
+
+            await markEpochAsProcessedOnChain(ctx, epochId);
+            await program.methods.reclaimSupport()
+                .accounts({
+                    user: supporters[0].publicKey,
+                    tokenProposal: proposalPda,
+                    proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                    userProposalSupport: userSupportPdas[0],
+                    epochManagement: activeEpochPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([supporters[0]])
+                .rpc();
+            expect(await ctx.provider.connection.getAccountInfo(userSupportPdas[0])).to.be.null;
+        });
+    });
+}
@@ -0,0 +1,209 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, getLeaderboardPda } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    getSupportPda,
+    getEpochSymbolPda,
+    getProposalMetadataPda,
+    getProposalVaultPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runManageProposalTests() {
+    describe('Instructions: update_proposal_metadata / cancel_proposal', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+        let proposalDetails: TokenProposalDetails;
+
+        async function updateMetadata(
+            signer: Keypair,
+            fields: { description?: string; imageUrl?: string; clearImage?: boolean; totalSupply?: anchor.BN; creatorAllocation?: number; lockupPeriod?: anchor.BN },
+        ) {
+            await program.methods
+                .updateProposalMetadata(
+                    fields.description ?? null,
+                    fields.imageUrl ?? null,
+                    fields.clearImage ?? false,
+                    fields.totalSupply ?? null,
+                    fields.creatorAllocation ?? null,
+                    fields.lockupPeriod ?? null,
+                )
+                .accounts({
+                    creator: signer.publicKey,
+                    epoch: activeEpochPda,
+                    tokenProposal: proposalPda,
+                    proposalMetadata: getProposalMetadataPda(program.programId, proposalPda)[0],
+                } as any)
+                .signers([signer])
+                .rpc();
+        }
+
+        async function cancelProposal(signer: Keypair) {
+            await program.methods
+                .cancelProposal()
+                .accounts({
+                    creator: signer.publicKey,
+                    epoch: activeEpochPda,
+                    tokenProposal: proposalPda,
+                    epochSymbol: getEpochSymbolPda(program.programId, epochId, proposalDetails.symbol)[0],
+                    leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                } as any)
+                .signers([signer])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 3 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            proposalDetails = {
+                epochId,
+                name: "Managed Token",
+                symbol: "MGT",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Descriptoin with a typo.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await createProposalOnChain(ctx, creatorKeypair, proposalDetails, activeEpochPda);
+        });
+
+        it('should let the creator fix the description and image before any support', async () => {
+            await updateMetadata(creatorKeypair, {
+                description: "Description without a typo.",
+                imageUrl: "https://example.com/image.png",
+                totalSupply: new anchor.BN(2000000),
+                creatorAllocation: 8,
+            });
+
+            const metadata = await program.account.proposalMetadata.fetch(getProposalMetadataPda(program.programId, proposalPda)[0]);
+            expect(metadata.description).to.equal("Description without a typo.");
+            expect(metadata.imageUrl).to.equal("https://example.com/image.png");
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.totalSupply.toNumber()).to.equal(2000000);
+            expect(proposal.creatorAllocation).to.equal(8);
+            expect(proposal.supporterAllocation).to.equal(46);
+        });
+
+        it('should let the creator clear the image', async () => {
+            try {
+                await updateMetadata(creatorKeypair, { imageUrl: "https://example.com/other.png", clearImage: true });
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('ConflictingImageUpdate');
+            }
+
+            await updateMetadata(creatorKeypair, { clearImage: true });
+            const metadata = await program.account.proposalMetadata.fetch(getProposalMetadataPda(program.programId, proposalPda)[0]);
+            expect(metadata.imageUrl).to.be.null;
+        });
+
+        it('should only allow non-economic fields once the proposal has supporters', async () => {
+            await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+
+            await updateMetadata(creatorKeypair, { description: "Updated after support." });
+            const metadata = await program.account.proposalMetadata.fetch(getProposalMetadataPda(program.programId, proposalPda)[0]);
+            expect(metadata.description).to.equal("Updated after support.");
+
+            try {
+                await updateMetadata(creatorKeypair, { totalSupply: new anchor.BN(1) });
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('EconomicFieldsLocked');
+            }
+        });
+
+        it('should fail when someone else than the creator updates the proposal', async () => {
+            try {
+                await updateMetadata(supporterKeypair, { description: "Hijacked." });
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('NotProposalCreator');
+            }
+        });
+
+        it('should let the creator cancel the proposal and block further support', async () => {
+            await cancelProposal(creatorKeypair);
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.status).to.deep.equal({ cancelled: {} });
+
+            try {
+                await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('ProposalNotActive');
+            }
+        });
+
+        it('should remove a cancelled proposal from the epoch leaderboard', async () => {
+            await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+            const [leaderboardPda] = getLeaderboardPda(program.programId, epochId);
+            let leaderboard = await program.account.epochLeaderboard.fetch(leaderboardPda);
+            expect(leaderboard.entries.slice(0, leaderboard.len).map((e: any) => e.proposal.toBase58())).to.include(proposalPda.toBase58());
+
+            await cancelProposal(creatorKeypair);
+
+            leaderboard = await program.account.epochLeaderboard.fetch(leaderboardPda);
+            expect(leaderboard.entries.slice(0, leaderboard.len).map((e: any) => e.proposal.toBase58())).to.not.include(proposalPda.toBase58());
+        });
+
+        it('should fail when someone else than the creator cancels the proposal', async () => {
+            try {
+                await cancelProposal(supporterKeypair);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('NotProposalCreator');
+            }
+        });
+
+        it('should let supporters reclaim immediately after a cancellation', async () => {
+            await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+            const [userSupportPda] = getSupportPda(program.programId, epochId, supporterKeypair.publicKey, proposalPda);
+            const support = await program.account.userProposalSupport.fetch(userSupportPda);
+
+            await cancelProposal(creatorKeypair);
+
+            // L'époque est toujours active et n'a pas été traitée
+            const proposalBalanceBefore = await ctx.provider.connection.getBalance(getProposalVaultPda(program.programId, proposalPda)[0]);
+            await program.methods.reclaimSupport()
+                .accounts({
+                    user: supporterKeypair.publicKey,
+                    tokenProposal: proposalPda,
+                    proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                    userProposalSupport: userSupportPda,
+                    epochManagement: activeEpochPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([supporterKeypair])
+                .rpc();
+
+            const proposalBalanceAfter = await ctx.provider.connection.getBalance(getProposalVaultPda(program.programId, proposalPda)[0]);
+            expect(proposalBalanceBefore - proposalBalanceAfter).to.equal(support.amount.toNumber());
+        });
+    });
+}
@@ -0,0 +1,276 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, getLeaderboardPda } from '../../setup/epochSetup';
+import { ensureTreasuryRolesInitialized, getTreasuryRolesPda } from '../../setup/treasurySetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    getSupportPda,
+    getUserEpochSupportPda,
+    getProposalVaultPda,
+    getModerationPda,
+    updateProposalStatusOnChain,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runModerationTests() {
+    describe('Instructions: flag_proposal / appeal_proposal_flag / unflag_proposal / remove_proposal', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let moderatorKeypair: Keypair;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+        let treasuryRolesPda: PublicKey;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+        let moderationPda: PublicKey;
+
+        async function flag(moderator: Keypair) {
+            await program.methods
+                .flagProposal({ impersonation: {} } as any)
+                .accounts({
+                    moderator: moderator.publicKey,
+                    treasuryRoles: treasuryRolesPda,
+                    epoch: activeEpochPda,
+                    tokenProposal: proposalPda,
+                    moderation: moderationPda,
+                    leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([moderator])
+                .rpc();
+        }
+
+        async function appeal() {
+            await program.methods
+                .appealProposalFlag()
+                .accounts({
+                    creator: creatorKeypair.publicKey,
+                    tokenProposal: proposalPda,
+                    moderation: moderationPda,
+                } as any)
+                .signers([creatorKeypair])
+                .rpc();
+        }
+
+        async function onLeaderboard(): Promise<boolean> {
+            const leaderboard = await program.account.epochLeaderboard.fetch(getLeaderboardPda(program.programId, epochId)[0]);
+            return leaderboard.entries.slice(0, leaderboard.len).some((e: any) => e.proposal.equals(proposalPda));
+        }
+
+        async function setUnsupportPenalty(bps: number) {
+            await program.methods
+                .updateUnsupportPenalty(bps)
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: ctx.adminKeypair.publicKey,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+            [treasuryRolesPda] = getTreasuryRolesPda(program.programId);
+
+            moderatorKeypair = Keypair.generate();
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [moderatorKeypair, creatorKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 5 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+
+            await ensureTreasuryRolesInitialized(ctx, [ctx.adminKeypair.publicKey], true);
+            await program.methods
+                .addTreasuryRole({ proposalModerator: {} } as any, moderatorKeypair.publicKey, null, null)
+                .accounts({ treasuryRoles: treasuryRolesPda, authority: ctx.adminKeypair.publicKey } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            const details: TokenProposalDetails = {
+                epochId,
+                name: "Moderated Token",
+                symbol: "MOD",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test proposal moderation.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+            [moderationPda] = getModerationPda(program.programId, proposalPda);
+            await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+        });
+
+        after(async () => {
+            await program.methods
+                .removeTreasuryRole({ proposalModerator: {} } as any, moderatorKeypair.publicKey)
+                .accounts({ treasuryRoles: treasuryRolesPda, authority: ctx.adminKeypair.publicKey } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        });
+
+        it('should reject a flag from a wallet without the moderator role', async () => {
+            try {
+                await flag(supporterKeypair);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('Unauthorized');
+            }
+        });
+
+        it('should hide a flagged proposal and freeze new support', async () => {
+            expect(await onLeaderboard()).to.be.true;
+            await flag(moderatorKeypair);
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.status).to.deep.equal({ flagged: {} });
+            const moderation = await program.account.proposalModeration.fetch(moderationPda);
+            expect(moderation.moderator.equals(moderatorKeypair.publicKey)).to.be.true;
+            expect(moderation.reason).to.deep.equal({ impersonation: {} });
+            expect(moderation.appealed).to.be.false;
+            expect(await onLeaderboard()).to.be.false;
+
+            try {
+                await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('ProposalNotActive');
+            }
+        });
+
+        it('should let supporters leave a flagged proposal without penalty', async () => {
+            await setUnsupportPenalty(1000);
+            try {
+                await flag(moderatorKeypair);
+                const [userSupportPda] = getSupportPda(program.programId, epochId, supporterKeypair.publicKey, proposalPda);
+                const userSupport = await program.account.userProposalSupport.fetch(userSupportPda);
+                const treasuryBefore = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+
+                await program.methods
+                    .unsupportProposal(userSupport.amount)
+                    .accounts({
+                        user: supporterKeypair.publicKey,
+                        epoch: activeEpochPda,
+                        proposal: proposalPda,
+                        proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                        userSupport: userSupportPda,
+                        userEpochSupport: getUserEpochSupportPda(program.programId, epochId, supporterKeypair.publicKey)[0],
+                        leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                        programConfig: ctx.programConfigAddress,
+                        treasury: ctx.treasuryAddress,
+                    } as any)
+                    .signers([supporterKeypair])
+                    .rpc();
+
+                expect(await ctx.provider.connection.getBalance(ctx.treasuryAddress!)).to.equal(treasuryBefore);
+                const proposal = await program.account.tokenProposal.fetch(proposalPda);
+                expect(proposal.solRaised.isZero()).to.be.true;
+                expect(await onLeaderboard()).to.be.false;
+            } finally {
+                await setUnsupportPenalty(0);
+            }
+        });
+
+        it('should record a single appeal and restore the proposal when unflagged', async () => {
+            await flag(moderatorKeypair);
+            await appeal();
+            const moderation = await program.account.proposalModeration.fetch(moderationPda);
+            expect(moderation.appealed).to.be.true;
+            expect(moderation.appealedAt.toNumber()).to.be.greaterThan(0);
+
+            try {
+                await appeal();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('AppealAlreadyFiled');
+            }
+
+            await program.methods
+                .unflagProposal()
+                .accounts({
+                    moderator: moderatorKeypair.publicKey,
+                    treasuryRoles: treasuryRolesPda,
+                    tokenProposal: proposalPda,
+                    moderation: moderationPda,
+                    flaggingModerator: moderatorKeypair.publicKey,
+                    leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                } as any)
+                .signers([moderatorKeypair])
+                .rpc();
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.status).to.deep.equal({ active: {} });
+            expect(await onLeaderboard()).to.be.true;
+            // Le signalement tranché est fermé ; un nouveau signalement reste possible
+            expect(await ctx.provider.connection.getAccountInfo(moderationPda)).to.be.null;
+            await flag(moderatorKeypair);
+        });
+
+        it('should let supporters reclaim immediately once the proposal is removed', async () => {
+            await flag(moderatorKeypair);
+            await program.methods
+                .removeProposal()
+                .accounts({
+                    moderator: moderatorKeypair.publicKey,
+                    treasuryRoles: treasuryRolesPda,
+                    tokenProposal: proposalPda,
+                    moderation: moderationPda,
+                    flaggingModerator: moderatorKeypair.publicKey,
+                } as any)
+                .signers([moderatorKeypair])
+                .rpc();
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.status).to.deep.equal({ removed: {} });
+            expect(await ctx.provider.connection.getAccountInfo(moderationPda)).to.be.null;
+
+            // L'époque est encore active : pas besoin d'attendre son traitement
+            const [userSupportPda] = getSupportPda(program.programId, epochId, supporterKeypair.publicKey, proposalPda);
+            await program.methods.reclaimSupport()
+                .accounts({
+                    user: supporterKeypair.publicKey,
+                    tokenProposal: proposalPda,
+                    proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                    userProposalSupport: userSupportPda,
+                    epochManagement: activeEpochPda,
+                    programConfig: ctx.programConfigAddress,
+                    treasury: ctx.treasuryAddress,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([supporterKeypair])
+                .rpc();
+
+            expect(await ctx.provider.connection.getAccountInfo(userSupportPda)).to.be.null;
+            const refunded = await program.account.tokenProposal.fetch(proposalPda);
+            expect(refunded.solRefunded.eq(refunded.solRaised)).to.be.true;
+        });
+
+        it('should refuse to finalize a flagged proposal until the moderation decides', async () => {
+            await flag(moderatorKeypair);
+            await closeEpochOnChain(ctx, epochId);
+
+            // Rejetée par le traitement, la proposition rendrait sa caution au seuil de levée
+            for (const status of [{ rejected: {} }, { validated: {} }]) {
+                try {
+                    await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, status);
+                    expect.fail('Transaction should have failed but succeeded.');
+                } catch (error) {
+                    expect((error as anchor.AnchorError).error.errorCode.code).to.equal('ProposalIsFlagged');
+                }
+            }
+        });
+    });
+}
@@ -0,0 +1,113 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, markEpochAsProcessedOnChain } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    updateProposalStatusOnChain,
+    getProposalVaultPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runPushRefundTests() {
+    describe('Instruction: push_refund', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+        let crankKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+        let userSupportPda: PublicKey;
+
+        async function pushRefund(user: PublicKey) {
+            await program.methods
+                .pushRefund()
+                .accounts({
+                    authority: crankKeypair.publicKey,
+                    programConfig: ctx.programConfigAddress,
+                    epochManagement: activeEpochPda,
+                    tokenProposal: proposalPda,
+                    proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                    userProposalSupport: userSupportPda,
+                    user,
+                    treasury: ctx.treasuryAddress,
+                } as any)
+                .signers([crankKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            crankKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair, crankKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 3 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            const details: TokenProposalDetails = {
+                epochId,
+                name: "Push Refund Token",
+                symbol: "PUSH",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test pushed refunds.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+            userSupportPda = await supportProposalOnChain(
+                ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL)
+            );
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { rejected: {} });
+        });
+
+        it('should let a third party push the refund to the supporter once the epoch is processed', async () => {
+            await markEpochAsProcessedOnChain(ctx, epochId);
+            const support = await program.account.userProposalSupport.fetch(userSupportPda);
+            const supportRent = await ctx.provider.connection.getBalance(userSupportPda);
+
+            const supporterBalanceBefore = await ctx.provider.connection.getBalance(supporterKeypair.publicKey);
+            await pushRefund(supporterKeypair.publicKey);
+            const supporterBalanceAfter = await ctx.provider.connection.getBalance(supporterKeypair.publicKey);
+
+            // Le supporter ne paie rien : il reçoit son support et la rente du compte fermé
+            expect(supporterBalanceAfter - supporterBalanceBefore).to.equal(support.amount.toNumber() + supportRent);
+            expect(await ctx.provider.connection.getAccountInfo(userSupportPda)).to.be.null;
+        });
+
+        it('should fail before the epoch is processed', async () => {
+            try {
+                await pushRefund(supporterKeypair.publicKey);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('EpochNotProcessedYet');
+            }
+        });
+
+        it('should fail when the refund is sent to another wallet than the supporter', async () => {
+            await markEpochAsProcessedOnChain(ctx, epochId);
+            try {
+                await pushRefund(crankKeypair.publicKey);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidAuthority');
+            }
+        });
+    });
+}
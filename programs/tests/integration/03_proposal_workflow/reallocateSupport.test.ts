@@ -0,0 +1,157 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, getLeaderboardPda } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    getSupportPda,
+    getProposalVaultPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runReallocateSupportTests() {
+    describe('Instruction: reallocate_support', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let fromProposalPda: PublicKey;
+        let toProposalPda: PublicKey;
+
+        async function createProposal(name: string, symbol: string): Promise<PublicKey> {
+            const details: TokenProposalDetails = {
+                epochId,
+                name,
+                symbol,
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test support reallocation.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            return createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+        }
+
+        async function reallocate(from: PublicKey, to: PublicKey, amount: number) {
+            await program.methods
+                .reallocateSupport(new anchor.BN(amount))
+                .accounts({
+                    user: supporterKeypair.publicKey,
+                    epoch: activeEpochPda,
+                    fromProposal: from,
+                    toProposal: to,
+                    fromVault: getProposalVaultPda(program.programId, from)[0],
+                    toVault: getProposalVaultPda(program.programId, to)[0],
+                    fromSupport: getSupportPda(program.programId, epochId, supporterKeypair.publicKey, from)[0],
+                    toSupport: getSupportPda(program.programId, epochId, supporterKeypair.publicKey, to)[0],
+                    programConfig: ctx.programConfigAddress,
+                    leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([supporterKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 5 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            fromProposalPda = await createProposal("Realloc From", "RFROM");
+            toProposalPda = await createProposal("Realloc To", "RTO");
+            await supportProposalOnChain(ctx, supporterKeypair, fromProposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+        });
+
+        it('should move part of the support without charging the fee again', async () => {
+            const moved = 300_000_000;
+            const treasuryBefore = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            const fromBefore = await program.account.tokenProposal.fetch(fromProposalPda);
+
+            await reallocate(fromProposalPda, toProposalPda, moved);
+
+            const treasuryAfter = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            expect(treasuryAfter).to.equal(treasuryBefore);
+
+            const fromAfter = await program.account.tokenProposal.fetch(fromProposalPda);
+            const toAfter = await program.account.tokenProposal.fetch(toProposalPda);
+            expect(fromAfter.solRaised.toNumber()).to.equal(fromBefore.solRaised.toNumber() - moved);
+            expect(fromAfter.totalContributions.toNumber()).to.equal(1);
+            expect(toAfter.solRaised.toNumber()).to.equal(moved);
+            expect(toAfter.totalContributions.toNumber()).to.equal(1);
+
+            const toSupport = await program.account.userProposalSupport.fetch(
+                getSupportPda(program.programId, epochId, supporterKeypair.publicKey, toProposalPda)[0]
+            );
+            expect(toSupport.amount.toNumber()).to.equal(moved);
+            expect(toSupport.proposal.equals(toProposalPda)).to.be.true;
+        });
+
+        it('should close the source support account when everything is moved', async () => {
+            const [fromSupportPda] = getSupportPda(program.programId, epochId, supporterKeypair.publicKey, fromProposalPda);
+            const fromSupport = await program.account.userProposalSupport.fetch(fromSupportPda);
+
+            await reallocate(fromProposalPda, toProposalPda, fromSupport.amount.toNumber());
+
+            expect(await ctx.provider.connection.getAccountInfo(fromSupportPda)).to.be.null;
+            const fromAfter = await program.account.tokenProposal.fetch(fromProposalPda);
+            const toAfter = await program.account.tokenProposal.fetch(toProposalPda);
+            expect(fromAfter.solRaised.isZero()).to.be.true;
+            expect(fromAfter.totalContributions.isZero()).to.be.true;
+            expect(toAfter.solRaised.eq(fromSupport.amount)).to.be.true;
+        });
+
+        it('should fail when moving more than the current support', async () => {
+            const fromSupport = await program.account.userProposalSupport.fetch(
+                getSupportPda(program.programId, epochId, supporterKeypair.publicKey, fromProposalPda)[0]
+            );
+            try {
+                await reallocate(fromProposalPda, toProposalPda, fromSupport.amount.toNumber() + 1);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('AmountExceedsSupport');
+            }
+        });
+
+        it('should fail when moving to a proposal of another epoch', async () => {
+            const otherEpochId = generateRandomBN();
+            const otherEpochPda = await ensureEpochIsActive(ctx, otherEpochId);
+            const details: TokenProposalDetails = {
+                epochId: otherEpochId,
+                name: "Realloc Other",
+                symbol: "ROTHER",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Proposal in another epoch.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            const otherProposalPda = await createProposalOnChain(ctx, creatorKeypair, details, otherEpochPda);
+            try {
+                await reallocate(fromProposalPda, otherProposalPda, 100_000_000);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                // Le PDA de destination dérivé côté client ne correspond pas aux seeds attendues,
+                // ou la contrainte d'époque échoue
+                const errorString = (error as Error).toString();
+                expect(errorString.includes("ProposalEpochMismatch") || errorString.includes("ConstraintSeeds")).to.be.true;
+            }
+        });
+    });
+}
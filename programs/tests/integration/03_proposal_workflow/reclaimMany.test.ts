@@ -0,0 +1,154 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, markEpochAsProcessedOnChain } from '../../setup/epochSetup';
+import { buildReclaimManyTransactions, getMaxReclaimManyPairs } from '../../../../front/lib/reclaimMany';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    updateProposalStatusOnChain,
+    getSupportPda,
+    getProposalVaultPda,
+    reclaimManyOnChain,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runReclaimManyTests() {
+    describe('Instruction: reclaim_many', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+
+        // Crée `count` propositions soutenues par le supporter puis rejetées, époque traitée
+        async function setupRejectedProposals(count: number, processEpoch: boolean = true): Promise<PublicKey[]> {
+            const proposals: PublicKey[] = [];
+            for (let i = 0; i < count; i++) {
+                const details: TokenProposalDetails = {
+                    epochId,
+                    name: `Batch Token ${i}`,
+                    symbol: `BATCH${i}`,
+                    totalSupply: new anchor.BN(1000000),
+                    creatorAllocationPercentage: 5,
+                    description: "Token used to test batch reclaims.",
+                    imageUrl: null,
+                    lockupPeriod: new anchor.BN(0),
+                };
+                const proposalPda = await createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+                await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL / 10));
+                proposals.push(proposalPda);
+            }
+            await closeEpochOnChain(ctx, epochId);
+            for (const proposalPda of proposals) {
+                await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { rejected: {} });
+            }
+            if (processEpoch) {
+                await markEpochAsProcessedOnChain(ctx, epochId);
+            }
+            return proposals;
+        }
+
+        async function reclaimManyRaw(triples: PublicKey[][]) {
+            await program.methods
+                .reclaimMany()
+                .accounts({
+                    user: supporterKeypair.publicKey,
+                    epochManagement: activeEpochPda,
+                    programConfig: ctx.programConfigAddress,
+                    treasury: ctx.treasuryAddress,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .remainingAccounts(triples.flat().map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+                .signers([supporterKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 10 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+        });
+
+        it('should reclaim several rejected proposals and close every support account in one transaction', async () => {
+            const proposals = await setupRejectedProposals(3);
+            const supportPdas = proposals.map((p) => getSupportPda(program.programId, epochId, supporterKeypair.publicKey, p)[0]);
+
+            const signatures = await reclaimManyOnChain(ctx, supporterKeypair, epochId, proposals);
+            expect(signatures.length).to.equal(1);
+
+            for (const supportPda of supportPdas) {
+                expect(await ctx.provider.connection.getAccountInfo(supportPda)).to.be.null;
+            }
+        });
+
+        it('should pack the triples into as few transactions as allowed', async () => {
+            const proposals = await setupRejectedProposals(12);
+            const transactions = await buildReclaimManyTransactions(program, supporterKeypair.publicKey, epochId, proposals);
+            // 10 triplets maximum par transaction côté programme, lus dans l'IDL
+            expect(getMaxReclaimManyPairs(program)).to.equal(10);
+            expect(transactions.length).to.be.greaterThanOrEqual(2);
+            const triplesPerTx = transactions.map((tx) => (tx.instructions[0].keys.length - 5) / 3);
+            expect(triplesPerTx.reduce((a, b) => a + b, 0)).to.equal(12);
+            triplesPerTx.forEach((n) => expect(n).to.be.at.most(10));
+
+            await reclaimManyOnChain(ctx, supporterKeypair, epochId, proposals);
+            for (const proposalPda of proposals) {
+                const [supportPda] = getSupportPda(program.programId, epochId, supporterKeypair.publicKey, proposalPda);
+                expect(await ctx.provider.connection.getAccountInfo(supportPda)).to.be.null;
+            }
+        });
+
+        it('should fail before the epoch is processed', async () => {
+            const proposals = await setupRejectedProposals(2, false);
+            try {
+                await reclaimManyRaw(proposals.map((p) => [
+                    p,
+                    getProposalVaultPda(program.programId, p)[0],
+                    getSupportPda(program.programId, epochId, supporterKeypair.publicKey, p)[0],
+                ]));
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('EpochNotProcessedYet');
+            }
+        });
+
+        it('should fail when the same support account is passed twice', async () => {
+            const [proposalPda] = await setupRejectedProposals(1);
+            const [supportPda] = getSupportPda(program.programId, epochId, supporterKeypair.publicKey, proposalPda);
+            const [vaultPda] = getProposalVaultPda(program.programId, proposalPda);
+            try {
+                await reclaimManyRaw([[proposalPda, vaultPda, supportPda], [proposalPda, vaultPda, supportPda]]);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('DuplicateSupportAccount');
+            }
+        });
+
+        it('should fail with an incomplete triple of remaining accounts', async () => {
+            const [proposalPda] = await setupRejectedProposals(1);
+            try {
+                await reclaimManyRaw([[proposalPda, getProposalVaultPda(program.programId, proposalPda)[0]]]);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidReclaimManyAccounts');
+            }
+        });
+    });
+}
@@ -5,10 +5,11 @@ import chai from 'chai';
 import { expect } from 'chai';
 import { Programs } from '../../../target/types/programs';
 import { TestContext, getInitializedContext, generateRandomBN, shortenAddress } from '../../setup';
-import { ensureEpochIsActive, closeEpochOnChain, markEpochAsProcessedOnChain } from '../../setup/epochSetup';
+import { ensureEpochIsActive, closeEpochOnChain, markEpochAsProcessedOnChain, getLeaderboardPda } from '../../setup/epochSetup';
 import { 
     createProposalOnChain, 
     supportProposalOnChain, 
+    getProposalVaultPda,
     TokenProposalDetails, 
     getSupportPda
 } from '../../setup/proposalSetup';
@@ -80,7 +81,8 @@ export function runReclaimSupportTests() {
                         epochManagement: activeEpochPda,
                         authority: adminKeypair.publicKey,
                         programConfig: ctx.programConfigAddress,
-                    })
+                        leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                    } as any)
                     .signers([adminKeypair])
                     .rpc();
             }
@@ -108,6 +110,7 @@ export function runReclaimSupportTests() {
                     .accounts({
                         user: supporterKeypair.publicKey,
                         tokenProposal: proposalPda,
+                        proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
                         userProposalSupport: userSupportPda,
                         epochManagement: activeEpochPda,
                         systemProgram: SystemProgram.programId,
@@ -144,6 +147,7 @@ export function runReclaimSupportTests() {
                         .accounts({
                             user: supporterKeypair.publicKey,
                             tokenProposal: proposalPda,
+                            proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
                             userProposalSupport: userSupportPda,
                             epochManagement: activeEpochPda,
                             systemProgram: SystemProgram.programId,
@@ -172,7 +176,7 @@ export function runReclaimSupportTests() {
                 await closeEpochOnChain(ctx, epochId, adminKeypair);
                 // Ne pas appeler markEpochAsProcessedOnChain
                  await ctx.program.methods.updateProposalStatus({ rejected: {} } as any)
-                    .accounts({ proposal: proposalPda, epochManagement: activeEpochPda, authority: adminKeypair.publicKey, programConfig: ctx.programConfigAddress })
+                    .accounts({ proposal: proposalPda, epochManagement: activeEpochPda, authority: adminKeypair.publicKey, programConfig: ctx.programConfigAddress, leaderboard: getLeaderboardPda(program.programId, epochId)[0] } as any)
                     .signers([adminKeypair]).rpc();
 
                 let errorCaught = false;
@@ -181,6 +185,7 @@ export function runReclaimSupportTests() {
                         .accounts({
                             user: supporterKeypair.publicKey,
                             tokenProposal: proposalPda,
+                            proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
                             userProposalSupport: userSupportPda,
                             epochManagement: activeEpochPda,
                             systemProgram: SystemProgram.programId,
@@ -207,6 +212,7 @@ export function runReclaimSupportTests() {
                         .accounts({
                             user: otherSupporter.publicKey,
                             tokenProposal: proposalPda,
+                            proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
                             userProposalSupport: userSupportPda, // Ce PDA appartient à supporterKeypair, pas à otherSupporter
                             epochManagement: activeEpochPda,
                             systemProgram: SystemProgram.programId,
@@ -230,6 +236,7 @@ export function runReclaimSupportTests() {
                     .accounts({
                         user: supporterKeypair.publicKey,
                         tokenProposal: proposalPda,
+                        proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
                         userProposalSupport: userSupportPda,
                         epochManagement: activeEpochPda,
                         systemProgram: SystemProgram.programId,
@@ -244,6 +251,7 @@ export function runReclaimSupportTests() {
                         .accounts({
                             user: supporterKeypair.publicKey,
                             tokenProposal: proposalPda,
+                            proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
                             userProposalSupport: userSupportPda,
                             epochManagement: activeEpochPda,
                             systemProgram: SystemProgram.programId,
@@ -0,0 +1,128 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    getUserEpochSupportPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+// Frais de support de 0.5% : un support de `amount` engage `amount * 995 / 1000` lamports nets
+const net = (amount: number) => amount - Math.floor(amount * 5 / 1000);
+
+export function runSupportCapsTests() {
+    describe('Support caps (update_support_caps / support_proposal)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let adminKeypair: Keypair;
+        let creatorKeypair: Keypair;
+        let whaleKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+
+        async function setSupportCaps(signer: Keypair, perProposal: number, perEpoch: number, perProposalRaise: number) {
+            await program.methods
+                .updateSupportCaps(new anchor.BN(perProposal), new anchor.BN(perEpoch), new anchor.BN(perProposalRaise))
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: signer.publicKey,
+                } as any)
+                .signers([signer])
+                .rpc();
+        }
+
+        async function createProposal(symbol: string): Promise<PublicKey> {
+            const details: TokenProposalDetails = {
+                epochId,
+                name: `Capped Token ${symbol}`,
+                symbol,
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test support caps.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            return createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+        }
+
+        async function expectSupportError(proposalPda: PublicKey, amount: number, expectedCode: string) {
+            try {
+                await supportProposalOnChain(ctx, whaleKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(amount));
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal(expectedCode);
+            }
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+            adminKeypair = ctx.adminKeypair;
+
+            creatorKeypair = Keypair.generate();
+            whaleKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, whaleKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 10 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+        });
+
+        after(async () => {
+            await setSupportCaps(adminKeypair, 0, 0, 0);
+        });
+
+        it('should fail to update the support caps when the signer is not the admin', async () => {
+            try {
+                await setSupportCaps(whaleKeypair, 1, 1, 1);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('Unauthorized');
+            }
+        });
+
+        it('should cap the net support of a wallet on a proposal', async () => {
+            await setSupportCaps(adminKeypair, net(LAMPORTS_PER_SOL), 0, 0);
+            const proposalPda = await createProposal("CAPA");
+
+            await supportProposalOnChain(ctx, whaleKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+            await expectSupportError(proposalPda, LAMPORTS_PER_SOL / 10, 'WalletProposalSupportCapExceeded');
+        });
+
+        it('should cap the net support of a wallet across an epoch', async () => {
+            await setSupportCaps(adminKeypair, 0, net(LAMPORTS_PER_SOL) + net(LAMPORTS_PER_SOL / 2), 0);
+            const firstProposalPda = await createProposal("CAPB");
+            const secondProposalPda = await createProposal("CAPC");
+
+            await supportProposalOnChain(ctx, whaleKeypair, firstProposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+            await supportProposalOnChain(ctx, whaleKeypair, secondProposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL / 2));
+
+            const [userEpochSupportPda] = getUserEpochSupportPda(program.programId, epochId, whaleKeypair.publicKey);
+            const userEpochSupport = await program.account.userEpochSupport.fetch(userEpochSupportPda);
+            expect(userEpochSupport.amount.toNumber()).to.equal(net(LAMPORTS_PER_SOL) + net(LAMPORTS_PER_SOL / 2));
+
+            await expectSupportError(secondProposalPda, LAMPORTS_PER_SOL / 10, 'WalletEpochSupportCapExceeded');
+        });
+
+        it('should cap the SOL raised by a proposal', async () => {
+            await setSupportCaps(adminKeypair, 0, 0, net(2 * LAMPORTS_PER_SOL));
+            const proposalPda = await createProposal("CAPD");
+
+            await supportProposalOnChain(ctx, whaleKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(2 * LAMPORTS_PER_SOL));
+            await expectSupportError(proposalPda, LAMPORTS_PER_SOL / 10, 'ProposalSolRaisedCapExceeded');
+
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.solRaised.toNumber()).to.equal(net(2 * LAMPORTS_PER_SOL));
+        });
+    });
+}
@@ -11,6 +11,7 @@ import {
     TokenProposalDetails,
     supportProposalOnChain,
     getSupportPda,
+    getProposalVaultPda,
     updateProposalStatusOnChain,
 } from '../../setup/proposalSetup';
 
@@ -119,6 +120,13 @@ export function runSupportProposalTests() {
             console.log(`  [SupportProposalTests] TokenProposal.totalContributions incremented to ${updatedProposalAccountState.totalContributions.toString()} (expected: ${expectedTotalContributionsAfterSupport.toString()}).`);
         });
 
+        it('should hold the net amount in the proposal vault (reserve + solRaised - solRefunded)', async () => {
+            const vaultLamports = await ctx.provider.connection.getBalance(getProposalVaultPda(program.programId, proposalPda)[0]);
+            const vaultReserve = await ctx.provider.connection.getMinimumBalanceForRentExemption(0);
+            const outstanding = updatedProposalAccountState.solRaised.sub(updatedProposalAccountState.solRefunded);
+            expect(vaultLamports).to.equal(vaultReserve + outstanding.toNumber());
+        });
+
         it("should decrease the supporter's SOL balance by at least the gross support amount", async () => {
             const difference = initialSupporterBalance - finalSupporterBalance;
             const grossAmountNum = supportAmountGross.toNumber();
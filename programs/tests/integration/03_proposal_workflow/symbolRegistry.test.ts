@@ -0,0 +1,171 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, getLeaderboardPda } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    updateProposalStatusOnChain,
+    getEpochSymbolPda,
+    getLaunchedSymbolPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runSymbolRegistryTests() {
+    describe('Symbol registry (create_proposal / release_proposal_symbol / register_launched_symbol)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let firstCreator: Keypair;
+        let secondCreator: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+
+        const details = (symbol: string, name: string = "Registry Token"): TokenProposalDetails => ({
+            epochId,
+            name,
+            symbol,
+            totalSupply: new anchor.BN(1000000),
+            creatorAllocationPercentage: 5,
+            description: "Token used to test the symbol registry.",
+            imageUrl: null,
+            lockupPeriod: new anchor.BN(0),
+        });
+
+        // Symbole unique par test, pour ne pas dépendre du registre global des autres tests
+        const randomSymbol = () => "S" + Math.floor(Math.random() * 1_000_000).toString();
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            firstCreator = Keypair.generate();
+            secondCreator = Keypair.generate();
+            for (const kp of [firstCreator, secondCreator]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 2 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+        });
+
+        it('should reserve the normalized symbol for the epoch', async () => {
+            const symbol = randomSymbol().toLowerCase();
+            const proposalPda = await createProposalOnChain(ctx, firstCreator, details(symbol), activeEpochPda);
+
+            const [epochSymbolPda] = getEpochSymbolPda(program.programId, epochId, symbol);
+            const registry = await program.account.symbolRegistry.fetch(epochSymbolPda);
+            expect(registry.symbol).to.equal(symbol.toUpperCase());
+            expect(registry.proposal.equals(proposalPda)).to.be.true;
+            expect(registry.epochId.eq(epochId)).to.be.true;
+        });
+
+        it('should fail when another wallet proposes the same symbol with a different case in the same epoch', async () => {
+            const symbol = randomSymbol();
+            await createProposalOnChain(ctx, firstCreator, details(symbol), activeEpochPda);
+            try {
+                await createProposalOnChain(ctx, secondCreator, details(symbol.toLowerCase()), activeEpochPda);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('SymbolAlreadyTaken');
+            }
+        });
+
+        it('should allow the same symbol in another epoch', async () => {
+            const symbol = randomSymbol();
+            await createProposalOnChain(ctx, firstCreator, details(symbol), activeEpochPda);
+
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            await createProposalOnChain(ctx, secondCreator, details(symbol), activeEpochPda);
+        });
+
+        it('should release the symbol when the proposal is cancelled', async () => {
+            const symbol = randomSymbol();
+            const proposalPda = await createProposalOnChain(ctx, firstCreator, details(symbol), activeEpochPda);
+            const [epochSymbolPda] = getEpochSymbolPda(program.programId, epochId, symbol);
+
+            await program.methods
+                .cancelProposal()
+                .accounts({
+                    creator: firstCreator.publicKey,
+                    epoch: activeEpochPda,
+                    tokenProposal: proposalPda,
+                    epochSymbol: epochSymbolPda,
+                    leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                } as any)
+                .signers([firstCreator])
+                .rpc();
+
+            expect(await ctx.provider.connection.getAccountInfo(epochSymbolPda)).to.be.null;
+            await createProposalOnChain(ctx, secondCreator, details(symbol), activeEpochPda);
+        });
+
+        it('should let anyone release the symbol of a rejected proposal', async () => {
+            const symbol = randomSymbol();
+            const proposalPda = await createProposalOnChain(ctx, firstCreator, details(symbol), activeEpochPda);
+            const [epochSymbolPda] = getEpochSymbolPda(program.programId, epochId, symbol);
+
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { rejected: {} });
+
+            const creatorBalanceBefore = await ctx.provider.connection.getBalance(firstCreator.publicKey);
+            await program.methods
+                .releaseProposalSymbol()
+                .accounts({
+                    authority: secondCreator.publicKey,
+                    programConfig: ctx.programConfigAddress,
+                    epochManagement: activeEpochPda,
+                    tokenProposal: proposalPda,
+                    epochSymbol: epochSymbolPda,
+                    creator: firstCreator.publicKey,
+                    treasury: ctx.treasuryAddress,
+                } as any)
+                .signers([secondCreator])
+                .rpc();
+
+            expect(await ctx.provider.connection.getAccountInfo(epochSymbolPda)).to.be.null;
+            const creatorBalanceAfter = await ctx.provider.connection.getBalance(firstCreator.publicKey);
+            expect(creatorBalanceAfter).to.be.greaterThan(creatorBalanceBefore);
+        });
+
+        it('should block a launched symbol in every later epoch', async () => {
+            const symbol = randomSymbol();
+            const proposalPda = await createProposalOnChain(ctx, firstCreator, details(symbol), activeEpochPda);
+
+            await closeEpochOnChain(ctx, epochId);
+            await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { validated: {} });
+
+            const [launchedSymbolPda] = getLaunchedSymbolPda(program.programId, symbol);
+            await program.methods
+                .registerLaunchedSymbol()
+                .accounts({
+                    authority: ctx.adminKeypair.publicKey,
+                    tokenProposal: proposalPda,
+                    epochSymbol: getEpochSymbolPda(program.programId, epochId, symbol)[0],
+                    launchedSymbol: launchedSymbolPda,
+                    creator: firstCreator.publicKey,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+
+            const launched = await program.account.symbolRegistry.fetch(launchedSymbolPda);
+            expect(launched.proposal.equals(proposalPda)).to.be.true;
+
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            try {
+                await createProposalOnChain(ctx, secondCreator, details(symbol.toLowerCase()), activeEpochPda);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('SymbolAlreadyLaunched');
+            }
+        });
+    });
+}
@@ -0,0 +1,172 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, getLeaderboardPda } from '../../setup/epochSetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    getSupportPda,
+    getUserEpochSupportPda,
+    getProposalVaultPda,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runUnsupportProposalTests() {
+    describe('Instruction: unsupport_proposal', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let creatorKeypair: Keypair;
+        let supporterKeypair: Keypair;
+
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+        let userSupportPda: PublicKey;
+
+        async function setUnsupportPenalty(bps: number) {
+            await program.methods
+                .updateUnsupportPenalty(bps)
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: ctx.adminKeypair.publicKey,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        }
+
+        async function unsupport(amount: number) {
+            await program.methods
+                .unsupportProposal(new anchor.BN(amount))
+                .accounts({
+                    user: supporterKeypair.publicKey,
+                    epoch: activeEpochPda,
+                    proposal: proposalPda,
+                    proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                    userSupport: userSupportPda,
+                    userEpochSupport: getUserEpochSupportPda(program.programId, epochId, supporterKeypair.publicKey)[0],
+                    leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                    programConfig: ctx.programConfigAddress,
+                    treasury: ctx.treasuryAddress,
+                } as any)
+                .signers([supporterKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+
+            creatorKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [creatorKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 5 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+        });
+
+        beforeEach(async () => {
+            epochId = generateRandomBN();
+            activeEpochPda = await ensureEpochIsActive(ctx, epochId);
+            const details: TokenProposalDetails = {
+                epochId,
+                name: "Unsupport Token",
+                symbol: "UNS",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token used to test support withdrawals.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await createProposalOnChain(ctx, creatorKeypair, details, activeEpochPda);
+            userSupportPda = await supportProposalOnChain(
+                ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, new anchor.BN(LAMPORTS_PER_SOL)
+            );
+        });
+
+        after(async () => {
+            await setUnsupportPenalty(0);
+        });
+
+        it('should return part of the support and keep the supporter counted', async () => {
+            const supportBefore = await program.account.userProposalSupport.fetch(userSupportPda);
+            const proposalBefore = await program.account.tokenProposal.fetch(proposalPda);
+            const withdrawn = 200_000_000;
+
+            const userBalanceBefore = await ctx.provider.connection.getBalance(supporterKeypair.publicKey);
+            await unsupport(withdrawn);
+            const userBalanceAfter = await ctx.provider.connection.getBalance(supporterKeypair.publicKey);
+
+            const supportAfter = await program.account.userProposalSupport.fetch(userSupportPda);
+            const proposalAfter = await program.account.tokenProposal.fetch(proposalPda);
+            expect(supportAfter.amount.toNumber()).to.equal(supportBefore.amount.toNumber() - withdrawn);
+            expect(proposalAfter.solRaised.toNumber()).to.equal(proposalBefore.solRaised.toNumber() - withdrawn);
+            expect(proposalAfter.totalContributions.toNumber()).to.equal(proposalBefore.totalContributions.toNumber());
+            // Le supporter paie les frais de transaction
+            expect(userBalanceAfter).to.be.greaterThan(userBalanceBefore + withdrawn - 10_000);
+        });
+
+        it('should close the support account and decrement contributors on full exit', async () => {
+            const support = await program.account.userProposalSupport.fetch(userSupportPda);
+            const proposalBefore = await program.account.tokenProposal.fetch(proposalPda);
+
+            await unsupport(support.amount.toNumber());
+
+            expect(await ctx.provider.connection.getAccountInfo(userSupportPda)).to.be.null;
+            const proposalAfter = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposalAfter.solRaised.toNumber()).to.equal(proposalBefore.solRaised.toNumber() - support.amount.toNumber());
+            expect(proposalAfter.totalContributions.toNumber()).to.equal(proposalBefore.totalContributions.toNumber() - 1);
+
+            // Plus rien d'engagé dans l'époque : le compte de suivi est fermé aussi
+            expect(await ctx.provider.connection.getAccountInfo(
+                getUserEpochSupportPda(program.programId, epochId, supporterKeypair.publicKey)[0]
+            )).to.be.null;
+        });
+
+        it('should route the exit penalty to the treasury', async () => {
+            await setUnsupportPenalty(1000); // 10%
+            const withdrawn = 100_000_000;
+            const penalty = withdrawn / 10;
+
+            const treasuryBefore = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            const treasuryLamportsBefore = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            const vaultLamportsBefore = await ctx.provider.connection.getBalance(getProposalVaultPda(program.programId, proposalPda)[0]);
+
+            await unsupport(withdrawn);
+
+            const treasuryAfter = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            const treasuryLamportsAfter = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            const vaultLamportsAfter = await ctx.provider.connection.getBalance(getProposalVaultPda(program.programId, proposalPda)[0]);
+
+            expect(vaultLamportsBefore - vaultLamportsAfter).to.equal(withdrawn);
+            expect(treasuryLamportsAfter - treasuryLamportsBefore).to.equal(penalty);
+            const subAccountsTotal = (t: any) =>
+                t.marketing.solBalance.toNumber() + t.team.solBalance.toNumber() + t.operations.solBalance.toNumber()
+                + t.investments.solBalance.toNumber() + t.crank.solBalance.toNumber();
+            expect(subAccountsTotal(treasuryAfter) - subAccountsTotal(treasuryBefore)).to.equal(penalty);
+
+            await setUnsupportPenalty(0);
+        });
+
+        it('should fail when withdrawing more than the current support', async () => {
+            const support = await program.account.userProposalSupport.fetch(userSupportPda);
+            try {
+                await unsupport(support.amount.toNumber() + 1);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('AmountExceedsSupport');
+            }
+        });
+
+        it('should reject a penalty above the maximum', async () => {
+            try {
+                await setUnsupportPenalty(5001);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidUnsupportPenalty');
+            }
+        });
+    });
+}
@@ -5,7 +5,7 @@ import chai from 'chai';
 import { expect } from 'chai';
 import { Programs } from '../../../target/types/programs';
 import { TestContext, getInitializedContext, generateRandomBN, shortenAddress } from '../../setup';
-import { ensureEpochIsActive, closeEpochOnChain } from '../../setup/epochSetup';
+import { ensureEpochIsActive, closeEpochOnChain, getLeaderboardPda } from '../../setup/epochSetup';
 import { createProposalOnChain, TokenProposalDetails, updateProposalStatusOnChain } from '../../setup/proposalSetup';
 
 export function runUpdateProposalStatusTests() {
@@ -103,6 +103,16 @@ export function runUpdateProposalStatusTests() {
 
         // Tests pour les cas d'erreur
 
+        it('should fail to set the Cancelled status reserved to the creator', async () => {
+            await closeEpochOnChain(ctx, epochId, adminKeypair);
+            try {
+                await updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { cancelled: {} });
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidProposalStatusUpdate');
+            }
+        });
+
         it('should fail to update status if epoch is still active', async () => {
             const newStatus = { validated: {} };
             let errorCaught = false;
@@ -131,7 +141,8 @@ export function runUpdateProposalStatusTests() {
                         epochManagement: activeEpochPda,
                         authority: nonAdminKeypair.publicKey,
                         programConfig: ctx.programConfigAddress,
-                    })
+                        leaderboard: getLeaderboardPda(program.programId, epochId)[0],
+                    } as any)
                     .signers([nonAdminKeypair])
                     .rpc();
             } catch (error) {
@@ -132,7 +132,9 @@ export function runProposalSupportFeeTests() {
             const supportAccount = await program.account.userProposalSupport.fetch(userSupportPda);
             const expectedNetAmount = supportAmountLamports.sub(expectedSupportFee);
             expect(supportAccount.amount.eq(expectedNetAmount)).to.be.true;
-            // TODO: Ajouter une vérification si un champ `feePaid` est ajouté à `UserProposalSupport`
+            // Sans parrain, la totalité des frais est suivie sur le support
+            expect(supportAccount.feesPaid.eq(expectedSupportFee)).to.be.true;
+            expect(supportAccount.version).to.equal(1);
 
             // 5. Vérifier la distribution des frais aux sous-comptes de la trésorerie
             const treasuryAccountAfter = await program.account.treasury.fetch(ctx.treasuryAddress!);
@@ -0,0 +1,190 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import * as epochSetup from '../../setup/epochSetup';
+import * as proposalSetup from '../../setup/proposalSetup';
+import {
+    SUPPORT_FEE_PERCENTAGE_NUMERATOR,
+    SUPPORT_FEE_PERCENTAGE_DENOMINATOR,
+} from '../../utils_for_tests/constants';
+
+export function runReferralRewardsTests() {
+    describe('Instructions: register_referrer / claim_referral_rewards (Referral rewards)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let proposerKeypair: Keypair;
+        let supporterKeypair: Keypair;
+        let referrerKeypair: Keypair;
+        let referralPda: PublicKey;
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+        const supportAmountLamports = new anchor.BN(LAMPORTS_PER_SOL);
+        const expectedFee = supportAmountLamports
+            .mul(new anchor.BN(SUPPORT_FEE_PERCENTAGE_NUMERATOR))
+            .div(new anchor.BN(SUPPORT_FEE_PERCENTAGE_DENOMINATOR));
+
+        async function setReferralFee(bps: number) {
+            await program.methods
+                .updateReferralFee(bps)
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: ctx.adminKeypair.publicKey,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        }
+
+        async function registerReferrer(referrer: Keypair) {
+            await program.methods
+                .registerReferrer()
+                .accounts({
+                    referrer: referrer.publicKey,
+                    referralAccount: proposalSetup.getReferralPda(program.programId, referrer.publicKey)[0],
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([referrer])
+                .rpc();
+        }
+
+        async function claim() {
+            await program.methods
+                .claimReferralRewards()
+                .accounts({
+                    referrer: referrerKeypair.publicKey,
+                    referralAccount: referralPda,
+                } as any)
+                .signers([referrerKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+        });
+
+        beforeEach(async () => {
+            proposerKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            referrerKeypair = Keypair.generate();
+            for (const kp of [proposerKeypair, supporterKeypair, referrerKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 3 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+            await registerReferrer(referrerKeypair);
+            [referralPda] = proposalSetup.getReferralPda(program.programId, referrerKeypair.publicKey);
+
+            epochId = generateRandomBN();
+            activeEpochPda = await epochSetup.ensureEpochIsActive(ctx, epochId);
+            const proposalDetails: proposalSetup.TokenProposalDetails = {
+                epochId,
+                name: "Referral Token",
+                symbol: "REFT",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "A token to test referral rewards.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await proposalSetup.createProposalOnChain(ctx, proposerKeypair, proposalDetails, activeEpochPda);
+            await setReferralFee(2000); // 20% des frais
+        });
+
+        after(async () => {
+            await setReferralFee(0);
+        });
+
+        it('should open an empty referral account for the referrer', async () => {
+            const referral = await program.account.referralAccount.fetch(referralPda);
+            expect(referral.referrer.equals(referrerKeypair.publicKey)).to.be.true;
+            expect(referral.pendingRewards.isZero()).to.be.true;
+            expect(referral.totalEarned.isZero()).to.be.true;
+        });
+
+        it('should send the referral share of the fee to the referrer and the rest to the treasury', async () => {
+            const expectedShare = expectedFee.muln(2000).divn(10000);
+            const treasuryBefore = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            const referralLamportsBefore = await ctx.provider.connection.getBalance(referralPda);
+
+            const userSupportPda = await proposalSetup.supportProposalOnChain(
+                ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, supportAmountLamports, referrerKeypair.publicKey
+            );
+
+            const treasuryAfter = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            const referralLamportsAfter = await ctx.provider.connection.getBalance(referralPda);
+            expect(referralLamportsAfter - referralLamportsBefore).to.equal(expectedShare.toNumber());
+            expect(treasuryAfter - treasuryBefore).to.equal(expectedFee.sub(expectedShare).toNumber());
+
+            const referral = await program.account.referralAccount.fetch(referralPda);
+            expect(referral.pendingRewards.eq(expectedShare)).to.be.true;
+            expect(referral.totalEarned.eq(expectedShare)).to.be.true;
+            expect(referral.referredSupports.toNumber()).to.equal(1);
+
+            // Seule la part de la trésorerie reste remboursable au reclaim
+            const support = await program.account.userProposalSupport.fetch(userSupportPda);
+            expect(support.feesPaid.eq(expectedFee.sub(expectedShare))).to.be.true;
+        });
+
+        it('should let the referrer claim the pending rewards', async () => {
+            await proposalSetup.supportProposalOnChain(
+                ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, supportAmountLamports, referrerKeypair.publicKey
+            );
+            const pending = (await program.account.referralAccount.fetch(referralPda)).pendingRewards;
+            const rentExempt = await ctx.provider.connection.getBalance(referralPda) - pending.toNumber();
+            const referrerBefore = await ctx.provider.connection.getBalance(referrerKeypair.publicKey);
+
+            await claim();
+
+            // Le parrain paie aussi les frais de transaction
+            const referrerAfter = await ctx.provider.connection.getBalance(referrerKeypair.publicKey);
+            expect(referrerAfter - referrerBefore).to.be.within(pending.toNumber() - 10_000, pending.toNumber());
+            expect(await ctx.provider.connection.getBalance(referralPda)).to.equal(rentExempt);
+            const referral = await program.account.referralAccount.fetch(referralPda);
+            expect(referral.pendingRewards.isZero()).to.be.true;
+            expect(referral.totalEarned.eq(pending)).to.be.true;
+
+            try {
+                await claim();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('NothingToClaim');
+            }
+        });
+
+        it('should fail when the supporter refers themselves', async () => {
+            await registerReferrer(supporterKeypair);
+            try {
+                await proposalSetup.supportProposalOnChain(
+                    ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, supportAmountLamports, supporterKeypair.publicKey
+                );
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('SelfReferral');
+            }
+        });
+
+        it('should fail when the referrer is the creator of the proposal', async () => {
+            await registerReferrer(proposerKeypair);
+            try {
+                await proposalSetup.supportProposalOnChain(
+                    ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, supportAmountLamports, proposerKeypair.publicKey
+                );
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('ReferrerIsCreator');
+            }
+        });
+
+        it('should reject a referral fee above the maximum', async () => {
+            try {
+                await setReferralFee(5001);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidReferralFee');
+            }
+        });
+    });
+}
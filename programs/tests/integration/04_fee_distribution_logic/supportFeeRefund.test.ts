@@ -0,0 +1,138 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import * as epochSetup from '../../setup/epochSetup';
+import * as proposalSetup from '../../setup/proposalSetup';
+import {
+    SUPPORT_FEE_PERCENTAGE_NUMERATOR,
+    SUPPORT_FEE_PERCENTAGE_DENOMINATOR,
+} from '../../utils_for_tests/constants';
+
+export function runSupportFeeRefundTests() {
+    describe('Instruction: reclaim_support (Support fee refund)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let proposerKeypair: Keypair;
+        let supporterKeypair: Keypair;
+        let epochId: anchor.BN;
+        let activeEpochPda: PublicKey;
+        let proposalPda: PublicKey;
+        let userSupportPda: PublicKey;
+        const supportAmountLamports = new anchor.BN(LAMPORTS_PER_SOL);
+        const expectedFee = supportAmountLamports
+            .mul(new anchor.BN(SUPPORT_FEE_PERCENTAGE_NUMERATOR))
+            .div(new anchor.BN(SUPPORT_FEE_PERCENTAGE_DENOMINATOR));
+
+        async function setSupportFeeRefund(bps: number) {
+            await program.methods
+                .updateSupportFeeRefund(bps)
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: ctx.adminKeypair.publicKey,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+        }
+
+        async function reclaim() {
+            await program.methods.reclaimSupport()
+                .accounts({
+                    user: supporterKeypair.publicKey,
+                    tokenProposal: proposalPda,
+                    proposalVault: proposalSetup.getProposalVaultPda(program.programId, proposalPda)[0],
+                    userProposalSupport: userSupportPda,
+                    epochManagement: activeEpochPda,
+                    programConfig: ctx.programConfigAddress,
+                    treasury: ctx.treasuryAddress,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([supporterKeypair])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+        });
+
+        beforeEach(async () => {
+            proposerKeypair = Keypair.generate();
+            supporterKeypair = Keypair.generate();
+            for (const kp of [proposerKeypair, supporterKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 3 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+
+            epochId = generateRandomBN();
+            activeEpochPda = await epochSetup.ensureEpochIsActive(ctx, epochId);
+            const proposalDetails: proposalSetup.TokenProposalDetails = {
+                epochId,
+                name: "Fee Refund Token",
+                symbol: "FRT",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "A token to test the support fee refund.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            proposalPda = await proposalSetup.createProposalOnChain(ctx, proposerKeypair, proposalDetails, activeEpochPda);
+            userSupportPda = await proposalSetup.supportProposalOnChain(
+                ctx, supporterKeypair, proposalPda, epochId, activeEpochPda, supportAmountLamports
+            );
+
+            await epochSetup.closeEpochOnChain(ctx, epochId);
+            await proposalSetup.updateProposalStatusOnChain(ctx, proposalPda, activeEpochPda, { rejected: {} });
+            await epochSetup.markEpochAsProcessedOnChain(ctx, epochId);
+        });
+
+        after(async () => {
+            await setSupportFeeRefund(0);
+        });
+
+        it('should track the gross fee paid on the support account', async () => {
+            const support = await program.account.userProposalSupport.fetch(userSupportPda);
+            expect(support.feesPaid.eq(expectedFee)).to.be.true;
+        });
+
+        it('should not refund any fee by default', async () => {
+            await setSupportFeeRefund(0);
+            const treasuryBefore = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            await reclaim();
+            const treasuryAfter = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            expect(treasuryAfter).to.equal(treasuryBefore);
+        });
+
+        it('should refund the configured share of the fee from the treasury sub-accounts', async () => {
+            await setSupportFeeRefund(5000); // 50%
+            const expectedRefund = expectedFee.divn(2).toNumber();
+
+            const treasuryLamportsBefore = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            const treasuryBefore = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            await reclaim();
+            const treasuryLamportsAfter = await ctx.provider.connection.getBalance(ctx.treasuryAddress!);
+            const treasuryAfter = await program.account.treasury.fetch(ctx.treasuryAddress!);
+
+            expect(treasuryLamportsBefore - treasuryLamportsAfter).to.equal(expectedRefund);
+            const subAccountsTotal = (t: any) =>
+                t.marketing.solBalance.toNumber() + t.team.solBalance.toNumber() + t.operations.solBalance.toNumber()
+                + t.investments.solBalance.toNumber() + t.crank.solBalance.toNumber();
+            expect(subAccountsTotal(treasuryBefore) - subAccountsTotal(treasuryAfter)).to.equal(expectedRefund);
+            // Un remboursement de frais n'est pas un retrait de la trésorerie
+            for (const category of ['marketing', 'team', 'operations', 'investments', 'crank']) {
+                expect((treasuryAfter as any)[category].lastWithdrawal.eq((treasuryBefore as any)[category].lastWithdrawal)).to.be.true;
+            }
+        });
+
+        it('should reject a refund share above 100%', async () => {
+            try {
+                await setSupportFeeRefund(10001);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidSupportFeeRefund');
+            }
+        });
+    });
+}
@@ -0,0 +1,140 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, ensureEpochExists, activateEpochOnChain, closeEpochOnChain, markEpochAsProcessedOnChain } from '../../setup/epochSetup';
+import { ensureTreasuryInitialized } from '../../setup/treasurySetup';
+import { createProposalOnChain, supportProposalOnChain, TokenProposalDetails } from '../../setup/proposalSetup';
+
+// Valeurs par défaut définies dans constants.rs
+const DEFAULT_CRANK_REWARD_LAMPORTS = new anchor.BN(10_000);
+const DEFAULT_CRANK_REWARD_EPOCH_CAP = new anchor.BN(1_000_000);
+
+export function runCrankRewardTests() {
+    describe('Crank rewards (update_crank_reward_config / activate_epoch / end_epoch)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let adminKeypair: Keypair;
+        let crankKeypair: Keypair;
+
+        async function setCrankRewardConfig(signer: Keypair, reward: anchor.BN, cap: anchor.BN) {
+            await program.methods
+                .updateCrankRewardConfig(reward, cap)
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    adminAuthority: signer.publicKey,
+                } as any)
+                .signers([signer])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+            adminKeypair = ctx.adminKeypair;
+
+            await ensureTreasuryInitialized(ctx);
+
+            crankKeypair = Keypair.generate();
+            const sig = await ctx.provider.connection.requestAirdrop(crankKeypair.publicKey, LAMPORTS_PER_SOL);
+            await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+        });
+
+        after(async () => {
+            await setCrankRewardConfig(adminKeypair, DEFAULT_CRANK_REWARD_LAMPORTS, DEFAULT_CRANK_REWARD_EPOCH_CAP);
+        });
+
+        it('should let the admin update the crank reward config', async () => {
+            const reward = new anchor.BN(20_000);
+            const cap = new anchor.BN(2_000_000);
+            await setCrankRewardConfig(adminKeypair, reward, cap);
+
+            const config = await program.account.programConfig.fetch(ctx.programConfigAddress!);
+            expect(config.crankRewardLamports.eq(reward)).to.be.true;
+            expect(config.crankRewardEpochCap.eq(cap)).to.be.true;
+        });
+
+        it('should fail to update the crank reward config when the signer is not the admin', async () => {
+            try {
+                await setCrankRewardConfig(crankKeypair, new anchor.BN(1), new anchor.BN(1));
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('Unauthorized');
+            }
+        });
+
+        it('should fail to set a reward above the per-epoch cap', async () => {
+            try {
+                await setCrankRewardConfig(adminKeypair, new anchor.BN(2), new anchor.BN(1));
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidCrankRewardConfig');
+            }
+        });
+
+        it('should pay the crank from the Crank sub-account and cap the total per epoch', async () => {
+            const reward = new anchor.BN(1_000);
+            const cap = new anchor.BN(1_500);
+            await setCrankRewardConfig(adminKeypair, reward, cap);
+
+            // Les frais de support alimentent le sous-compte Crank (1%)
+            const fundingEpochId = generateRandomBN();
+            const fundingEpochPda = await ensureEpochIsActive(ctx, fundingEpochId);
+            const details: TokenProposalDetails = {
+                epochId: fundingEpochId,
+                name: "Crank Reward Token",
+                symbol: "CRT",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Proposal whose support funds the crank sub-account.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            const proposalPda = await createProposalOnChain(ctx, crankKeypair, details, fundingEpochPda);
+            await supportProposalOnChain(ctx, crankKeypair, proposalPda, fundingEpochId, fundingEpochPda, new anchor.BN(LAMPORTS_PER_SOL / 2));
+
+            const treasuryBefore = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            expect(treasuryBefore.crank.solBalance.gte(cap)).to.be.true;
+
+            // Première maintenance (activation, sans permission) : récompense complète
+            const now = Math.floor(Date.now() / 1000);
+            const epochId = generateRandomBN();
+            const epochPda = await ensureEpochExists(ctx, epochId, new anchor.BN(now - 60), new anchor.BN(now + 3));
+            await activateEpochOnChain(ctx, epochId, crankKeypair);
+            let epoch = await program.account.epochManagement.fetch(epochPda);
+            expect(epoch.crankRewardsPaid.eq(reward)).to.be.true;
+
+            // Deuxième maintenance (fermeture de l'époque expirée, sans permission) :
+            // limitée par le plafond restant de l'époque
+            await new Promise((resolve) => setTimeout(resolve, 5000));
+            await closeEpochOnChain(ctx, epochId, crankKeypair);
+            epoch = await program.account.epochManagement.fetch(epochPda);
+            expect(epoch.crankRewardsPaid.eq(cap)).to.be.true;
+
+            const treasuryAfter = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            expect(treasuryBefore.crank.solBalance.sub(treasuryAfter.crank.solBalance).eq(cap)).to.be.true;
+            expect(treasuryAfter.crank.lastWithdrawal.gtn(0)).to.be.true;
+        });
+
+        it('should not pay a crank reward on admin-only instructions', async () => {
+            await setCrankRewardConfig(adminKeypair, new anchor.BN(1_000), new anchor.BN(10_000));
+
+            const epochId = generateRandomBN();
+            const epochPda = await ensureEpochIsActive(ctx, epochId);
+            const active = await program.account.epochManagement.fetch(epochPda);
+
+            // Fermeture anticipée par l'admin, avant end_time
+            await closeEpochOnChain(ctx, epochId);
+            const before = await program.account.epochManagement.fetch(epochPda);
+            expect(before.crankRewardsPaid.eq(active.crankRewardsPaid)).to.be.true;
+
+            await markEpochAsProcessedOnChain(ctx, epochId);
+
+            const after = await program.account.epochManagement.fetch(epochPda);
+            expect(after.processed).to.be.true;
+            expect(after.crankRewardsPaid.eq(before.crankRewardsPaid)).to.be.true;
+        });
+    });
+}
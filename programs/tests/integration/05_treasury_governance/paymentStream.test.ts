@@ -0,0 +1,159 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import {
+    ensureTreasuryInitialized,
+    ensureTreasuryRolesInitialized,
+    getPaymentStreamPda,
+} from '../../setup/treasurySetup';
+
+export function runPaymentStreamTests() {
+    describe('Instructions: create_stream / claim_stream / cancel_stream', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let adminKeypair: Keypair;
+        let recipientKeypair: Keypair;
+
+        const ratePerSecond = new anchor.BN(1);
+
+        async function createStream(
+            signer: Keypair,
+            durationSeconds: number = 3600,
+            category: object = { team: {} },
+            rate: anchor.BN = ratePerSecond
+        ): Promise<PublicKey> {
+            const streamId = generateRandomBN();
+            const [streamPda] = getPaymentStreamPda(program.programId, streamId);
+            const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + durationSeconds);
+            await program.methods
+                .createStream(streamId, category as any, recipientKeypair.publicKey, rate, endTime)
+                .accounts({
+                    authority: signer.publicKey,
+                    treasuryRoles: ctx.treasuryRolesAddress,
+                    stream: streamPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc();
+            return streamPda;
+        }
+
+        async function claimStream(signer: Keypair, streamPda: PublicKey) {
+            await program.methods
+                .claimStream()
+                .accounts({
+                    recipient: signer.publicKey,
+                    stream: streamPda,
+                    treasury: ctx.treasuryAddress,
+                } as any)
+                .signers([signer])
+                .rpc();
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+            adminKeypair = ctx.adminKeypair;
+
+            await ensureTreasuryInitialized(ctx);
+            await ensureTreasuryRolesInitialized(ctx, [adminKeypair.publicKey]);
+
+            recipientKeypair = Keypair.generate();
+            const sig = await ctx.provider.connection.requestAirdrop(recipientKeypair.publicKey, LAMPORTS_PER_SOL);
+            await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+        });
+
+        it('should let an admin create a stream', async () => {
+            const streamPda = await createStream(adminKeypair);
+            const stream = await program.account.paymentStream.fetch(streamPda);
+
+            expect(stream.creator.equals(adminKeypair.publicKey)).to.be.true;
+            expect(stream.recipient.equals(recipientKeypair.publicKey)).to.be.true;
+            expect(stream.category).to.deep.equal({ team: {} });
+            expect(stream.ratePerSecond.eq(ratePerSecond)).to.be.true;
+            expect(stream.endTime.gt(stream.startTime)).to.be.true;
+            expect(stream.totalClaimed.isZero()).to.be.true;
+        });
+
+        it('should fail to create a stream when the signer is not an admin', async () => {
+            try {
+                await createStream(recipientKeypair);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('Unauthorized');
+            }
+        });
+
+        it('should let the recipient claim the vested amount from the Team sub-account', async () => {
+            const streamPda = await createStream(adminKeypair);
+            await new Promise((resolve) => setTimeout(resolve, 2000));
+
+            const treasuryBefore = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            await claimStream(recipientKeypair, streamPda);
+            const treasuryAfter = await program.account.treasury.fetch(ctx.treasuryAddress!);
+
+            const stream = await program.account.paymentStream.fetch(streamPda);
+            expect(stream.totalClaimed.gtn(0)).to.be.true;
+            expect(treasuryBefore.team.solBalance.sub(treasuryAfter.team.solBalance).eq(stream.totalClaimed)).to.be.true;
+            expect(treasuryAfter.team.lastWithdrawal.gtn(0)).to.be.true;
+        });
+
+        it('should pay only what the category holds when the stream outgrows it', async () => {
+            // Flux bien plus rapide que ce que le sous-compte Operations peut couvrir
+            const streamPda = await createStream(adminKeypair, 3600, { operations: {} }, new anchor.BN(LAMPORTS_PER_SOL));
+            await new Promise((resolve) => setTimeout(resolve, 2000));
+
+            const treasuryBefore = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            await claimStream(recipientKeypair, streamPda);
+            const treasuryAfter = await program.account.treasury.fetch(ctx.treasuryAddress!);
+
+            const stream = await program.account.paymentStream.fetch(streamPda);
+            expect(stream.totalClaimed.eq(treasuryBefore.operations.solBalance)).to.be.true;
+            expect(treasuryAfter.operations.solBalance.isZero()).to.be.true;
+        });
+
+        it('should fail to claim a stream for another recipient', async () => {
+            const streamPda = await createStream(adminKeypair);
+            try {
+                await claimStream(adminKeypair, streamPda);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InvalidRecipient');
+            }
+        });
+
+        it('should stop vesting once the stream is cancelled', async () => {
+            const streamPda = await createStream(adminKeypair);
+            await program.methods
+                .cancelStream()
+                .accounts({
+                    authority: adminKeypair.publicKey,
+                    treasuryRoles: ctx.treasuryRolesAddress,
+                    stream: streamPda,
+                } as any)
+                .signers([adminKeypair])
+                .rpc();
+
+            const stream = await program.account.paymentStream.fetch(streamPda);
+            expect(stream.endTime.toNumber()).to.be.closeTo(Math.floor(Date.now() / 1000), 5);
+
+            try {
+                await program.methods
+                    .cancelStream()
+                    .accounts({
+                        authority: adminKeypair.publicKey,
+                        treasuryRoles: ctx.treasuryRolesAddress,
+                        stream: streamPda,
+                    } as any)
+                    .signers([adminKeypair])
+                    .rpc();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('StreamAlreadyEnded');
+            }
+        });
+    });
+}
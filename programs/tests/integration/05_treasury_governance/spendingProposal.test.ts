@@ -0,0 +1,200 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, closeEpochOnChain, markEpochAsProcessedOnChain } from '../../setup/epochSetup';
+import {
+    ensureTreasuryInitialized,
+    ensureTreasuryRolesInitialized,
+    getSpendingProposalPda,
+    getSpendingVoteRecordPda,
+} from '../../setup/treasurySetup';
+import {
+    createProposalOnChain,
+    supportProposalOnChain,
+    TokenProposalDetails,
+} from '../../setup/proposalSetup';
+
+export function runSpendingProposalTests() {
+    describe('Instructions: create/vote/execute_spending_proposal', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let adminKeypair: Keypair;
+        let supporterKeypair: Keypair;
+        let outsiderKeypair: Keypair;
+
+        let pastEpochPda: PublicKey;
+        let pastSupportPda: PublicKey;
+
+        const recipient = Keypair.generate().publicKey;
+        const spendingAmount = new anchor.BN(1000);
+
+        async function createSpendingProposal(signer: Keypair, amount: anchor.BN = spendingAmount): Promise<PublicKey> {
+            const spendingId = generateRandomBN();
+            const [spendingPda] = getSpendingProposalPda(program.programId, spendingId);
+            await program.methods
+                .createSpendingProposal(spendingId, { operations: {} } as any, recipient, amount, "Audit of the program")
+                .accounts({
+                    proposer: signer.publicKey,
+                    treasuryRoles: ctx.treasuryRolesAddress,
+                    treasury: ctx.treasuryAddress,
+                    spendingProposal: spendingPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc();
+            return spendingPda;
+        }
+
+        async function vote(voter: Keypair, spendingPda: PublicKey, approve: boolean, weightAccounts: PublicKey[]) {
+            const [voteRecordPda] = getSpendingVoteRecordPda(program.programId, spendingPda, voter.publicKey);
+            await program.methods
+                .voteSpendingProposal(approve)
+                .accounts({
+                    voter: voter.publicKey,
+                    spendingProposal: spendingPda,
+                    voteRecord: voteRecordPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .remainingAccounts(weightAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+                .signers([voter])
+                .rpc();
+            return voteRecordPda;
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+            adminKeypair = ctx.adminKeypair;
+
+            await ensureTreasuryInitialized(ctx);
+            await ensureTreasuryRolesInitialized(ctx, [adminKeypair.publicKey]);
+
+            supporterKeypair = Keypair.generate();
+            outsiderKeypair = Keypair.generate();
+            for (const kp of [supporterKeypair, outsiderKeypair]) {
+                const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, 3 * LAMPORTS_PER_SOL);
+                await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+            }
+
+            // Une époque passée et traitée, dans laquelle le supporter a soutenu une proposition.
+            // La création de la proposition alimente aussi le sous-compte Operations.
+            const epochId = generateRandomBN();
+            pastEpochPda = await ensureEpochIsActive(ctx, epochId);
+            const details: TokenProposalDetails = {
+                epochId,
+                name: "Spending Weight Token",
+                symbol: "SWT",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Proposal used to give voting weight.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            const proposalPda = await createProposalOnChain(ctx, supporterKeypair, details, pastEpochPda);
+            pastSupportPda = await supportProposalOnChain(ctx, supporterKeypair, proposalPda, epochId, pastEpochPda, new anchor.BN(LAMPORTS_PER_SOL));
+            await closeEpochOnChain(ctx, epochId);
+            await markEpochAsProcessedOnChain(ctx, epochId);
+        });
+
+        it('should let an admin create a spending proposal with an open voting window', async () => {
+            const spendingPda = await createSpendingProposal(adminKeypair);
+            const spending = await program.account.spendingProposal.fetch(spendingPda);
+
+            expect(spending.proposer.equals(adminKeypair.publicKey)).to.be.true;
+            expect(spending.recipient.equals(recipient)).to.be.true;
+            expect(spending.amount.eq(spendingAmount)).to.be.true;
+            expect(spending.category).to.deep.equal({ operations: {} });
+            expect(spending.status).to.deep.equal({ voting: {} });
+            expect(spending.votingEnd.gt(spending.votingStart)).to.be.true;
+            expect(spending.votesFor.isZero()).to.be.true;
+        });
+
+        it('should fail to create a spending proposal without a role', async () => {
+            try {
+                await createSpendingProposal(outsiderKeypair);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('Unauthorized');
+            }
+        });
+
+        it('should fail to create a spending proposal above the sub-account balance', async () => {
+            const treasury = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            try {
+                await createSpendingProposal(adminKeypair, treasury.operations.solBalance.addn(1));
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('InsufficientTreasuryBalance');
+            }
+        });
+
+        it('should record a vote weighted by the historic support of the voter', async () => {
+            const spendingPda = await createSpendingProposal(adminKeypair);
+            const support = await program.account.userProposalSupport.fetch(pastSupportPda);
+
+            const voteRecordPda = await vote(supporterKeypair, spendingPda, true, [pastSupportPda, pastEpochPda]);
+
+            const spending = await program.account.spendingProposal.fetch(spendingPda);
+            expect(spending.votesFor.eq(support.amount)).to.be.true;
+            expect(spending.votesAgainst.isZero()).to.be.true;
+            expect(spending.votersCount.toNumber()).to.equal(1);
+
+            const voteRecord = await program.account.spendingVoteRecord.fetch(voteRecordPda);
+            expect(voteRecord.weight.eq(support.amount)).to.be.true;
+            expect(voteRecord.approve).to.be.true;
+        });
+
+        it('should fail to vote twice on the same spending proposal', async () => {
+            const spendingPda = await createSpendingProposal(adminKeypair);
+            await vote(supporterKeypair, spendingPda, true, [pastSupportPda, pastEpochPda]);
+            try {
+                await vote(supporterKeypair, spendingPda, false, [pastSupportPda, pastEpochPda]);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as Error).toString()).to.include('already in use');
+            }
+        });
+
+        it('should fail to vote with a support account that belongs to someone else', async () => {
+            const spendingPda = await createSpendingProposal(adminKeypair);
+            try {
+                await vote(outsiderKeypair, spendingPda, true, [pastSupportPda, pastEpochPda]);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('IneligibleSupportAccount');
+            }
+        });
+
+        it('should fail to count the same support account twice', async () => {
+            const spendingPda = await createSpendingProposal(adminKeypair);
+            try {
+                await vote(supporterKeypair, spendingPda, true, [pastSupportPda, pastEpochPda, pastSupportPda, pastEpochPda]);
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('DuplicateSupportAccount');
+            }
+        });
+
+        it('should fail to execute a spending proposal while voting is still open', async () => {
+            const spendingPda = await createSpendingProposal(adminKeypair);
+            try {
+                await program.methods
+                    .executeSpendingProposal()
+                    .accounts({
+                        executor: adminKeypair.publicKey,
+                        spendingProposal: spendingPda,
+                        treasury: ctx.treasuryAddress,
+                        recipient,
+                    } as any)
+                    .signers([adminKeypair])
+                    .rpc();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('VotingStillOpen');
+            }
+        });
+    });
+}
@@ -0,0 +1,328 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Programs } from '../../../target/types/programs';
+import { TestContext, getInitializedContext, generateRandomBN } from '../../setup';
+import { ensureEpochIsActive, getLeaderboardPda } from '../../setup/epochSetup';
+import { ensureTreasuryRolesInitialized } from '../../setup/treasurySetup';
+import { createProposalOnChain, getProposalMetadataPda, getProposalVaultPda, TokenProposalDetails } from '../../setup/proposalSetup';
+
+// Comptes chargés par le validateur de test depuis tests/fixtures (voir Anchor.toml),
+// sérialisés avec le format antérieur au champ `version`.
+const LEGACY_EPOCH_ID = new anchor.BN("41000000001");
+const LEGACY_EPOCH_ADDRESS = new PublicKey("7NjX9MC6VWyjXsgnCMnCvLnGep322J7SeWo39QpCp7mV");
+const LEGACY_PROPOSAL_ADDRESS = new PublicKey("FkrfckKLYv8XUvCzroUAhdRKnxH2kYa7LrRZd49W1h97");
+// Proposition v0 dont les chaînes remplissent toute l'allocation (rien après `status`)
+const LEGACY_MAX_LENGTH_PROPOSAL_ADDRESS = new PublicKey("C7YbuG4gHiM1gujwwYyHdB2vfYcumMn84UDF2Vihdb6M");
+const LEGACY_SUPPORT_ADDRESS = new PublicKey("Chco4vVFkyQoJSYxw1ebtv97U65QeJJe2Ny5Jocvn36j");
+const LEGACY_EPOCH_SPACE = 34;
+const LEGACY_PROPOSAL_SPACE = 916;
+const LEGACY_SUPPORT_SPACE = 88;
+// Format v3 : liste d'autorisation Merkle et EpochConfig
+const CURRENT_EPOCH_SPACE = 507;
+// Format v2 : description et image déplacées dans ProposalMetadata
+const CURRENT_PROPOSAL_SPACE = 148;
+// Format v1 : fees_paid et version
+const CURRENT_SUPPORT_SPACE = 97;
+
+export function runAccountMigrationTests() {
+    describe('Instructions: migrate_* (account versioning)', () => {
+        let ctx: TestContext;
+        let program: Program<Programs>;
+        let payerKeypair: Keypair;
+
+        async function expectAlreadyMigrated(call: Promise<string>) {
+            try {
+                await call;
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('AccountAlreadyMigrated');
+            }
+        }
+
+        before(async () => {
+            ctx = getInitializedContext();
+            program = ctx.program;
+            payerKeypair = Keypair.generate();
+            const sig = await ctx.provider.connection.requestAirdrop(payerKeypair.publicKey, 2 * LAMPORTS_PER_SOL);
+            await ctx.provider.connection.confirmTransaction(sig, "confirmed");
+        });
+
+        it('should load the legacy fixtures with the pre-versioning layout', async () => {
+            const epochInfo = await ctx.provider.connection.getAccountInfo(LEGACY_EPOCH_ADDRESS);
+            const proposalInfo = await ctx.provider.connection.getAccountInfo(LEGACY_PROPOSAL_ADDRESS);
+            expect(epochInfo!.owner.equals(program.programId)).to.be.true;
+            expect(epochInfo!.data.length).to.equal(LEGACY_EPOCH_SPACE);
+            expect(proposalInfo!.data.length).to.equal(LEGACY_PROPOSAL_SPACE);
+            const supportInfo = await ctx.provider.connection.getAccountInfo(LEGACY_SUPPORT_ADDRESS);
+            expect(supportInfo!.data.length).to.equal(LEGACY_SUPPORT_SPACE);
+        });
+
+        it('should realloc and upgrade a legacy EpochManagement in place', async () => {
+            await program.methods
+                .migrateEpochManagement(LEGACY_EPOCH_ID)
+                .accounts({
+                    payer: payerKeypair.publicKey,
+                    epochManagement: LEGACY_EPOCH_ADDRESS,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([payerKeypair])
+                .rpc();
+
+            const info = await ctx.provider.connection.getAccountInfo(LEGACY_EPOCH_ADDRESS);
+            expect(info!.data.length).to.equal(CURRENT_EPOCH_SPACE);
+            const epoch = await program.account.epochManagement.fetch(LEGACY_EPOCH_ADDRESS);
+            expect(epoch.version).to.equal(3);
+            expect(epoch.allowlistRoot).to.be.null;
+            expect(epoch.config.maxWinners).to.be.null;
+            expect(epoch.epochId.eq(LEGACY_EPOCH_ID)).to.be.true;
+            expect(epoch.status).to.deep.equal({ closed: {} });
+            expect(epoch.processed).to.be.true;
+            // Compteurs inconnus avant la version 1 : l'époque reste non archivable
+            expect(epoch.proposalsCount.eq(new anchor.BN("18446744073709551615"))).to.be.true;
+            expect(epoch.processedAt.isZero()).to.be.true;
+        });
+
+        it('should let the admin backfill the counters of a legacy epoch once', async () => {
+            const backfill = () => program.methods
+                .backfillLegacyEpoch(LEGACY_EPOCH_ID, new anchor.BN(1), new anchor.BN(0), new anchor.BN(0), new anchor.BN(0))
+                .accounts({
+                    programConfig: ctx.programConfigAddress,
+                    epochManagement: LEGACY_EPOCH_ADDRESS,
+                    adminAuthority: ctx.adminKeypair.publicKey,
+                } as any)
+                .signers([ctx.adminKeypair])
+                .rpc();
+
+            await backfill();
+
+            const epoch = await program.account.epochManagement.fetch(LEGACY_EPOCH_ADDRESS);
+            expect(epoch.proposalsCount.toNumber()).to.equal(1);
+            expect(epoch.proposalsSettled.isZero()).to.be.true;
+            // Le délai de grâce avant archivage part du backfill
+            expect(epoch.processedAt.toNumber()).to.be.greaterThan(0);
+
+            try {
+                await backfill();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect((error as anchor.AnchorError).error.errorCode.code).to.equal('EpochAlreadyBackfilled');
+            }
+        });
+
+        it('should let anyone create the missing leaderboard of a legacy epoch once', async () => {
+            const [leaderboardPda] = getLeaderboardPda(program.programId, LEGACY_EPOCH_ID);
+            expect(await ctx.provider.connection.getAccountInfo(leaderboardPda)).to.be.null;
+
+            const initLeaderboard = () => program.methods
+                .initEpochLeaderboard(LEGACY_EPOCH_ID)
+                .accounts({
+                    payer: payerKeypair.publicKey,
+                    epochManagement: LEGACY_EPOCH_ADDRESS,
+                    leaderboard: leaderboardPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([payerKeypair])
+                .rpc();
+
+            await initLeaderboard();
+
+            const leaderboard = await program.account.epochLeaderboard.fetch(leaderboardPda);
+            expect(leaderboard.epochId.eq(LEGACY_EPOCH_ID)).to.be.true;
+            expect(leaderboard.len).to.equal(0);
+            expect(leaderboard.version).to.equal(1);
+
+            // Le classement existe déjà : init échoue
+            try {
+                await initLeaderboard();
+                expect.fail('Transaction should have failed but succeeded.');
+            } catch (error) {
+                expect(error).to.exist;
+            }
+        });
+
+        it('should split a legacy TokenProposal into a slim proposal and its metadata', async () => {
+            const [metadataPda] = getProposalMetadataPda(program.programId, LEGACY_PROPOSAL_ADDRESS);
+            const lamportsBefore = await ctx.provider.connection.getBalance(LEGACY_PROPOSAL_ADDRESS);
+
+            await program.methods
+                .migrateTokenProposal()
+                .accounts({
+                    payer: payerKeypair.publicKey,
+                    tokenProposal: LEGACY_PROPOSAL_ADDRESS,
+                    proposalVault: getProposalVaultPda(program.programId, LEGACY_PROPOSAL_ADDRESS)[0],
+                    proposalMetadata: metadataPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([payerKeypair])
+                .rpc();
+
+            const info = await ctx.provider.connection.getAccountInfo(LEGACY_PROPOSAL_ADDRESS);
+            expect(info!.data.length).to.equal(CURRENT_PROPOSAL_SPACE);
+            // Seule la rente libérée par la réduction quitte le compte
+            const freedRent = (await ctx.provider.connection.getMinimumBalanceForRentExemption(LEGACY_PROPOSAL_SPACE))
+                - (await ctx.provider.connection.getMinimumBalanceForRentExemption(CURRENT_PROPOSAL_SPACE));
+            expect(lamportsBefore - info!.lamports).to.equal(freedRent);
+
+            const proposal = await program.account.tokenProposal.fetch(LEGACY_PROPOSAL_ADDRESS);
+            expect(proposal.version).to.equal(2);
+            expect(proposal.tokenName).to.equal("Legacy Token");
+            expect(proposal.tokenSymbol).to.equal("LEGACY");
+            expect(proposal.status).to.deep.equal({ rejected: {} });
+            expect(proposal.epochId.eq(LEGACY_EPOCH_ID)).to.be.true;
+
+            const metadata = await program.account.proposalMetadata.fetch(metadataPda);
+            expect(metadata.proposal.equals(LEGACY_PROPOSAL_ADDRESS)).to.be.true;
+            expect(metadata.description).to.equal("Proposal serialized before account versioning.");
+            expect(metadata.version).to.equal(1);
+
+            // Aucun support en cours : le vault ne contient que sa réserve de rente
+            const vaultLamports = await ctx.provider.connection.getBalance(getProposalVaultPda(program.programId, LEGACY_PROPOSAL_ADDRESS)[0]);
+            expect(vaultLamports).to.equal(await ctx.provider.connection.getMinimumBalanceForRentExemption(0));
+        });
+
+        it('should migrate a legacy TokenProposal whose strings fill the whole account', async () => {
+            const [metadataPda] = getProposalMetadataPda(program.programId, LEGACY_MAX_LENGTH_PROPOSAL_ADDRESS);
+            expect((await ctx.provider.connection.getAccountInfo(LEGACY_MAX_LENGTH_PROPOSAL_ADDRESS))!.data.length).to.equal(LEGACY_PROPOSAL_SPACE);
+
+            await program.methods
+                .migrateTokenProposal()
+                .accounts({
+                    payer: payerKeypair.publicKey,
+                    tokenProposal: LEGACY_MAX_LENGTH_PROPOSAL_ADDRESS,
+                    proposalVault: getProposalVaultPda(program.programId, LEGACY_MAX_LENGTH_PROPOSAL_ADDRESS)[0],
+                    proposalMetadata: metadataPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([payerKeypair])
+                .rpc();
+
+            const proposal = await program.account.tokenProposal.fetch(LEGACY_MAX_LENGTH_PROPOSAL_ADDRESS);
+            expect(proposal.version).to.equal(2);
+            expect(proposal.tokenName).to.equal("M".repeat(32));
+            expect(proposal.tokenSymbol).to.equal("MAXSYMBL");
+            expect(proposal.solRefunded.isZero()).to.be.true;
+            expect(proposal.status).to.deep.equal({ rejected: {} });
+
+            const metadata = await program.account.proposalMetadata.fetch(metadataPda);
+            expect(metadata.description).to.equal("D".repeat(512));
+            expect(metadata.imageUrl).to.have.lengthOf(256);
+        });
+
+        it('should realloc a legacy UserProposalSupport with no refundable fees', async () => {
+            const migrateSupport = () => program.methods
+                .migrateUserProposalSupport()
+                .accounts({
+                    payer: payerKeypair.publicKey,
+                    userSupport: LEGACY_SUPPORT_ADDRESS,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([payerKeypair])
+                .rpc();
+
+            await migrateSupport();
+
+            const info = await ctx.provider.connection.getAccountInfo(LEGACY_SUPPORT_ADDRESS);
+            expect(info!.data.length).to.equal(CURRENT_SUPPORT_SPACE);
+            const support = await program.account.userProposalSupport.fetch(LEGACY_SUPPORT_ADDRESS);
+            expect(support.version).to.equal(1);
+            expect(support.amount.toNumber()).to.equal(250_000_000);
+            expect(support.feesPaid.isZero()).to.be.true;
+            expect(support.proposal.equals(LEGACY_PROPOSAL_ADDRESS)).to.be.true;
+
+            await expectAlreadyMigrated(migrateSupport());
+        });
+
+        it('should refuse to migrate an account twice', async () => {
+            await expectAlreadyMigrated(
+                program.methods
+                    .migrateTokenProposal()
+                    .accounts({
+                        payer: payerKeypair.publicKey,
+                        tokenProposal: LEGACY_PROPOSAL_ADDRESS,
+                        proposalVault: getProposalVaultPda(program.programId, LEGACY_PROPOSAL_ADDRESS)[0],
+                        proposalMetadata: getProposalMetadataPda(program.programId, LEGACY_PROPOSAL_ADDRESS)[0],
+                        systemProgram: SystemProgram.programId,
+                    } as any)
+                    .signers([payerKeypair])
+                    .rpc()
+            );
+        });
+
+        it('should create new accounts at the current version', async () => {
+            const config = await program.account.programConfig.fetch(ctx.programConfigAddress!);
+            const treasury = await program.account.treasury.fetch(ctx.treasuryAddress!);
+            expect(config.version).to.equal(3);
+            expect(treasury.version).to.equal(1);
+
+            const epochId = generateRandomBN();
+            const epochPda = await ensureEpochIsActive(ctx, epochId);
+            const epoch = await program.account.epochManagement.fetch(epochPda);
+            expect(epoch.version).to.equal(3);
+
+            const details: TokenProposalDetails = {
+                epochId,
+                name: "Versioned Token",
+                symbol: "VERS",
+                totalSupply: new anchor.BN(1000000),
+                creatorAllocationPercentage: 5,
+                description: "Token created at the current account version.",
+                imageUrl: null,
+                lockupPeriod: new anchor.BN(0),
+            };
+            const proposalPda = await createProposalOnChain(ctx, payerKeypair, details, epochPda);
+            const proposal = await program.account.tokenProposal.fetch(proposalPda);
+            expect(proposal.version).to.equal(2);
+            // Comptes créés directement avec un champ `version`
+            const leaderboard = await program.account.epochLeaderboard.fetch(getLeaderboardPda(program.programId, epochId)[0]);
+            expect(leaderboard.version).to.equal(1);
+            const metadata = await program.account.proposalMetadata.fetch(getProposalMetadataPda(program.programId, proposalPda)[0]);
+            expect(metadata.version).to.equal(1);
+
+            await expectAlreadyMigrated(
+                program.methods
+                    .migrateProgramConfig()
+                    .accounts({
+                        payer: payerKeypair.publicKey,
+                        programConfig: ctx.programConfigAddress,
+                        systemProgram: SystemProgram.programId,
+                    } as any)
+                    .signers([payerKeypair])
+                    .rpc()
+            );
+            await expectAlreadyMigrated(
+                program.methods
+                    .migrateTreasury()
+                    .accounts({
+                        payer: payerKeypair.publicKey,
+                        treasury: ctx.treasuryAddress,
+                        systemProgram: SystemProgram.programId,
+                    } as any)
+                    .signers([payerKeypair])
+                    .rpc()
+            );
+        });
+
+        // TreasuryRoles est un singleton : une fixture v0 à son PDA imposerait ses autorités aux tests
+        // de rôles (l'admin de test est généré à chaque exécution). Le passage depuis v0 est couvert
+        // par les tests unitaires de utils/migration.rs ; ici, le compte créé par les tests.
+        it('should create TreasuryRoles at the current version and refuse to migrate it', async () => {
+            const treasuryRolesPda = await ensureTreasuryRolesInitialized(ctx);
+            const treasuryRoles = await program.account.treasuryRoles.fetch(treasuryRolesPda);
+            expect(treasuryRoles.version).to.equal(1);
+
+            await expectAlreadyMigrated(
+                program.methods
+                    .migrateTreasuryRoles()
+                    .accounts({
+                        payer: payerKeypair.publicKey,
+                        treasuryRoles: treasuryRolesPda,
+                        systemProgram: SystemProgram.programId,
+                    } as any)
+                    .signers([payerKeypair])
+                    .rpc()
+            );
+        });
+    });
+}
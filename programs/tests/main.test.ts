@@ -13,8 +13,11 @@ import { runInitializeProgramConfigTests } from './integration/00_program_config
 
 // Importer les tests individuels pour Epoch Lifecycle
 import { runStartEpochTests } from './integration/01_epoch_lifecycle/startEpoch.test';
+import { runActivateEpochTests } from './integration/01_epoch_lifecycle/activateEpoch.test';
 import { runEndEpochTests } from './integration/01_epoch_lifecycle/endEpoch.test';
 import { runMarkEpochProcessedTests } from './integration/01_epoch_lifecycle/markEpochProcessed.test';
+import { runArchiveEpochTests } from './integration/01_epoch_lifecycle/archiveEpoch.test';
+import { runEpochConfigTests } from './integration/01_epoch_lifecycle/epochConfig.test';
 
 import { 
     ensureTreasuryInitialized, 
@@ -35,10 +38,31 @@ import { runCreateTokenProposalTests } from './integration/03_proposal_workflow/
 import { runSupportProposalTests } from './integration/03_proposal_workflow/supportProposal.test';
 import { runUpdateProposalStatusTests } from './integration/03_proposal_workflow/updateProposalStatus.test';
 import { runReclaimSupportTests } from './integration/03_proposal_workflow/reclaimSupport.test';
+import { runManageProposalTests } from './integration/03_proposal_workflow/manageProposal.test';
+import { runSymbolRegistryTests } from './integration/03_proposal_workflow/symbolRegistry.test';
+import { runSupportCapsTests } from './integration/03_proposal_workflow/supportCaps.test';
+import { runLaunchThresholdsTests } from './integration/03_proposal_workflow/launchThresholds.test';
+import { runUnsupportProposalTests } from './integration/03_proposal_workflow/unsupportProposal.test';
+import { runReallocateSupportTests } from './integration/03_proposal_workflow/reallocateSupport.test';
+import { runReclaimManyTests } from './integration/03_proposal_workflow/reclaimMany.test';
+import { runPushRefundTests } from './integration/03_proposal_workflow/pushRefund.test';
+import { runCloseProposalTests } from './integration/03_proposal_workflow/closeProposal.test';
+import { runEpochLeaderboardTests } from './integration/03_proposal_workflow/epochLeaderboard.test';
+import { runCreatorBondTests } from './integration/03_proposal_workflow/creatorBond.test';
+import { runModerationTests } from './integration/03_proposal_workflow/moderation.test';
+import { runEpochAllowlistTests } from './integration/03_proposal_workflow/epochAllowlist.test';
 
 // Importer les tests pour la logique des frais
 import { runProposalCreationFeeTests } from './integration/04_fee_distribution_logic/proposalCreationFee.test';
 import { runProposalSupportFeeTests } from './integration/04_fee_distribution_logic/proposalSupportFee.test';
+import { runSupportFeeRefundTests } from './integration/04_fee_distribution_logic/supportFeeRefund.test';
+import { runReferralRewardsTests } from './integration/04_fee_distribution_logic/referralRewards.test';
+
+// Importer les tests pour la gouvernance de la trésorerie
+import { runSpendingProposalTests } from './integration/05_treasury_governance/spendingProposal.test';
+import { runPaymentStreamTests } from './integration/05_treasury_governance/paymentStream.test';
+import { runCrankRewardTests } from './integration/05_treasury_governance/crankReward.test';
+import { runAccountMigrationTests } from './integration/06_account_migrations/accountMigration.test';
 
 describe('Norug Fun - Integration Tests Orchestrator', () => {
     let ctx: TestContext;
@@ -55,6 +79,11 @@ describe('Norug Fun - Integration Tests Orchestrator', () => {
         console.log('Ensuring ProgramConfig is initialized globally before all test modules...');
         await ensureProgramConfigInitialized(ctx);
         console.log(`ProgramConfig PDA: ${ctx.programConfigAddress?.toBase58()}`);
+
+        // La trésorerie est requise par les instructions de maintenance (récompenses de crank)
+        console.log('Ensuring Treasury is initialized globally before all test modules...');
+        await ensureTreasuryInitialized(ctx);
+        console.log(`Treasury PDA: ${ctx.treasuryAddress?.toBase58()}`);
         
         console.log("\n==================== GLOBAL SETUP COMPLETE (main.test.ts) ====================\n");
     });
@@ -76,8 +105,11 @@ describe('Norug Fun - Integration Tests Orchestrator', () => {
     });
         // Appeler les tests d'époque individuellement
         runStartEpochTests(); 
+        runActivateEpochTests();
         runEndEpochTests();
         runMarkEpochProcessedTests();
+        runArchiveEpochTests();
+        runEpochConfigTests();
         after(() => console.log("==================== MODULE 01 END: Epoch Lifecycle ======================\n"));
     });
 
@@ -132,6 +164,19 @@ describe('Norug Fun - Integration Tests Orchestrator', () => {
         runSupportProposalTests();
         runUpdateProposalStatusTests();
         runReclaimSupportTests();
+        runManageProposalTests();
+        runSymbolRegistryTests();
+        runSupportCapsTests();
+        runLaunchThresholdsTests();
+        runUnsupportProposalTests();
+        runReallocateSupportTests();
+        runReclaimManyTests();
+        runPushRefundTests();
+        runCloseProposalTests();
+        runEpochLeaderboardTests();
+        runCreatorBondTests();
+        runModerationTests();
+        runEpochAllowlistTests();
 
         after(() => console.log("==================== MODULE 03 END: Proposal Workflow ======================\n"));
     });
@@ -145,11 +190,35 @@ describe('Norug Fun - Integration Tests Orchestrator', () => {
 
         runProposalCreationFeeTests();
         runProposalSupportFeeTests();
+        runSupportFeeRefundTests();
+        runReferralRewardsTests();
         // TODO: Ajouter runFeeDistributionUtilTests();
 
         after(() => console.log("==================== MODULE 04 END: Fee Distribution Logic ======================\n"));
     });
 
+    describe('Module 05: Treasury Governance', () => {
+        before(() => {
+            console.log("\n==================== MODULE 05 START: Treasury Governance ====================");
+        });
+
+        runSpendingProposalTests();
+        runPaymentStreamTests();
+        runCrankRewardTests();
+
+        after(() => console.log("==================== MODULE 05 END: Treasury Governance ======================\n"));
+    });
+
+    describe('Module 06: Account Migrations', () => {
+        before(() => {
+            console.log("\n==================== MODULE 06 START: Account Migrations ====================");
+        });
+
+        runAccountMigrationTests();
+
+        after(() => console.log("==================== MODULE 06 END: Account Migrations ======================\n"));
+    });
+
     after(async () => {
         console.log("\n==================== ALL INTEGRATION TESTS COMPLETE (main.test.ts) ====================");
     });
@@ -21,16 +21,30 @@ export const getEpochManagementPda = (programId: PublicKey, epochId: anchor.BN):
     );
 };
 
+/**
+ * Calcule le PDA du classement zero-copy (EpochLeaderboard) d'une époque.
+ * @param programId L'ID du programme Solana.
+ * @param epochId L'identifiant de l'époque.
+ * @returns Une paire [PublicKey, bump] pour le PDA du classement.
+ */
+export const getLeaderboardPda = (programId: PublicKey, epochId: anchor.BN): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("leaderboard"), epochId.toArrayLike(Buffer, "le", 8)],
+        programId
+    );
+};
+
 /**
  * S'assure qu'un compte EpochManagement existe pour un epochId donné.
  * Si le compte n'existe pas, il appelle l'instruction `start_epoch`.
- * Note: `start_epoch` crée l'epoch avec un statut initial (ex: Pending ou directement Active si les temps correspondent).
+ * Note: `start_epoch` crée l'epoch au statut Pending ; voir `activateEpochOnChain` pour la passer à Active.
  * 
  * @param ctx Le contexte de test actuel.
  * @param epochId L'identifiant de l'époque à assurer/créer.
  * @param startTime Le temps de début de l'époque.
  * @param endTime Le temps de fin de l'époque.
  * @param authorityKp Optionnel, le Keypair de l'autorité pour signer. Si non fourni, `ctx.adminKeypair` sera utilisé.
+ * @param epochConfig Optionnel, les règles propres à l'époque (EpochConfig). Si non fourni, les règles globales s'appliquent.
  * @returns L'adresse PublicKey du compte EpochManagement.
  */
 export async function ensureEpochExists(
@@ -38,7 +52,8 @@ export async function ensureEpochExists(
     epochId: anchor.BN, 
     startTime: anchor.BN, 
     endTime: anchor.BN,
-    authorityKp?: Keypair
+    authorityKp?: Keypair,
+    epochConfig: any = null
 ): Promise<PublicKey> {
     if (!ctx.programConfigAddress) {
         throw new Error("ProgramConfigAddress not found in TestContext. Ensure it was initialized before calling epoch setup functions.");
@@ -60,11 +75,12 @@ export async function ensureEpochExists(
 
     try {
         await ctx.program.methods
-            .startEpoch(epochId, startTime, endTime)
+            .startEpoch(epochId, startTime, endTime, epochConfig)
             .accounts({
                 authority: signer.publicKey,
                 programConfig: ctx.programConfigAddress,
                 epochManagement: epochManagementAddress,
+                leaderboard: getLeaderboardPda(ctx.program.programId, epochId)[0],
                 systemProgram: SystemProgram.programId,
             } as any)
             .signers([signer])
@@ -83,18 +99,54 @@ export async function ensureEpochExists(
  * @param ctx Le contexte de test actuel.
  * @param epochId L'identifiant de l'époque.
  * @param authorityKp Optionnel, le Keypair de l'autorité. Si non fourni, `ctx.adminKeypair` sera utilisé.
+ * @param epochConfig Optionnel, les règles propres à l'époque (EpochConfig).
  * @returns L'adresse PublicKey du compte EpochManagement.
  */
 export async function ensureEpochIsActive(
     ctx: TestContext, 
     epochId: anchor.BN,
-    authorityKp?: Keypair 
+    authorityKp?: Keypair,
+    epochConfig: any = null
 ): Promise<PublicKey> {
     const now = Math.floor(Date.now() / 1000);
     const startTime = new anchor.BN(now - 60); // Commence il y a 1 minute
     const endTime = new anchor.BN(now + 3600); // Se termine dans 1 heure
     console.log(`Ensuring epoch ${epochId.toString()} is active (startTime: ${startTime}, endTime: ${endTime})...`);
-    return ensureEpochExists(ctx, epochId, startTime, endTime, authorityKp);
+    const epochManagementAddress = await ensureEpochExists(ctx, epochId, startTime, endTime, authorityKp, epochConfig);
+    const epochAccount = await ctx.program.account.epochManagement.fetch(epochManagementAddress);
+    if (epochAccount.status.pending) {
+        await activateEpochOnChain(ctx, epochId);
+    }
+    return epochManagementAddress;
+}
+
+/**
+ * Appelle l'instruction `activate_epoch` pour passer une époque planifiée (Pending) à Active.
+ * L'instruction est sans permission une fois `start_time` atteint.
+ *
+ * @param ctx Le contexte de test actuel.
+ * @param epochId L'identifiant de l'époque à activer.
+ * @param authorityKp Optionnel, le Keypair du crank. Si non fourni, `ctx.adminKeypair` sera utilisé.
+ */
+export async function activateEpochOnChain(
+    ctx: TestContext,
+    epochId: anchor.BN,
+    authorityKp?: Keypair
+): Promise<void> {
+    const [epochManagementAddress, _bump] = getEpochManagementPda(ctx.program.programId, epochId);
+    const signer = authorityKp || ctx.adminKeypair;
+
+    console.log(`Activating epoch ${epochId.toString()} on-chain...`);
+    await ctx.program.methods
+        .activateEpoch(epochId)
+        .accounts({
+            authority: signer.publicKey,
+            programConfig: ctx.programConfigAddress,
+            epochManagement: epochManagementAddress,
+            treasury: ctx.treasuryAddress,
+        } as any)
+        .signers([signer])
+        .rpc();
 }
 
 
@@ -133,6 +185,7 @@ export async function closeEpochOnChain(
                 authority: signer.publicKey,
                 programConfig: ctx.programConfigAddress,
                 epochManagement: epochManagementAddress,
+                treasury: ctx.treasuryAddress,
                 systemProgram: SystemProgram.programId, // end_epoch peut aussi avoir besoin de system_program selon l'IDL
             } as any)
             .signers([signer])
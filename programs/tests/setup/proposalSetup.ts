@@ -8,7 +8,8 @@ import { expect } from 'chai';
 // import { TOKEN_PROGRAM_ID } from '@solana/spl-token'; // Commenté car non utilisé par les fonctions actuelles de ce module
 import { Programs } from '../../target/types/programs';
 import { TestContext, shortenAddress, generateRandomBN } from './index'; // Assurer que shortenAddress et generateRandomBN sont importés
-import { getEpochManagementPda } from './epochSetup'; // À créer ou vérifier
+import { getEpochManagementPda, getLeaderboardPda } from './epochSetup'; // À créer ou vérifier
+import { buildReclaimManyTransactions } from '../../../front/lib/reclaimMany';
 
 /**
  * Interface pour les détails d'une proposition de token.
@@ -49,6 +50,111 @@ export function getProposalPda(
     );
 }
 
+/**
+ * Forme normalisée d'un symbole, identique à `normalize_symbol` côté programme.
+ * @param symbol Le symbole du token.
+ * @returns Le symbole en majuscules ASCII.
+ */
+export function normalizeSymbol(symbol: string): string {
+    return symbol.replace(/[a-z]/g, (c) => c.toUpperCase());
+}
+
+/**
+ * Calcule le PDA du registre de symbole d'une époque (SymbolRegistry).
+ * @param programId L'ID du programme.
+ * @param epochId L'ID de l'époque.
+ * @param symbol Le symbole du token (normalisé par la fonction).
+ * @returns La PublicKey du PDA et le bump.
+ */
+export function getEpochSymbolPda(programId: PublicKey, epochId: anchor.BN, symbol: string): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("epoch_symbol"),
+            epochId.toArrayLike(Buffer, "le", 8),
+            Buffer.from(normalizeSymbol(symbol)),
+        ],
+        programId
+    );
+}
+
+/**
+ * Calcule le PDA du registre global des symboles lancés (SymbolRegistry).
+ * @param programId L'ID du programme.
+ * @param symbol Le symbole du token (normalisé par la fonction).
+ * @returns La PublicKey du PDA et le bump.
+ */
+export function getLaunchedSymbolPda(programId: PublicKey, symbol: string): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("launched_symbol"), Buffer.from(normalizeSymbol(symbol))],
+        programId
+    );
+}
+
+/**
+ * Calcule le PDA des métadonnées d'une proposition (ProposalMetadata : description et image).
+ * @param programId L'ID du programme.
+ * @param proposalPda Le PDA de la proposition décrite.
+ * @returns La PublicKey du PDA et le bump.
+ */
+export function getProposalMetadataPda(programId: PublicKey, proposalPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal_metadata"), proposalPda.toBuffer()],
+        programId
+    );
+}
+
+/**
+ * Calcule le PDA du vault SOL d'une proposition (compte système qui détient les supports).
+ * @param programId L'ID du programme.
+ * @param proposalPda Le PDA de la proposition.
+ * @returns La PublicKey du PDA et le bump.
+ */
+export function getProposalVaultPda(programId: PublicKey, proposalPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal_vault"), proposalPda.toBuffer()],
+        programId
+    );
+}
+
+/**
+ * Calcule le PDA de la caution déposée par le créateur d'une proposition (CreatorBond).
+ * @param programId L'ID du programme.
+ * @param proposalPda Le PDA de la proposition.
+ * @returns La PublicKey du PDA et le bump.
+ */
+export function getCreatorBondPda(programId: PublicKey, proposalPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("creator_bond"), proposalPda.toBuffer()],
+        programId
+    );
+}
+
+/**
+ * Calcule le PDA du dernier signalement d'une proposition (ProposalModeration).
+ * @param programId L'ID du programme.
+ * @param proposalPda Le PDA de la proposition.
+ * @returns La PublicKey du PDA et le bump.
+ */
+export function getModerationPda(programId: PublicKey, proposalPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("moderation"), proposalPda.toBuffer()],
+        programId
+    );
+}
+
+/**
+ * Calcule le PDA du compte de parrainage (ReferralAccount) d'un parrain.
+ * @param programId L'ID du programme.
+ * @param referrer La clé publique du parrain.
+ * @returns La PublicKey du PDA et le bump.
+ */
+export function getReferralPda(programId: PublicKey, referrer: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), referrer.toBuffer()],
+        programId
+    );
+}
+
 /**
  * Crée une proposition de token on-chain.
  * S'assure que la config du programme et la trésorerie sont initialisées via le TestContext.
@@ -56,13 +162,16 @@ export function getProposalPda(
  * @param proposerKeypair Le Keypair du créateur de la proposition (signataire et payeur).
  * @param details Les détails de la proposition à créer.
  * @param epochManagementAddress L'adresse PDA du compte EpochManagement pour l'époque de la proposition.
+ * @param allowlistProof Preuve Merkle du créateur si l'époque restreint la création (vide sinon).
  * @returns La PublicKey du PDA de la proposition créée.
  */
 export async function createProposalOnChain(
     ctx: TestContext,
     proposerKeypair: Keypair,
     details: TokenProposalDetails,
-    epochManagementAddress: PublicKey
+    epochManagementAddress: PublicKey,
+    allowlistProof: number[][] = [],
+    proposalAddress?: PublicKey // Adresse imposée, pour un nom qui ne peut pas servir de seed côté client
 ): Promise<PublicKey> {
     const { program, treasuryAddress } = ctx;
 
@@ -70,12 +179,12 @@ export async function createProposalOnChain(
         throw new Error("TreasuryAddress non trouvé dans TestContext. Assurez-vous qu'il est initialisé.");
     }
 
-    const [proposalPda] = getProposalPda(
+    const proposalPda = proposalAddress ?? getProposalPda(
         program.programId,
         proposerKeypair.publicKey,
         details.epochId,
         details.name
-    );
+    )[0];
 
     console.log(`  [ProposalSetup] Création de la proposition "${details.name}" par ${proposerKeypair.publicKey.toBase58()} pour l'époque ${details.epochId.toString()}`);
     console.log(`  [ProposalSetup] PDA Proposition: ${proposalPda.toBase58()}`);
@@ -89,12 +198,20 @@ export async function createProposalOnChain(
                 details.imageUrl || null,
                 details.totalSupply,
                 details.creatorAllocationPercentage,
-                details.lockupPeriod
+                details.lockupPeriod,
+                allowlistProof
             )
             .accounts({
                 tokenProposal: proposalPda,
+                proposalMetadata: getProposalMetadataPda(program.programId, proposalPda)[0],
+                proposalVault: getProposalVaultPda(program.programId, proposalPda)[0],
+                creatorBond: getCreatorBondPda(program.programId, proposalPda)[0],
+                programConfig: ctx.programConfigAddress,
                 creator: proposerKeypair.publicKey,
                 epoch: epochManagementAddress,
+                epochSymbol: getEpochSymbolPda(program.programId, details.epochId, details.symbol)[0],
+                launchedSymbol: getLaunchedSymbolPda(program.programId, details.symbol)[0],
+                leaderboard: getLeaderboardPda(program.programId, details.epochId)[0],
                 treasury: treasuryAddress,
                 systemProgram: SystemProgram.programId,
             } as any)
@@ -134,6 +251,28 @@ export function getSupportPda(
     );
 }
 
+/**
+ * Calcule le PDA du support cumulé d'un wallet sur une époque (UserEpochSupport dans l'IDL).
+ * @param programId L'ID du programme.
+ * @param epochId L'ID de l'époque.
+ * @param supporterPublicKey La clé publique du supporter.
+ * @returns La PublicKey du PDA et le bump.
+ */
+export function getUserEpochSupportPda(
+    programId: PublicKey,
+    epochId: anchor.BN,
+    supporterPublicKey: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("user_epoch_support"),
+            epochId.toArrayLike(Buffer, "le", 8),
+            supporterPublicKey.toBuffer(),
+        ],
+        programId
+    );
+}
+
 /**
  * Soutient une proposition on-chain.
  * @param ctx Le contexte de test initialisé.
@@ -142,6 +281,8 @@ export function getSupportPda(
  * @param epochIdOfProposal L'ID de l'époque de la proposition à soutenir (nécessaire pour dériver le PDA de UserProposalSupport).
  * @param epochManagementAddressForProposalEpoch L'adresse PDA du compte EpochManagement pour l'époque de la proposition.
  * @param supportAmount Le montant en lamports à engager pour le soutien.
+ * @param referrer Le parrain éventuel du supporter.
+ * @param allowlistProof Preuve Merkle du supporter si l'époque a une liste d'autorisation (vide sinon).
  * @returns La PublicKey du PDA du compte UserProposalSupport créé.
  */
 export async function supportProposalOnChain(
@@ -150,7 +291,9 @@ export async function supportProposalOnChain(
     proposalToSupportPda: PublicKey,
     epochIdOfProposal: anchor.BN,
     epochManagementAddressForProposalEpoch: PublicKey,
-    supportAmount: anchor.BN
+    supportAmount: anchor.BN,
+    referrer: PublicKey | null = null,
+    allowlistProof: number[][] = []
 ): Promise<PublicKey> {
     const { program, treasuryAddress } = ctx;
 
@@ -170,13 +313,18 @@ export async function supportProposalOnChain(
 
     try {
         await program.methods
-            .supportProposal(supportAmount)
+            .supportProposal(supportAmount, allowlistProof)
             .accounts({
                 userSupport: userSupportPda,
+                userEpochSupport: getUserEpochSupportPda(program.programId, epochIdOfProposal, supporterKeypair.publicKey)[0],
+                programConfig: ctx.programConfigAddress,
                 proposal: proposalToSupportPda,
+                proposalVault: getProposalVaultPda(program.programId, proposalToSupportPda)[0],
                 user: supporterKeypair.publicKey,
                 epoch: epochManagementAddressForProposalEpoch,
+                leaderboard: getLeaderboardPda(program.programId, epochIdOfProposal)[0],
                 treasury: treasuryAddress,
+                referralAccount: referrer ? getReferralPda(program.programId, referrer)[0] : null,
                 systemProgram: SystemProgram.programId,
             } as any)
             .signers([supporterKeypair])
@@ -204,6 +352,7 @@ export async function updateProposalStatusOnChain(
     newStatus: object
 ): Promise<void> {
     console.log(`  [ProposalSetup] Attempting to update proposal ${shortenAddress(proposalPda)} status to: ${JSON.stringify(newStatus)} using epoch ${shortenAddress(epochManagementAddress)}`);
+    const epoch = await ctx.program.account.epochManagement.fetch(epochManagementAddress);
     try {
         await ctx.program.methods
             .updateProposalStatus(newStatus as any)
@@ -212,7 +361,8 @@ export async function updateProposalStatusOnChain(
                 programConfig: ctx.programConfigAddress,
                 epochManagement: epochManagementAddress,
                 proposal: proposalPda,
-            })
+                leaderboard: getLeaderboardPda(ctx.program.programId, epoch.epochId)[0],
+            } as any)
             .signers([ctx.adminKeypair])
             .rpc();
         console.log(`  [ProposalSetup] Status of proposal ${shortenAddress(proposalPda)} updated successfully to ${JSON.stringify(newStatus)}.`);
@@ -222,5 +372,26 @@ export async function updateProposalStatusOnChain(
     }
 }
 
+/**
+ * Réclame en une ou plusieurs transactions reclaim_many le support d'un utilisateur sur plusieurs propositions.
+ * @returns Les signatures des transactions envoyées.
+ */
+export async function reclaimManyOnChain(
+    ctx: TestContext,
+    userKeypair: Keypair,
+    epochId: anchor.BN,
+    proposalPdas: PublicKey[]
+): Promise<string[]> {
+    // Même découpage que le front (front/lib/reclaimMany.ts)
+    const transactions = await buildReclaimManyTransactions(ctx.program, userKeypair.publicKey, epochId, proposalPdas);
+    console.log(`  [ProposalSetup] ${proposalPdas.length} propositions regroupées en ${transactions.length} transaction(s) reclaim_many.`);
+    const signatures: string[] = [];
+    for (const tx of transactions) {
+        signatures.push(await ctx.provider.connection.sendTransaction(tx, [userKeypair]));
+        await ctx.provider.connection.confirmTransaction(signatures[signatures.length - 1], "confirmed");
+    }
+    return signatures;
+}
+
 // Fonctions de setup pour les Propositions de token
 export {}; // Pour que le fichier soit traité comme un module 
\ No newline at end of file
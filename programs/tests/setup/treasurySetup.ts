@@ -178,4 +178,48 @@ export async function ensureTreasuryRolesInitialized(
     return pda;
 }
 
-// --- Fonction à ajouter : addAdminRoleOnChain (qui sera probablement ensureAdminInTreasuryRoles) --- 
\ No newline at end of file
+// --- Fonction à ajouter : addAdminRoleOnChain (qui sera probablement ensureAdminInTreasuryRoles) --- 
+
+/**
+ * Calcule le PDA pour un compte SpendingProposal.
+ * @param programId L'ID du programme Solana.
+ * @param spendingId L'identifiant de la proposition de dépense.
+ * @returns Une paire [PublicKey, bump] pour le PDA de la proposition de dépense.
+ */
+export const getSpendingProposalPda = (programId: PublicKey, spendingId: anchor.BN): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("spending"), spendingId.toArrayLike(Buffer, "le", 8)],
+        programId
+    );
+};
+
+/**
+ * Calcule le PDA pour le compte SpendingVoteRecord d'un votant.
+ * @param programId L'ID du programme Solana.
+ * @param spendingProposalPda Le PDA de la proposition de dépense.
+ * @param voter La clé publique du votant.
+ * @returns Une paire [PublicKey, bump] pour le PDA du vote.
+ */
+export const getSpendingVoteRecordPda = (
+    programId: PublicKey,
+    spendingProposalPda: PublicKey,
+    voter: PublicKey
+): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("spending_vote"), spendingProposalPda.toBuffer(), voter.toBuffer()],
+        programId
+    );
+};
+
+/**
+ * Calcule le PDA pour un compte PaymentStream.
+ * @param programId L'ID du programme Solana.
+ * @param streamId L'identifiant du flux de paiement.
+ * @returns Une paire [PublicKey, bump] pour le PDA du flux.
+ */
+export const getPaymentStreamPda = (programId: PublicKey, streamId: anchor.BN): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("stream"), streamId.toArrayLike(Buffer, "le", 8)],
+        programId
+    );
+};
@@ -0,0 +1,183 @@
+// Construit la liste d'autorisation Merkle d'une époque à partir d'un CSV de portefeuilles.
+//
+// Usage : allowlist-tool <portefeuilles.csv> [sortie.json]
+//
+// Le CSV contient un portefeuille (base58) en première colonne de chaque ligne ; une ligne
+// d'en-tête, les lignes vides et les commentaires (#) sont ignorés, les doublons fusionnés.
+// La sortie JSON (sur stdout sans fichier de sortie) donne la racine à passer à
+// set_epoch_allowlist et la preuve de chaque portefeuille pour support_proposal / create_proposal :
+//
+// {
+//   "root": [..32 octets..],
+//   "rootHex": "…",
+//   "proofs": { "<portefeuille>": [[..32 octets..], …] }
+// }
+//
+// Le hachage est celui du programme (programs::utils::allowlist) : feuilles préfixées, paires
+// triées. Un nœud sans frère remonte tel quel au niveau supérieur.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use programs::utils::{allowlist_leaf, allowlist_node};
+
+type Hash = [u8; 32];
+
+fn parse_wallets(csv: &str) -> Result<Vec<Pubkey>, String> {
+    let mut wallets = Vec::new();
+    for (index, line) in csv.lines().enumerate() {
+        let field = line.split(',').next().unwrap_or("").trim().trim_matches('"');
+        if field.is_empty() || field.starts_with('#') {
+            continue;
+        }
+        match Pubkey::from_str(field) {
+            Ok(wallet) => wallets.push(wallet),
+            // Tolère une ligne d'en-tête
+            Err(_) if index == 0 => continue,
+            Err(_) => return Err(format!("line {}: invalid wallet `{}`", index + 1, field)),
+        }
+    }
+    wallets.sort();
+    wallets.dedup();
+    if wallets.is_empty() {
+        return Err("no wallet found in the CSV".to_string());
+    }
+    Ok(wallets)
+}
+
+// Niveaux de l'arbre, des feuilles (triées) à la racine
+fn build_levels(wallets: &[Pubkey]) -> Vec<Vec<Hash>> {
+    let mut leaves: Vec<Hash> = wallets.iter().map(allowlist_leaf).collect();
+    leaves.sort();
+    let mut levels = vec![leaves];
+    while levels.last().map_or(0, Vec::len) > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => allowlist_node(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+fn proof_for(levels: &[Vec<Hash>], leaf: &Hash) -> Vec<Hash> {
+    let mut index = levels[0].binary_search(leaf).expect("leaf must be in the tree");
+    let mut proof = Vec::new();
+    for level in &levels[..levels.len() - 1] {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            proof.push(level[sibling]);
+        }
+        index /= 2;
+    }
+    proof
+}
+
+fn json_bytes(hash: &Hash) -> String {
+    let bytes: Vec<String> = hash.iter().map(u8::to_string).collect();
+    format!("[{}]", bytes.join(","))
+}
+
+fn hex(hash: &Hash) -> String {
+    hash.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn render(root: &Hash, proofs: &BTreeMap<String, Vec<Hash>>) -> String {
+    let entries: Vec<String> = proofs
+        .iter()
+        .map(|(wallet, proof)| {
+            let hashes: Vec<String> = proof.iter().map(json_bytes).collect();
+            format!("    \"{}\": [{}]", wallet, hashes.join(", "))
+        })
+        .collect();
+    format!(
+        "{{\n  \"root\": {},\n  \"rootHex\": \"{}\",\n  \"proofs\": {{\n{}\n  }}\n}}\n",
+        json_bytes(root),
+        hex(root),
+        entries.join(",\n")
+    )
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let input = args
+        .first()
+        .ok_or("usage: allowlist-tool <wallets.csv> [output.json]")?;
+    let csv = fs::read_to_string(input).map_err(|e| format!("cannot read {}: {}", input, e))?;
+    let wallets = parse_wallets(&csv)?;
+
+    let levels = build_levels(&wallets);
+    let root = levels.last().unwrap()[0];
+    let proofs: BTreeMap<String, Vec<Hash>> = wallets
+        .iter()
+        .map(|wallet| (wallet.to_string(), proof_for(&levels, &allowlist_leaf(wallet))))
+        .collect();
+    let output = render(&root, &proofs);
+
+    match args.get(1) {
+        Some(path) => {
+            fs::write(path, output).map_err(|e| format!("cannot write {}: {}", path, e))?;
+            eprintln!("{} wallets, root {} written to {}", wallets.len(), hex(&root), path);
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use programs::utils::verify_allowlist_proof;
+
+    // Chaque portefeuille d'un arbre de `count` feuilles se vérifie avec sa preuve
+    fn assert_round_trip(count: usize) {
+        let wallets: Vec<Pubkey> = (0..count).map(|_| Pubkey::new_unique()).collect();
+        let levels = build_levels(&wallets);
+        let root = levels.last().unwrap()[0];
+
+        for wallet in &wallets {
+            let proof = proof_for(&levels, &allowlist_leaf(wallet));
+            assert!(verify_allowlist_proof(&root, wallet, &proof), "{} wallets: proof rejected", count);
+        }
+        assert!(!verify_allowlist_proof(&root, &Pubkey::new_unique(), &[]));
+    }
+
+    #[test]
+    fn single_wallet_is_its_own_root() {
+        assert_round_trip(1);
+    }
+
+    #[test]
+    fn two_wallets() {
+        assert_round_trip(2);
+    }
+
+    #[test]
+    fn three_wallets_with_an_unpaired_leaf() {
+        assert_round_trip(3);
+    }
+
+    #[test]
+    fn five_wallets_with_unpaired_nodes_on_several_levels() {
+        assert_round_trip(5);
+    }
+}